@@ -2,7 +2,7 @@
 use crate::{
     rect::Rect, resource::*, state::by_monitor::MonitorConfiguration,
     state::by_wallpaper::WallpaperInstanceSettings, state::context_menu::*, state::*,
-    system_state::*,
+    system_state::*, utils::TsUnknown,
 };
 #[cfg(test)]
 use std::{collections::HashMap, path::PathBuf};
@@ -100,11 +100,13 @@ slu_commands_declaration! {
     CreateWorkspace = create_workspace(monitor_id: MonitorId) -> WorkspaceId,
     DestroyWorkspace = destroy_workspace(workspace_id: WorkspaceId),
     RenameWorkspace = rename_workspace(workspace_id: WorkspaceId, name: Option<String>),
+    GetWorkspacesSnapshots = get_workspaces_snapshots() -> HashMap<WorkspaceId, WorkspaceSnapshot>,
 
     // wallpaper
     WallpaperNext = wallpaper_next(),
     WallpaperPrev = wallpaper_prev(),
     WallpaperSaveThumbnail = wallpaper_save_thumbnail(wallpaper_id: ResourceId, thumbnail_bytes: Vec<u8>),
+    WallpaperGetSpan = wallpaper_get_span(wallpaper_id: Option<WallpaperId>) -> WallpaperSpan,
 
     // General
     OpenFile = open_file(path: PathBuf),
@@ -156,6 +158,8 @@ slu_commands_declaration! {
     StateWriteWegItems = state_write_weg_items(items: WegItems),
     StateGetToolbarItems = state_get_toolbar_items() -> ToolbarState,
     StateWriteToolbarItems = state_write_toolbar_items(items: ToolbarState),
+    GetRenderedToolbarItems = get_rendered_toolbar_items() -> Vec<RenderedToolbarItem>,
+    GetToolbarRemoteData = get_toolbar_remote_data() -> HashMap<String, HashMap<String, TsUnknown>>,
     StateGetSettings = state_get_settings(path: Option<PathBuf>) -> Settings,
     StateWriteSettings = state_write_settings(settings: Settings),
     StateGetSettingsByApp = state_get_settings_by_app() -> Vec<AppConfig> ,
@@ -193,9 +197,16 @@ slu_commands_declaration! {
     MediaPrev = media_prev(id: String),
     MediaTogglePlayPause = media_toggle_play_pause(id: String),
     MediaNext = media_next(id: String),
+    MediaSeek = media_seek(id: String, position: i64),
+    MediaSetPlaybackRate = media_set_playback_rate(id: String, rate: f64),
+    MediaToggleShuffle = media_toggle_shuffle(id: String),
+    MediaCycleRepeatMode = media_cycle_repeat_mode(id: String),
     SetVolumeLevel = set_volume_level(device_id: String, session_id: Option<String>, level: f32),
     MediaToggleMute = media_toggle_mute(device_id: String, session_id: Option<String>),
     MediaSetDefaultDevice = media_set_default_device(id: String, role: String),
+    MediaSetAppDevice = media_set_app_device(process_id: u32, device_id: String),
+    MediaResetAppDevices = media_reset_app_devices(process_id: u32),
+    MediaApplyAudioProfile = media_apply_audio_profile(id: uuid::Uuid),
 
     // Brightness - Multi-monitor support
     GetAllMonitorsBrightness = get_all_monitors_brightness() -> Vec<MonitorBrightness>,
@@ -247,11 +258,19 @@ slu_commands_declaration! {
     GetNotifications = get_notifications() -> Vec<AppNotification>,
     NotificationsClose = notifications_close(id: u32),
     NotificationsCloseAll = notifications_close_all(),
+    GetNotificationsGrouped = get_notifications_grouped() -> Vec<NotificationAppGroup>,
+    NotificationsCloseGroup = notifications_close_group(group: NotificationGroupId),
+    NotificationsSetGroupCollapsed = notifications_set_group_collapsed(group: NotificationGroupId, collapsed: bool),
     ActivateNotification = activate_notification(
         umid: String,
         args: String,
         input_data: HashMap<String, String>,
     ),
+    NotificationsPost = notifications_post(notification: WidgetNotification) -> u32,
+    NotificationsHistory = notifications_history(query: NotificationHistoryQuery) -> Vec<AppNotification>,
+    NotificationsHistoryClear = notifications_history_clear(),
+    NotificationsGetFocusMode = notifications_get_focus_mode() -> FocusModeState,
+    NotificationsSetFocusMode = notifications_set_focus_mode(id: Option<uuid::Uuid>),
 
     // Radios
     GetRadios = get_radios() -> Vec<RadioDevice>,
@@ -262,6 +281,11 @@ slu_commands_declaration! {
     GetSystemNetwork = get_system_network() -> Vec<NetworkStatistics>,
     GetSystemMemory = get_system_memory() -> Memory,
     GetSystemCores = get_system_cores() -> Vec<Core>,
+    GetSystemMetricsHistory = get_system_metrics_history(query: MetricsHistoryQuery) -> Vec<MetricBucket>,
+    GetSystemAlerts = get_system_alerts() -> Vec<SystemAlert>,
+    GetSystemProcesses = get_system_processes(query: ProcessUsageQuery) -> Vec<AppResourceUsage>,
+    SystemEndProcessTree = system_end_process_tree(process_id: u32),
+    SystemLowerProcessPriority = system_lower_process_priority(process_id: u32),
 
     // Bluetooth
     GetBluetoothDevices = get_bluetooth_devices() -> Vec<BluetoothDevice>,
@@ -274,5 +298,11 @@ slu_commands_declaration! {
 
     // Start Menu
     GetStartMenuItems = get_start_menu_items() -> Vec<StartMenuItem>,
+    StartMenuSearch = start_menu_search(query: String, limit: Option<usize>) -> Vec<StartMenuItem>,
+    StartMenuSetItemAliases = start_menu_set_item_aliases(item: StartMenuItem, aliases: Vec<String>),
     GetNativeStartMenu = get_native_start_menu() -> StartMenuLayout,
+
+    // Launcher
+    LauncherQuery = launcher_query(query: String, limit: Option<usize>) -> Vec<LauncherResult>,
+    LauncherExecute = launcher_execute(action: LauncherAction),
 }
@@ -5,9 +5,11 @@ export enum SeelenCommand {
   CreateWorkspace = "create_workspace",
   DestroyWorkspace = "destroy_workspace",
   RenameWorkspace = "rename_workspace",
+  GetWorkspacesSnapshots = "get_workspaces_snapshots",
   WallpaperNext = "wallpaper_next",
   WallpaperPrev = "wallpaper_prev",
   WallpaperSaveThumbnail = "wallpaper_save_thumbnail",
+  WallpaperGetSpan = "wallpaper_get_span",
   OpenFile = "open_file",
   SelectFileOnExplorer = "select_file_on_explorer",
   Run = "run",
@@ -40,6 +42,8 @@ export enum SeelenCommand {
   StateWriteWegItems = "state_write_weg_items",
   StateGetToolbarItems = "state_get_toolbar_items",
   StateWriteToolbarItems = "state_write_toolbar_items",
+  GetRenderedToolbarItems = "get_rendered_toolbar_items",
+  GetToolbarRemoteData = "get_toolbar_remote_data",
   StateGetSettings = "state_get_settings",
   StateWriteSettings = "state_write_settings",
   StateGetSettingsByApp = "state_get_settings_by_app",
@@ -69,9 +73,16 @@ export enum SeelenCommand {
   MediaPrev = "media_prev",
   MediaTogglePlayPause = "media_toggle_play_pause",
   MediaNext = "media_next",
+  MediaSeek = "media_seek",
+  MediaSetPlaybackRate = "media_set_playback_rate",
+  MediaToggleShuffle = "media_toggle_shuffle",
+  MediaCycleRepeatMode = "media_cycle_repeat_mode",
   SetVolumeLevel = "set_volume_level",
   MediaToggleMute = "media_toggle_mute",
   MediaSetDefaultDevice = "media_set_default_device",
+  MediaSetAppDevice = "media_set_app_device",
+  MediaResetAppDevices = "media_reset_app_devices",
+  MediaApplyAudioProfile = "media_apply_audio_profile",
   GetAllMonitorsBrightness = "get_all_monitors_brightness",
   SetMonitorBrightness = "set_monitor_brightness",
   GetPowerStatus = "get_power_status",
@@ -107,13 +118,26 @@ export enum SeelenCommand {
   GetNotifications = "get_notifications",
   NotificationsClose = "notifications_close",
   NotificationsCloseAll = "notifications_close_all",
+  GetNotificationsGrouped = "get_notifications_grouped",
+  NotificationsCloseGroup = "notifications_close_group",
+  NotificationsSetGroupCollapsed = "notifications_set_group_collapsed",
   ActivateNotification = "activate_notification",
+  NotificationsPost = "notifications_post",
+  NotificationsHistory = "notifications_history",
+  NotificationsHistoryClear = "notifications_history_clear",
+  NotificationsGetFocusMode = "notifications_get_focus_mode",
+  NotificationsSetFocusMode = "notifications_set_focus_mode",
   GetRadios = "get_radios",
   SetRadioState = "set_radios_state",
   GetSystemDisks = "get_system_disks",
   GetSystemNetwork = "get_system_network",
   GetSystemMemory = "get_system_memory",
   GetSystemCores = "get_system_cores",
+  GetSystemMetricsHistory = "get_system_metrics_history",
+  GetSystemAlerts = "get_system_alerts",
+  GetSystemProcesses = "get_system_processes",
+  SystemEndProcessTree = "system_end_process_tree",
+  SystemLowerProcessPriority = "system_lower_process_priority",
   GetBluetoothDevices = "get_bluetooth_devices",
   StartBluetoothScanning = "start_bluetooth_scanning",
   StopBluetoothScanning = "stop_bluetooth_scanning",
@@ -122,5 +146,9 @@ export enum SeelenCommand {
   DisconnectBluetoothDevice = "disconnect_bluetooth_device",
   ForgetBluetoothDevice = "forget_bluetooth_device",
   GetStartMenuItems = "get_start_menu_items",
+  StartMenuSearch = "start_menu_search",
+  StartMenuSetItemAliases = "start_menu_set_item_aliases",
   GetNativeStartMenu = "get_native_start_menu",
+  LauncherQuery = "launcher_query",
+  LauncherExecute = "launcher_execute",
 }
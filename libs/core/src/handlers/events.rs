@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::state::*;
 use crate::system_state::*;
+use crate::utils::TsUnknown;
 
 macro_rules! slu_events_declaration {
     ($($name:ident$(($payload:ty))? as $value:literal,)*) => {
@@ -44,6 +45,7 @@ macro_rules! slu_events_declaration {
 
 slu_events_declaration! {
     VirtualDesktopsChanged(VirtualDesktops) as "virtual-desktops::changed",
+    VirtualDesktopsSwitched(WorkspaceSwitch) as "virtual-desktops::switched",
 
     GlobalFocusChanged(FocusedApp) as "global-focus-changed",
     GlobalMouseMove([i32; 2]) as "global-mouse-move",
@@ -70,6 +72,9 @@ slu_events_declaration! {
     NetworkWlanScanned(Vec<WlanBssEntry>) as "wlan-scanned",
 
     Notifications(Vec<AppNotification>) as "notifications",
+    NotificationsGrouped(Vec<NotificationAppGroup>) as "notifications::grouped",
+    NotificationsFocusModeChanged(FocusModeState) as "notifications::focus-mode-changed",
+    NotificationsWidgetActionActivated(WidgetNotificationActivated) as "notifications::widget-action-activated",
 
     PowerStatus(PowerStatus) as "power-status",
     PowerMode(PowerMode) as "power-mode",
@@ -83,6 +88,9 @@ slu_events_declaration! {
 
     PopupContentChanged(SluPopupConfig) as "popup-content-changed",
 
+    ToolbarItemsRendered(Vec<RenderedToolbarItem>) as "toolbar::items-rendered",
+    ToolbarRemoteDataChanged(HashMap<String, HashMap<String, TsUnknown>>) as "toolbar::remote-data-changed",
+
     StateSettingsChanged(Settings) as "settings-changed",
     StateWegItemsChanged as "weg-items",
     StateThemesChanged(Vec<Theme>) as "themes",
@@ -98,6 +106,8 @@ slu_events_declaration! {
     StatePerformanceModeChanged(PerformanceMode) as "state::performance-mode-changed",
 
     WidgetTriggered(WidgetTriggerPayload) as "widget::triggered",
+    WidgetCssChanged(String) as "widget::css-changed",
+    WidgetHotReloadFailed(WidgetReloadError) as "widget::hot-reload-failed",
 
     // Radios
     RadiosChanged(Vec<RadioDevice>) as "radio::changed",
@@ -107,6 +117,7 @@ slu_events_declaration! {
     SystemNetworkChanged(Vec<NetworkStatistics>) as "system::network-changed",
     SystemMemoryChanged(Memory) as "system::memory-changed",
     SystemCoresChanged(Vec<Core>) as "system::cores-changed",
+    SystemAlertsChanged(Vec<SystemAlert>) as "system::alerts-changed",
 
     BluetoothDevicesChanged(Vec<BluetoothDevice>) as "bluetooth-devices-changed",
 
@@ -1,6 +1,7 @@
 // This file was generated via rust macros. Don't modify manually.
 export enum SeelenEvent {
   VirtualDesktopsChanged = "virtual-desktops::changed",
+  VirtualDesktopsSwitched = "virtual-desktops::switched",
   GlobalFocusChanged = "global-focus-changed",
   GlobalMouseMove = "global-mouse-move",
   HandleLayeredHitboxes = "handle-layered",
@@ -20,6 +21,9 @@ export enum SeelenEvent {
   NetworkInternetConnection = "network-internet-connection",
   NetworkWlanScanned = "wlan-scanned",
   Notifications = "notifications",
+  NotificationsGrouped = "notifications::grouped",
+  NotificationsFocusModeChanged = "notifications::focus-mode-changed",
+  NotificationsWidgetActionActivated = "notifications::widget-action-activated",
   PowerStatus = "power-status",
   PowerMode = "power-mode",
   BatteriesStatus = "batteries-status",
@@ -28,6 +32,8 @@ export enum SeelenEvent {
   WMForceRetiling = "wm::force-retiling",
   WMTreeChanged = "wm::tree-changed",
   PopupContentChanged = "popup-content-changed",
+  ToolbarItemsRendered = "toolbar::items-rendered",
+  ToolbarRemoteDataChanged = "toolbar::remote-data-changed",
   StateSettingsChanged = "settings-changed",
   StateWegItemsChanged = "weg-items",
   StateThemesChanged = "themes",
@@ -39,11 +45,14 @@ export enum SeelenEvent {
   SystemTrayChanged = "system-tray::changed",
   StatePerformanceModeChanged = "state::performance-mode-changed",
   WidgetTriggered = "widget::triggered",
+  WidgetCssChanged = "widget::css-changed",
+  WidgetHotReloadFailed = "widget::hot-reload-failed",
   RadiosChanged = "radio::changed",
   SystemDisksChanged = "system::disks-changed",
   SystemNetworkChanged = "system::network-changed",
   SystemMemoryChanged = "system::memory-changed",
   SystemCoresChanged = "system::cores-changed",
+  SystemAlertsChanged = "system::alerts-changed",
   BluetoothDevicesChanged = "bluetooth-devices-changed",
   StartMenuItemsChanged = "start-menu::items-changed",
 }
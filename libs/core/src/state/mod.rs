@@ -2,8 +2,11 @@ mod icon_pack;
 mod placeholder;
 mod plugin;
 mod popups;
+mod remote_data;
 mod settings;
 mod theme;
+#[cfg(feature = "js-engine")]
+mod toolbar_engine;
 mod wallpaper;
 mod weg_items;
 mod widget;
@@ -14,8 +17,11 @@ pub use icon_pack::*;
 pub use placeholder::*;
 pub use plugin::*;
 pub use popups::*;
+pub use remote_data::*;
 pub use settings::*;
 pub use theme::*;
+#[cfg(feature = "js-engine")]
+pub use toolbar_engine::*;
 pub use wallpaper::*;
 pub use weg_items::*;
 pub use widget::*;
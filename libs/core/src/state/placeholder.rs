@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use url::Url;
 
-use crate::{resource::PluginId, utils::TsUnknown};
+use crate::{handlers::SeelenEvent, resource::PluginId, utils::TsUnknown};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, TS)]
 #[ts(repr(enum = name))]
@@ -26,7 +26,39 @@ pub enum ToolbarJsScope {
     Cpu,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
+impl ToolbarJsScope {
+    /// Scopes whose data is changed by the event, `Date` also changes with the time.
+    pub fn changed_by(event: &str) -> &'static [ToolbarJsScope] {
+        match event {
+            SeelenEvent::StateSettingsChanged => &[Self::Date, Self::User],
+            SeelenEvent::Notifications => &[Self::Notifications],
+            SeelenEvent::MediaSessions
+            | SeelenEvent::MediaDevices
+            | SeelenEvent::MediaInputs
+            | SeelenEvent::MediaOutputs => &[Self::Media],
+            SeelenEvent::NetworkAdapters
+            | SeelenEvent::NetworkDefaultLocalIp
+            | SeelenEvent::NetworkInternetConnection => &[Self::Network],
+            SeelenEvent::SystemLanguagesChanged => &[Self::Keyboard],
+            SeelenEvent::UserChanged => &[Self::User],
+            SeelenEvent::BluetoothDevicesChanged => &[Self::Bluetooth],
+            SeelenEvent::PowerStatus | SeelenEvent::PowerMode | SeelenEvent::BatteriesStatus => {
+                &[Self::Power]
+            }
+            SeelenEvent::GlobalFocusChanged => &[Self::FocusedApp],
+            SeelenEvent::VirtualDesktopsChanged | SeelenEvent::VirtualDesktopsSwitched => {
+                &[Self::Workspaces]
+            }
+            SeelenEvent::SystemDisksChanged => &[Self::Disk],
+            SeelenEvent::SystemNetworkChanged => &[Self::NetworkStatistics],
+            SeelenEvent::SystemMemoryChanged => &[Self::Memory],
+            SeelenEvent::SystemCoresChanged => &[Self::Cpu],
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(default, rename_all = "camelCase")]
 pub struct ToolbarItem {
     /// Id to identify the item, should be unique. Preferably a uuid.
@@ -46,17 +78,40 @@ pub struct ToolbarItem {
     pub style: HashMap<String, Option<StyleValue>>,
     /// Remote data to be added to the item scope.
     pub remote_data: HashMap<String, RemoteDataDeclaration>,
+    /// Evaluate the template, tooltip and badge on the background instead of the toolbar webview.
+    /// `onClick` is still evaluated on the webview, as it needs the webview apis.
+    pub background_evaluation: bool,
+    /// Max milliseconds that each evaluation on the background can take, defaults to 100 and
+    /// is limited to 1000.
+    pub evaluation_timeout_ms: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+/// Content of an item evaluated on the background.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct RenderedToolbarItem {
+    pub id: String,
+    pub template: TsUnknown,
+    pub tooltip: Option<TsUnknown>,
+    pub badge: Option<TsUnknown>,
+    /// errors of the last evaluation, e.g. exceptions or timeouts
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteDataDeclaration {
-    url: Url,
-    request_init: Option<TsUnknown>,
-    update_interval_seconds: Option<u32>,
+    pub url: Url,
+    /// Same interface as the `RequestInit` of the fetch api, only `method`, `headers` and `body` are used.\
+    /// Allowed methods are `GET`, `HEAD` and `POST`, and only the CORS-safelisted headers are allowed.\
+    /// Local network hosts can only be requested by bundled items.
+    pub request_init: Option<TsUnknown>,
+    /// Seconds between fetches, the data is fetched only once if not set.
+    pub update_interval_seconds: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(untagged)]
 pub enum StyleValue {
     String(String),
@@ -3,6 +3,7 @@ use schemars::JsonSchema;
 use crate::{
     resource::WidgetId,
     state::{ToolbarItem, WindowManagerLayout},
+    system_state::LauncherKeywordProvider,
     utils::TsUnknown,
 };
 
@@ -13,6 +14,8 @@ pub enum KnownPlugin {
     FacyToolbar(ToolbarItem),
     #[serde(rename = "@seelen/window-manager")]
     WManager(WindowManagerLayout),
+    #[serde(rename = "@seelen/launcher")]
+    Launcher(LauncherKeywordProvider),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
@@ -0,0 +1,629 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use serde_json::Value;
+use url::{Host, Url};
+
+use super::RemoteDataDeclaration;
+
+/// Delay before retrying a failed fetch, doubled on each consecutive failure.
+const BACKOFF_BASE_MILLIS: i64 = 5_000;
+const BACKOFF_MAX_MILLIS: i64 = 30 * 60 * 1000;
+
+/// Request to be sent for a declaration, built from its `request_init`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteDataRequest {
+    pub url: Url,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// Only bundled items can request loopback and private network hosts.
+    pub local_network: bool,
+}
+
+impl RemoteDataRequest {
+    /// Requests are sent from the background, bypassing the CORS policy of the webviews,
+    /// so only the methods a browser sends cross-origin without a preflight are allowed.
+    pub const ALLOWED_METHODS: &[&str] = &["GET", "HEAD", "POST"];
+    /// Same as the methods, only the CORS-safelisted request headers are allowed.
+    pub const ALLOWED_HEADERS: &[&str] = &[
+        "accept",
+        "accept-language",
+        "content-language",
+        "content-type",
+        "range",
+    ];
+    /// Content types a form can send, the only ones not needing a preflight.
+    const ALLOWED_CONTENT_TYPES: &[&str] = &[
+        "application/x-www-form-urlencoded",
+        "multipart/form-data",
+        "text/plain",
+    ];
+    const MAX_HEADER_VALUE_LEN: usize = 128;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !Self::ALLOWED_METHODS.contains(&self.method.as_str()) {
+            return Err(format!("method {} is not allowed", self.method));
+        }
+        for (name, value) in &self.headers {
+            if !is_safelisted_header(name, value) {
+                return Err(format!("header {name} is not allowed"));
+            }
+        }
+        if !self.local_network && is_local_url(&self.url) {
+            return Err(format!(
+                "host {} is not allowed",
+                self.url.host_str().unwrap_or("")
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn is_safelisted_header(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if !RemoteDataRequest::ALLOWED_HEADERS.contains(&name.as_str())
+        || value.len() > RemoteDataRequest::MAX_HEADER_VALUE_LEN
+    {
+        return false;
+    }
+
+    match name.as_str() {
+        "content-type" => {
+            let essence = value.split(';').next().unwrap_or_default().trim();
+            RemoteDataRequest::ALLOWED_CONTENT_TYPES
+                .iter()
+                .any(|allowed| essence.eq_ignore_ascii_case(allowed))
+        }
+        // a single range of bytes, as `bytes=0-` or `bytes=0-99`
+        "range" => value
+            .strip_prefix("bytes=")
+            .and_then(|range| range.split_once('-'))
+            .is_some_and(|(start, end)| {
+                !start.is_empty()
+                    && start.bytes().all(|b| b.is_ascii_digit())
+                    && end.bytes().all(|b| b.is_ascii_digit())
+            }),
+        _ => true,
+    }
+}
+
+/// Loopback, private, link-local and other addresses not reachable from the internet.
+pub fn is_local_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // shared address space (carrier-grade NAT)
+                || (a == 100 && (64..128).contains(&b))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_local_address(IpAddr::V4(ip)),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+            }
+        },
+    }
+}
+
+/// Urls on a local address or a domain reserved to the local network.\
+/// Domains resolving to local addresses are only known on resolving them, see [`is_local_address`].
+pub fn is_local_url(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost") || domain.ends_with(".local")
+        }
+        Some(Host::Ipv4(ip)) => is_local_address(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_local_address(IpAddr::V6(ip)),
+        None => false,
+    }
+}
+
+/// Relevant parts of the http response of a remote data request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteDataResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+impl RemoteDataResponse {
+    /// Same as the webview, json responses are parsed and the rest are handled as text.
+    pub fn data(&self) -> Value {
+        let is_json = self
+            .content_type
+            .as_deref()
+            .is_some_and(|c| c.contains("application/json"));
+        if is_json {
+            if let Ok(data) = serde_json::from_str(&self.body) {
+                return data;
+            }
+        }
+        Value::String(self.body.clone())
+    }
+}
+
+impl RemoteDataDeclaration {
+    /// Declarations with the same key are fetched only once and share the response.
+    pub fn source_key(&self) -> String {
+        let init = self.request_init.as_ref().map(|i| i.0.to_string());
+        format!("{}|{}", self.url, init.unwrap_or_default())
+    }
+
+    pub fn request(&self) -> RemoteDataRequest {
+        let init = self.request_init.as_ref().map(|i| &i.0);
+        let field = |name: &str| init.and_then(|i| i.get(name));
+
+        let headers = match field("headers") {
+            Some(Value::Object(headers)) => headers
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect(),
+            // array of [name, value] pairs
+            Some(Value::Array(headers)) => headers
+                .iter()
+                .filter_map(|pair| {
+                    let pair = pair.as_array()?;
+                    Some((
+                        pair.first()?.as_str()?.to_string(),
+                        pair.get(1)?.as_str()?.to_string(),
+                    ))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        RemoteDataRequest {
+            url: self.url.clone(),
+            method: field("method")
+                .and_then(Value::as_str)
+                .unwrap_or("GET")
+                .to_uppercase(),
+            headers,
+            body: field("body").and_then(Value::as_str).map(str::to_string),
+            local_network: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoteDataSource {
+    request: RemoteDataRequest,
+    /// shortest interval of the declarations sharing this source
+    interval_millis: Option<i64>,
+    data: Option<Value>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    failures: u32,
+    /// None when there is nothing more to fetch or while a fetch is in flight
+    next_fetch: Option<i64>,
+    in_flight: bool,
+    last_error: Option<String>,
+}
+
+/// Decides when each remote data source should be fetched and keeps the last responses.
+/// Time is given as unix timestamp in milliseconds, so the scheduling can be tested.
+#[derive(Debug, Default)]
+pub struct RemoteDataScheduler {
+    sources: HashMap<String, RemoteDataSource>,
+}
+
+impl RemoteDataScheduler {
+    /// Adds the new declarations and forgets the sources no longer declared.\
+    /// Declarations are paired with a flag telling if they come from a bundled item,
+    /// sources declared by a bundled item can request local network hosts.
+    pub fn sync<'a>(
+        &mut self,
+        declarations: impl IntoIterator<Item = (&'a RemoteDataDeclaration, bool)>,
+    ) {
+        let mut intervals: HashMap<String, (RemoteDataRequest, Option<i64>)> = HashMap::new();
+        for (declaration, bundled) in declarations {
+            let interval = declaration
+                .update_interval_seconds
+                .filter(|secs| *secs > 0)
+                .map(|secs| secs as i64 * 1000);
+            let entry = intervals
+                .entry(declaration.source_key())
+                .or_insert_with(|| (declaration.request(), interval));
+            entry.0.local_network |= bundled;
+            entry.1 = match (entry.1, interval) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+        }
+
+        self.sources.retain(|key, _| intervals.contains_key(key));
+        for (key, (request, interval_millis)) in intervals {
+            let local_network = request.local_network;
+            let source = self.sources.entry(key).or_insert_with(|| RemoteDataSource {
+                request,
+                interval_millis,
+                data: None,
+                etag: None,
+                last_modified: None,
+                failures: 0,
+                next_fetch: Some(0),
+                in_flight: false,
+                last_error: None,
+            });
+            // the bundled items declaring the source can change between syncs
+            source.request.local_network = local_network;
+            if let Err(err) = source.request.validate() {
+                source.last_error = Some(err);
+            }
+            if source.interval_millis != interval_millis {
+                // a newly added interval should start from now, in flight fetches are
+                // rescheduled with the new interval when they finish
+                if source.next_fetch.is_none() && !source.in_flight {
+                    source.next_fetch = Some(0);
+                }
+                source.interval_millis = interval_millis;
+            }
+        }
+    }
+
+    /// Requests that should be sent now, with the conditional headers of the previous response.
+    pub fn due(&self, now: i64) -> Vec<(String, RemoteDataRequest)> {
+        self.sources
+            .iter()
+            .filter(|(_, source)| {
+                source.request.validate().is_ok()
+                    && source.next_fetch.is_some_and(|next| next <= now)
+            })
+            .map(|(key, source)| {
+                let mut request = source.request.clone();
+                if let Some(etag) = &source.etag {
+                    request
+                        .headers
+                        .push(("If-None-Match".to_string(), etag.clone()));
+                }
+                if let Some(last_modified) = &source.last_modified {
+                    request
+                        .headers
+                        .push(("If-Modified-Since".to_string(), last_modified.clone()));
+                }
+                (key.clone(), request)
+            })
+            .collect()
+    }
+
+    /// Marks the source as in flight so it is not returned again by `due` until it finishes.
+    pub fn start(&mut self, key: &str) {
+        if let Some(source) = self.sources.get_mut(key) {
+            source.next_fetch = None;
+            source.in_flight = true;
+        }
+    }
+
+    /// Stores the result of a fetch, returns true if the data changed.
+    pub fn finish(
+        &mut self,
+        key: &str,
+        result: Result<RemoteDataResponse, String>,
+        now: i64,
+    ) -> bool {
+        let Some(source) = self.sources.get_mut(key) else {
+            return false; // removed while in flight
+        };
+        source.in_flight = false;
+
+        let result = result.and_then(|response| match response.status {
+            200..=299 | 304 => Ok(response),
+            status => Err(format!("unexpected status {status}")),
+        });
+
+        match result {
+            Ok(response) => {
+                source.failures = 0;
+                source.last_error = None;
+                source.next_fetch = source.interval_millis.map(|interval| now + interval);
+                if response.status == 304 {
+                    return false;
+                }
+
+                source.etag = response.etag.clone();
+                source.last_modified = response.last_modified.clone();
+                let data = Some(response.data());
+                let changed = source.data != data;
+                source.data = data;
+                changed
+            }
+            Err(err) => {
+                source.failures += 1;
+                let backoff = BACKOFF_BASE_MILLIS
+                    .saturating_mul(1 << (source.failures - 1).min(20))
+                    .min(BACKOFF_MAX_MILLIS);
+                source.next_fetch = Some(now + backoff);
+                source.last_error = Some(err);
+                false
+            }
+        }
+    }
+
+    pub fn data(&self, declaration: &RemoteDataDeclaration) -> Option<&Value> {
+        self.sources.get(&declaration.source_key())?.data.as_ref()
+    }
+
+    /// Error of the last fetch of the declaration, cleared on success.
+    pub fn error(&self, declaration: &RemoteDataDeclaration) -> Option<&str> {
+        self.sources
+            .get(&declaration.source_key())?
+            .last_error
+            .as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::TsUnknown;
+
+    const SEC: i64 = 1000;
+
+    fn declaration(url: &str, interval: Option<u32>) -> RemoteDataDeclaration {
+        RemoteDataDeclaration {
+            url: Url::parse(url).unwrap(),
+            request_init: None,
+            update_interval_seconds: interval,
+        }
+    }
+
+    /// Local stub of the remote server, answers like a server supporting etags would.
+    struct StubServer {
+        body: String,
+        etag: String,
+        fail: bool,
+        requests: Vec<RemoteDataRequest>,
+    }
+
+    impl StubServer {
+        fn new(body: &str) -> Self {
+            Self {
+                body: body.to_string(),
+                etag: "\"v1\"".to_string(),
+                fail: false,
+                requests: Vec::new(),
+            }
+        }
+
+        fn handle(&mut self, request: RemoteDataRequest) -> Result<RemoteDataResponse, String> {
+            let if_none_match = request
+                .headers
+                .iter()
+                .find(|(name, _)| name == "If-None-Match")
+                .map(|(_, value)| value.clone());
+            self.requests.push(request);
+
+            if self.fail {
+                return Ok(RemoteDataResponse {
+                    status: 503,
+                    ..Default::default()
+                });
+            }
+            if if_none_match.as_ref() == Some(&self.etag) {
+                return Ok(RemoteDataResponse {
+                    status: 304,
+                    ..Default::default()
+                });
+            }
+            Ok(RemoteDataResponse {
+                status: 200,
+                content_type: Some("application/json; charset=utf-8".to_string()),
+                etag: Some(self.etag.clone()),
+                last_modified: None,
+                body: self.body.clone(),
+            })
+        }
+    }
+
+    /// Fetches everything due at `now`, returns true if some data changed.
+    fn tick(scheduler: &mut RemoteDataScheduler, server: &mut StubServer, now: i64) -> bool {
+        let mut changed = false;
+        for (key, request) in scheduler.due(now) {
+            scheduler.start(&key);
+            changed |= scheduler.finish(&key, server.handle(request), now);
+        }
+        changed
+    }
+
+    #[test]
+    fn test_request_from_init() {
+        let mut declaration = declaration("https://example.com/api", None);
+        declaration.request_init = Some(TsUnknown(json!({
+            "method": "post",
+            "headers": { "Authorization": "Bearer x" },
+            "body": "{}",
+        })));
+        let request = declaration.request();
+        assert_eq!(request.method, "POST");
+        assert_eq!(
+            request.headers,
+            vec![("Authorization".to_string(), "Bearer x".to_string())]
+        );
+        assert_eq!(request.body.as_deref(), Some("{}"));
+
+        let response = RemoteDataResponse {
+            status: 200,
+            content_type: Some("text/plain".to_string()),
+            body: "{}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(response.data(), json!("{}"));
+    }
+
+    #[test]
+    fn test_shared_sources_and_conditional_requests() {
+        let mut scheduler = RemoteDataScheduler::default();
+        let mut server = StubServer::new(r#"{ "temp": 20 }"#);
+        let a = declaration("https://example.com/weather", Some(60));
+        let b = declaration("https://example.com/weather", Some(30));
+        scheduler.sync([(&a, false), (&b, false)]);
+
+        assert!(tick(&mut scheduler, &mut server, 0));
+        assert_eq!(server.requests.len(), 1, "same url is fetched once");
+        assert_eq!(scheduler.data(&a), Some(&json!({ "temp": 20 })));
+        assert_eq!(scheduler.data(&a), scheduler.data(&b));
+
+        // the shortest interval is used
+        assert!(!tick(&mut scheduler, &mut server, 29 * SEC));
+        assert_eq!(server.requests.len(), 1);
+        assert!(!tick(&mut scheduler, &mut server, 30 * SEC));
+        assert_eq!(server.requests.len(), 2);
+        assert!(server.requests[1]
+            .headers
+            .contains(&("If-None-Match".to_string(), "\"v1\"".to_string())));
+        assert_eq!(scheduler.data(&a), Some(&json!({ "temp": 20 })));
+
+        server.body = r#"{ "temp": 25 }"#.to_string();
+        server.etag = "\"v2\"".to_string();
+        assert!(tick(&mut scheduler, &mut server, 60 * SEC));
+        assert_eq!(scheduler.data(&b), Some(&json!({ "temp": 25 })));
+
+        // without declarations the source is forgotten
+        scheduler.sync([]);
+        assert!(scheduler.due(1000 * SEC).is_empty());
+        assert_eq!(scheduler.data(&a), None);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut scheduler = RemoteDataScheduler::default();
+        let mut server = StubServer::new("ok");
+        server.fail = true;
+        let once = declaration("https://example.com/once", None);
+        scheduler.sync([(&once, false)]);
+
+        let mut fetched_at = Vec::new();
+        for secs in 0..=40 {
+            let before = server.requests.len();
+            tick(&mut scheduler, &mut server, secs * SEC);
+            if server.requests.len() > before {
+                fetched_at.push(secs);
+            }
+        }
+        // 5s, 10s, 20s between retries
+        assert_eq!(fetched_at, vec![0, 5, 15, 35]);
+        assert_eq!(scheduler.error(&once), Some("unexpected status 503"));
+
+        server.fail = false;
+        tick(&mut scheduler, &mut server, 75 * SEC);
+        assert_eq!(scheduler.data(&once), Some(&json!("ok")));
+        assert_eq!(scheduler.error(&once), None);
+
+        // without interval it is not fetched again
+        let requests = server.requests.len();
+        tick(&mut scheduler, &mut server, 1000 * SEC);
+        assert_eq!(server.requests.len(), requests);
+    }
+
+    #[test]
+    fn test_interval_change_while_in_flight() {
+        let mut scheduler = RemoteDataScheduler::default();
+        let mut server = StubServer::new("ok");
+        let a = declaration("https://example.com/data", Some(60));
+        scheduler.sync([(&a, false)]);
+
+        let due = scheduler.due(0);
+        assert_eq!(due.len(), 1);
+        let (key, request) = due.into_iter().next().unwrap();
+        scheduler.start(&key);
+
+        // a new interval while in flight doesn't schedule a duplicated fetch
+        let b = declaration("https://example.com/data", Some(30));
+        scheduler.sync([(&a, false), (&b, false)]);
+        assert!(scheduler.due(0).is_empty());
+
+        // the new interval is used once the fetch finishes
+        assert!(scheduler.finish(&key, server.handle(request), 0));
+        assert!(scheduler.due(29 * SEC).is_empty());
+        assert_eq!(scheduler.due(30 * SEC).len(), 1);
+    }
+
+    #[test]
+    fn test_disallowed_methods() {
+        let mut declaration = declaration("https://example.com/api", None);
+        declaration.request_init = Some(TsUnknown(json!({ "method": "delete" })));
+        let mut scheduler = RemoteDataScheduler::default();
+        scheduler.sync([(&declaration, false)]);
+        assert!(scheduler.due(0).is_empty());
+        assert_eq!(
+            scheduler.error(&declaration),
+            Some("method DELETE is not allowed")
+        );
+    }
+
+    #[test]
+    fn test_disallowed_headers() {
+        let request = |headers: serde_json::Value| {
+            let mut declaration = declaration("https://example.com/api", None);
+            declaration.request_init = Some(TsUnknown(json!({ "headers": headers })));
+            declaration.request().validate()
+        };
+
+        assert_eq!(
+            request(json!({ "Accept": "application/json", "Range": "bytes=0-99" })),
+            Ok(())
+        );
+        assert_eq!(
+            request(json!({ "Content-Type": "text/plain; charset=utf-8" })),
+            Ok(())
+        );
+        assert_eq!(
+            request(json!({ "Authorization": "Bearer x" })),
+            Err("header Authorization is not allowed".to_string())
+        );
+        assert_eq!(
+            request(json!([["content-type", "application/json"]])),
+            Err("header content-type is not allowed".to_string())
+        );
+        assert_eq!(
+            request(json!({ "Range": "bytes=0-9, 20-29" })),
+            Err("header Range is not allowed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_local_network_hosts() {
+        for url in [
+            "http://localhost:8080/api",
+            "http://printer.local/status",
+            "http://127.0.0.1/",
+            "http://10.0.0.1/",
+            "http://192.168.1.1/",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://[fd00::1]/",
+        ] {
+            assert!(is_local_url(&Url::parse(url).unwrap()), "{url}");
+        }
+        assert!(!is_local_url(&Url::parse("https://example.com").unwrap()));
+        assert!(!is_local_url(&Url::parse("http://8.8.8.8").unwrap()));
+
+        let local = declaration("http://127.0.0.1:8080/api", None);
+        let mut scheduler = RemoteDataScheduler::default();
+        scheduler.sync([(&local, false)]);
+        assert!(scheduler.due(0).is_empty());
+        assert_eq!(
+            scheduler.error(&local),
+            Some("host 127.0.0.1 is not allowed")
+        );
+
+        // allowed if any of the items declaring it is bundled
+        scheduler.sync([(&local, false), (&local, true)]);
+        let due = scheduler.due(0);
+        assert_eq!(due.len(), 1);
+        assert!(due[0].1.local_network);
+    }
+}
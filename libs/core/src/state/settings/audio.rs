@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::system_state::MediaDeviceType;
+
+/// Preferred devices of an app by device id, None means the system default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppAudioRouting {
+    #[ts(optional = nullable)]
+    pub output: Option<String>,
+    #[ts(optional = nullable)]
+    pub input: Option<String>,
+}
+
+impl AppAudioRouting {
+    pub fn is_empty(&self) -> bool {
+        self.output.is_none() && self.input.is_none()
+    }
+
+    pub fn device(&self, kind: MediaDeviceType) -> Option<&String> {
+        match kind {
+            MediaDeviceType::Output => self.output.as_ref(),
+            MediaDeviceType::Input => self.input.as_ref(),
+        }
+    }
+}
+
+/// Audio routing of the apps by executable name, e.g: `spotify.exe`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(transparent)]
+pub struct AppsAudioRouting(pub HashMap<String, AppAudioRouting>);
+
+impl AppsAudioRouting {
+    fn key(exe: &str) -> String {
+        exe.trim().to_lowercase()
+    }
+
+    pub fn get(&self, exe: &str) -> Option<&AppAudioRouting> {
+        self.0.get(&Self::key(exe))
+    }
+
+    /// Sets the preferred device of the app, None resets it to the system default.
+    pub fn set(&mut self, exe: &str, kind: MediaDeviceType, device_id: Option<String>) {
+        let key = Self::key(exe);
+        let routing = self.0.entry(key.clone()).or_default();
+        match kind {
+            MediaDeviceType::Output => routing.output = device_id,
+            MediaDeviceType::Input => routing.input = device_id,
+        }
+        if routing.is_empty() {
+            self.0.remove(&key);
+        }
+    }
+
+    pub fn remove(&mut self, exe: &str) -> Option<AppAudioRouting> {
+        self.0.remove(&Self::key(exe))
+    }
+}
+
+/// Volume and mute to be applied, None keeps the current value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AudioLevel {
+    /// between 0 and 1
+    #[ts(optional = nullable)]
+    pub volume: Option<f32>,
+    #[ts(optional = nullable)]
+    pub muted: Option<bool>,
+}
+
+/// Named set of audio devices and volumes, e.g: "Headset" or "Speakers".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioProfile {
+    pub id: Uuid,
+    pub name: String,
+    /// default output device for multimedia, by device id
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub default_output: Option<String>,
+    /// default input device for multimedia, by device id
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub default_input: Option<String>,
+    /// default output device for communications, by device id
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub communications_output: Option<String>,
+    /// default input device for communications, by device id
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub communications_input: Option<String>,
+    /// volume and mute by device id
+    #[serde(default)]
+    pub devices: HashMap<String, AudioLevel>,
+    /// volume and mute of the apps sessions by executable name
+    #[serde(default)]
+    pub apps: HashMap<String, AudioLevel>,
+}
+
+impl AudioProfile {
+    /// Level override of the app, executable names are case insensitive.
+    pub fn app_level(&self, exe: &str) -> Option<&AudioLevel> {
+        self.apps
+            .iter()
+            .find(|(app, _)| app.eq_ignore_ascii_case(exe))
+            .map(|(_, level)| level)
+    }
+}
+
+/// Something that happened on the system, used to evaluate the audio profile rules.
+#[derive(Debug, Clone, Copy)]
+pub enum AudioRuleEvent<'a> {
+    DeviceConnected { id: &'a str, name: &'a str },
+    BluetoothConnected { id: &'a str, name: &'a str },
+    AppFocused { exe: &'a str },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AudioProfileTrigger {
+    /// an audio device is connected, matched by id or name (case insensitive)
+    DeviceConnected { device: String },
+    /// a bluetooth device is connected, matched by id or name (case insensitive)
+    BluetoothConnected { device: String },
+    /// a window of the app is focused, matched by executable name (case insensitive)
+    AppFocused { exe: String },
+}
+
+impl AudioProfileTrigger {
+    pub fn matches(&self, event: &AudioRuleEvent) -> bool {
+        match (self, event) {
+            (Self::DeviceConnected { device }, AudioRuleEvent::DeviceConnected { id, name })
+            | (
+                Self::BluetoothConnected { device },
+                AudioRuleEvent::BluetoothConnected { id, name },
+            ) => device == id || device.eq_ignore_ascii_case(name),
+            (Self::AppFocused { exe }, AudioRuleEvent::AppFocused { exe: focused }) => {
+                exe.eq_ignore_ascii_case(focused)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioProfileRule {
+    pub trigger: AudioProfileTrigger,
+    /// id of the profile to apply
+    pub profile: Uuid,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AudioSettings {
+    pub profiles: Vec<AudioProfile>,
+    /// rules to apply the profiles automatically, the first matching rule wins
+    pub rules: Vec<AudioProfileRule>,
+}
+
+impl AudioSettings {
+    pub fn profile(&self, id: &Uuid) -> Option<&AudioProfile> {
+        self.profiles.iter().find(|p| &p.id == id)
+    }
+
+    /// Profile of the first rule matching the event, rules of deleted profiles are ignored.
+    pub fn profile_for_event(&self, event: &AudioRuleEvent) -> Option<&AudioProfile> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.trigger.matches(event))
+            .find_map(|rule| self.profile(&rule.profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_audio_routing() {
+        let mut routing = AppsAudioRouting::default();
+        routing.set(
+            "Spotify.exe",
+            MediaDeviceType::Output,
+            Some("speakers".into()),
+        );
+        routing.set("spotify.exe", MediaDeviceType::Input, Some("mic".into()));
+
+        let app = routing.get("SPOTIFY.EXE").unwrap();
+        assert_eq!(app.device(MediaDeviceType::Output).unwrap(), "speakers");
+        assert_eq!(app.device(MediaDeviceType::Input).unwrap(), "mic");
+
+        routing.set("spotify.exe", MediaDeviceType::Output, None);
+        assert!(routing.get("spotify.exe").unwrap().output.is_none());
+
+        // empty entries are not kept
+        routing.set("spotify.exe", MediaDeviceType::Input, None);
+        assert!(routing.0.is_empty());
+        assert!(routing.remove("discord.exe").is_none());
+    }
+
+    fn profile(name: &str) -> AudioProfile {
+        AudioProfile {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            default_output: Some(format!("{name}-output")),
+            default_input: None,
+            communications_output: None,
+            communications_input: None,
+            devices: HashMap::new(),
+            apps: HashMap::from([(
+                "Discord.exe".to_string(),
+                AudioLevel {
+                    volume: Some(0.5),
+                    muted: None,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_audio_profile_rules() {
+        let headset = profile("headset");
+        let speakers = profile("speakers");
+        let settings = AudioSettings {
+            rules: vec![
+                AudioProfileRule {
+                    trigger: AudioProfileTrigger::BluetoothConnected {
+                        device: "WH-1000XM4".to_string(),
+                    },
+                    profile: headset.id,
+                },
+                AudioProfileRule {
+                    trigger: AudioProfileTrigger::AppFocused {
+                        exe: "game.exe".to_string(),
+                    },
+                    profile: Uuid::new_v4(), // deleted profile
+                },
+                AudioProfileRule {
+                    trigger: AudioProfileTrigger::AppFocused {
+                        exe: "GAME.exe".to_string(),
+                    },
+                    profile: speakers.id,
+                },
+                AudioProfileRule {
+                    trigger: AudioProfileTrigger::DeviceConnected {
+                        device: "{0.0.0.00000000}.{abc}".to_string(),
+                    },
+                    profile: headset.id,
+                },
+            ],
+            profiles: vec![headset.clone(), speakers.clone()],
+        };
+
+        let found = |event: AudioRuleEvent| settings.profile_for_event(&event).map(|p| p.id);
+
+        assert_eq!(
+            found(AudioRuleEvent::BluetoothConnected {
+                id: "bt-id",
+                name: "wh-1000xm4"
+            }),
+            Some(headset.id)
+        );
+        assert_eq!(
+            found(AudioRuleEvent::AppFocused { exe: "game.exe" }),
+            Some(speakers.id)
+        );
+        assert_eq!(
+            found(AudioRuleEvent::DeviceConnected {
+                id: "{0.0.0.00000000}.{abc}",
+                name: "Speakers"
+            }),
+            Some(headset.id)
+        );
+        // same name but different kind of event
+        assert_eq!(
+            found(AudioRuleEvent::DeviceConnected {
+                id: "other",
+                name: "WH-1000XM4"
+            }),
+            None
+        );
+        assert_eq!(found(AudioRuleEvent::AppFocused { exe: "code.exe" }), None);
+
+        assert_eq!(
+            headset.app_level("discord.exe").and_then(|l| l.volume),
+            Some(0.5)
+        );
+    }
+}
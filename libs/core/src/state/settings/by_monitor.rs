@@ -25,6 +25,10 @@ impl MonitorSettingsByWidget {
     pub fn insert(&mut self, widget_id: WidgetId, settings: ThirdPartyWidgetSettings) {
         self.0.insert(widget_id, settings);
     }
+
+    pub fn get_mut(&mut self, widget_id: &WidgetId) -> Option<&mut ThirdPartyWidgetSettings> {
+        self.0.get_mut(widget_id)
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, TS)]
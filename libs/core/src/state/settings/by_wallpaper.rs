@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use crate::{rect::Point, system_state::MonitorId};
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(default, rename_all = "camelCase")]
 pub struct WallpaperInstanceSettings {
@@ -23,6 +27,9 @@ pub struct WallpaperInstanceSettings {
     pub overlay_color: String,
     /// mute video backgrounds
     pub muted: bool,
+    /// offsets per monitor in DPI independent pixels, applied when spanning
+    /// the wallpaper across all monitors to compensate the bezels.
+    pub monitor_offsets: HashMap<MonitorId, Point>,
 }
 
 impl Default for WallpaperInstanceSettings {
@@ -40,6 +47,7 @@ impl Default for WallpaperInstanceSettings {
             overlay_mix_blend_mode: MixBlendMode::default(),
             overlay_color: "#ff0000".to_string(),
             muted: true,
+            monitor_offsets: HashMap::new(),
         }
     }
 }
@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use schemars::JsonSchema;
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::{resource::WidgetId, utils::TsUnknown};
+use crate::{
+    resource::WidgetId,
+    state::declaration::{WidgetSettingCheck, WidgetSettingsDeclarationList},
+    system_state::MonitorId,
+    utils::TsUnknown,
+};
 
 use super::{FancyToolbarSettings, SeelenWallSettings, SeelenWegSettings, WindowManagerSettings};
 
@@ -72,3 +78,279 @@ pub struct ThirdPartyWidgetSettings {
     #[serde(flatten)]
     pub rest: HashMap<String, TsUnknown>,
 }
+
+impl ThirdPartyWidgetSettings {
+    /// Checks the stored values, including the ones of the instances, against the widget
+    /// settings declaration. Invalid values are coerced or reset to the default value and
+    /// undeclared keys are removed.
+    pub fn sanitize(
+        &mut self,
+        widget_id: &WidgetId,
+        monitor_id: Option<&MonitorId>,
+        declaration: &WidgetSettingsDeclarationList,
+    ) -> Vec<WidgetSettingFix> {
+        let mut fixes = Vec::new();
+        let mut fix = |instance_id: Option<Uuid>, key: &str, action, previous, value| {
+            fixes.push(WidgetSettingFix {
+                widget_id: widget_id.clone(),
+                monitor_id: monitor_id.cloned(),
+                instance_id,
+                key: key.to_string(),
+                action,
+                previous,
+                value,
+            });
+        };
+
+        let items = declaration.items();
+        // each value is checked once, returns whether any value was changed
+        let mut sanitize_values =
+            |instance_id: Option<Uuid>,
+             hidden: &HashSet<&str>,
+             checked: &mut HashSet<String>,
+             values: &mut HashMap<String, TsUnknown>| {
+                let mut changed = false;
+                values.retain(|key, value| {
+                    let Some(item) = items.iter().find(|item| item.get_key() == key) else {
+                        fix(
+                            instance_id,
+                            key,
+                            WidgetSettingFixAction::Removed,
+                            value.clone(),
+                            None,
+                        );
+                        changed = true;
+                        return false;
+                    };
+
+                    // hidden settings keep their values
+                    if hidden.contains(key.as_str()) || !checked.insert(key.clone()) {
+                        return true;
+                    }
+
+                    let (action, new_value) = match item.check_value(&value.0) {
+                        WidgetSettingCheck::Valid => return true,
+                        WidgetSettingCheck::Coerced(coerced) => {
+                            (WidgetSettingFixAction::Coerced, coerced)
+                        }
+                        WidgetSettingCheck::Invalid => {
+                            (WidgetSettingFixAction::Reset, item.default_value())
+                        }
+                    };
+                    let new_value = TsUnknown(new_value);
+                    let previous = std::mem::replace(value, new_value.clone());
+                    fix(instance_id, key, action, previous, Some(new_value));
+                    changed = true;
+                    true
+                });
+                changed
+            };
+
+        // the visibility is evaluated again after each pass, as fixing the controlling
+        // settings can show other ones, until there is nothing left to fix.
+        let defaults = declaration.default_values();
+        let mut checked = HashSet::new();
+        loop {
+            let hidden = declaration.hidden_keys(&merge_values(defaults.clone(), &self.rest));
+            if !sanitize_values(None, &hidden, &mut checked, &mut self.rest) {
+                break;
+            }
+        }
+
+        let root = merge_values(defaults, &self.rest);
+        for (instance_id, values) in self.instances.iter_mut().flatten() {
+            let mut checked = HashSet::new();
+            loop {
+                let hidden = declaration.hidden_keys(&merge_values(root.clone(), values));
+                if !sanitize_values(Some(*instance_id), &hidden, &mut checked, values) {
+                    break;
+                }
+            }
+        }
+        fixes
+    }
+}
+
+/// Values used to evaluate the visibility of the settings, stored values override the base ones.
+fn merge_values(
+    mut base: HashMap<String, Value>,
+    values: &HashMap<String, TsUnknown>,
+) -> HashMap<String, Value> {
+    base.extend(values.iter().map(|(k, v)| (k.clone(), v.0.clone())));
+    base
+}
+
+/// A stored widget setting value that didn't match the widget settings declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetSettingFix {
+    pub widget_id: WidgetId,
+    /// Present if the value is from the settings of a monitor
+    pub monitor_id: Option<MonitorId>,
+    /// Present if the value is from the settings of an instance
+    pub instance_id: Option<Uuid>,
+    pub key: String,
+    pub action: WidgetSettingFixAction,
+    pub previous: TsUnknown,
+    /// None if the key was removed
+    pub value: Option<TsUnknown>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+pub enum WidgetSettingFixAction {
+    /// The value was converted to a valid one, ex: a number was clamped to the bounds
+    Coerced,
+    /// The value was replaced by the default value
+    Reset,
+    /// The key is not declared by the widget anymore
+    Removed,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_sanitize_third_party_settings() {
+        let declaration: WidgetSettingsDeclarationList = serde_json::from_value(json!([
+            { "type": "range", "key": "size", "defaultValue": 5, "min": 0, "max": 10 },
+            {
+                "type": "select",
+                "key": "mode",
+                "defaultValue": "a",
+                "options": [{ "label": "A", "value": "a" }],
+            },
+        ]))
+        .unwrap();
+
+        let instance_id = Uuid::new_v4();
+        let mut settings: ThirdPartyWidgetSettings = serde_json::from_value(json!({
+            "enabled": true,
+            "size": 20,
+            "mode": "a",
+            "removed": 1,
+            "$instances": { instance_id.to_string(): { "mode": "b" } },
+        }))
+        .unwrap();
+
+        let widget_id = WidgetId::from("@user/widget");
+        let mut fixes = settings.sanitize(&widget_id, None, &declaration);
+        fixes.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let fix = |instance_id, key: &str, action, previous: Value, value: Option<Value>| {
+            WidgetSettingFix {
+                widget_id: widget_id.clone(),
+                monitor_id: None,
+                instance_id,
+                key: key.to_string(),
+                action,
+                previous: TsUnknown(previous),
+                value: value.map(TsUnknown),
+            }
+        };
+        assert_eq!(
+            fixes,
+            vec![
+                fix(
+                    Some(instance_id),
+                    "mode",
+                    WidgetSettingFixAction::Reset,
+                    json!("b"),
+                    Some(json!("a"))
+                ),
+                fix(
+                    None,
+                    "removed",
+                    WidgetSettingFixAction::Removed,
+                    json!(1),
+                    None
+                ),
+                fix(
+                    None,
+                    "size",
+                    WidgetSettingFixAction::Coerced,
+                    json!(20),
+                    Some(json!(10))
+                ),
+            ]
+        );
+
+        assert!(settings.enabled);
+        assert_eq!(settings.rest.len(), 2);
+        assert!(settings.sanitize(&widget_id, None, &declaration).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_skips_hidden_settings() {
+        let declaration: WidgetSettingsDeclarationList = serde_json::from_value(json!([
+            { "type": "switch", "key": "custom" },
+            { "type": "color", "key": "color", "dependencies": ["custom"] },
+        ]))
+        .unwrap();
+
+        let instance_id = Uuid::new_v4();
+        let mut settings: ThirdPartyWidgetSettings = serde_json::from_value(json!({
+            "color": "red",
+            "$instances": { instance_id.to_string(): { "custom": true } },
+        }))
+        .unwrap();
+
+        let widget_id = WidgetId::from("@user/widget");
+        let fixes = settings.sanitize(&widget_id, None, &declaration);
+        assert!(fixes.is_empty());
+        assert_eq!(settings.rest.get("color"), Some(&TsUnknown(json!("red"))));
+
+        // the instance shows the setting, so its own value is validated
+        settings
+            .instances
+            .as_mut()
+            .unwrap()
+            .get_mut(&instance_id)
+            .unwrap()
+            .insert("color".to_string(), json!("blue").into());
+        let fixes = settings.sanitize(&widget_id, None, &declaration);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].instance_id, Some(instance_id));
+        assert_eq!(fixes[0].action, WidgetSettingFixAction::Reset);
+    }
+
+    #[test]
+    fn test_sanitize_evaluates_visibility_with_fixed_values() {
+        let declaration: WidgetSettingsDeclarationList = serde_json::from_value(json!([
+            {
+                "type": "select",
+                "key": "mode",
+                "defaultValue": "full",
+                "options": [{ "label": "Full", "value": "full" }, { "label": "Compact", "value": "compact" }],
+            },
+            {
+                "type": "range",
+                "key": "size",
+                "max": 10,
+                "visibleIf": { "key": "mode", "equals": "full" },
+            },
+        ]))
+        .unwrap();
+
+        // the invalid mode hides the size until it is reset to the default
+        let mut settings: ThirdPartyWidgetSettings = serde_json::from_value(json!({
+            "mode": "unknown",
+            "size": 20,
+        }))
+        .unwrap();
+
+        let widget_id = WidgetId::from("@user/widget");
+        let mut fixes = settings.sanitize(&widget_id, None, &declaration);
+        fixes.sort_by(|a, b| a.key.cmp(&b.key));
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].key, "mode");
+        assert_eq!(fixes[0].action, WidgetSettingFixAction::Reset);
+        assert_eq!(fixes[1].key, "size");
+        assert_eq!(fixes[1].action, WidgetSettingFixAction::Coerced);
+        assert_eq!(settings.rest.get("size"), Some(&TsUnknown(json!(10))));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::system_state::{LauncherAction, LauncherKeywordProvider};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSearchKeyword {
+    /// typed at the start of the query to search on this site, e.g: `g`
+    pub keyword: String,
+    pub name: String,
+    /// search url, `{query}` is replaced by the url encoded query
+    pub url: String,
+}
+
+impl WebSearchKeyword {
+    fn new(keyword: &str, name: &str, url: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    pub fn to_provider(&self) -> LauncherKeywordProvider {
+        LauncherKeywordProvider {
+            keyword: self.keyword.clone(),
+            title: "{query}".to_string(),
+            subtitle: Some(self.name.clone()),
+            icon: Some("TbWorldSearch".to_string()),
+            action: LauncherAction::Url {
+                url: self.url.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LauncherSettings {
+    /// keywords to search on the web from the launcher
+    pub web_search: Vec<WebSearchKeyword>,
+}
+
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        Self {
+            web_search: vec![
+                WebSearchKeyword::new("g", "Google", "https://www.google.com/search?q={query}"),
+                WebSearchKeyword::new("ddg", "DuckDuckGo", "https://duckduckgo.com/?q={query}"),
+                WebSearchKeyword::new(
+                    "yt",
+                    "YouTube",
+                    "https://www.youtube.com/results?search_query={query}",
+                ),
+                WebSearchKeyword::new(
+                    "wiki",
+                    "Wikipedia",
+                    "https://en.wikipedia.org/w/index.php?search={query}",
+                ),
+            ],
+        }
+    }
+}
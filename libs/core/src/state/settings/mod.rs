@@ -1,12 +1,20 @@
 /* In this file we use #[serde_alias(SnakeCase)] as backward compatibility from versions below v1.9.8 */
+pub mod audio;
 pub mod by_monitor;
 pub mod by_theme;
 pub mod by_wallpaper;
 pub mod by_widget;
+pub mod launcher;
+pub mod notifications;
 pub mod settings_by_app;
 pub mod shortcuts;
+pub mod system_metrics;
 
+pub use audio::*;
+pub use launcher::*;
+pub use notifications::*;
 pub use settings_by_app::*;
+pub use system_metrics::*;
 
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
@@ -19,15 +27,17 @@ use serde_alias::serde_alias;
 use ts_rs::TS;
 
 use crate::resource::WidgetId;
-use crate::state::WallpaperCollection;
+use crate::state::{WallpaperCollection, Widget};
 use crate::system_state::MonitorId;
 use crate::{
     error::Result,
     rect::Rect,
     resource::{IconPackId, PluginId, ThemeId, WallpaperId},
     state::{
-        by_monitor::MonitorConfiguration, by_theme::ThemeSettings,
-        by_wallpaper::WallpaperInstanceSettings, by_widget::SettingsByWidget,
+        by_monitor::MonitorConfiguration,
+        by_theme::ThemeSettings,
+        by_wallpaper::WallpaperInstanceSettings,
+        by_widget::{SettingsByWidget, WidgetSettingFix},
         shortcuts::SluShortcutsSettings,
     },
 };
@@ -309,6 +319,8 @@ pub enum MultimonitorBehaviour {
     PerMonitor,
     /// Single wallpaper extended across all monitors
     Extend,
+    /// Single wallpaper cut across all monitors, each monitor renders its own part
+    Span,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
@@ -384,6 +396,10 @@ pub enum StartOfWeek {
 #[cfg_attr(feature = "gen-binds", ts(export))]
 pub struct Settings {
     pub by_app: AppsConfigurationList,
+    /// preferred audio devices by app executable
+    pub audio_by_app: AppsAudioRouting,
+    /// audio profiles and the rules to apply them
+    pub audio: AudioSettings,
     /// list of monitors and their configurations
     pub monitors_v3: HashMap<MonitorId, MonitorConfiguration>,
     /// app shortcuts settings
@@ -426,12 +442,20 @@ pub struct Settings {
     pub wallpaper_collections: Vec<WallpaperCollection>,
     /// Performance options
     pub performance_mode: PerformanceModeSettings,
+    /// Notifications options
+    pub notifications: NotificationsSettings,
+    /// Launcher search options
+    pub launcher: LauncherSettings,
+    /// System metrics history options
+    pub system_metrics: SystemMetricsSettings,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             by_app: AppsConfigurationList::default(),
+            audio_by_app: AppsAudioRouting::default(),
+            audio: AudioSettings::default(),
             performance_mode: PerformanceModeSettings::default(),
             shortcuts: SluShortcutsSettings::default(),
             drpc: false,
@@ -448,6 +472,9 @@ impl Default for Settings {
             by_theme: HashMap::new(),
             by_wallpaper: HashMap::new(),
             wallpaper_collections: Vec::new(),
+            notifications: NotificationsSettings::default(),
+            launcher: LauncherSettings::default(),
+            system_metrics: SystemMetricsSettings::default(),
         }
     }
 }
@@ -472,6 +499,8 @@ impl Settings {
                     id: uuid::Uuid::new_v4(),
                     name: "Migrated".to_string(),
                     wallpapers: backgrounds,
+                    schedule: None,
+                    rule: None,
                 };
 
                 // Set as default collection if no default is set
@@ -513,6 +542,26 @@ impl Settings {
         Ok(())
     }
 
+    /// Checks the stored settings of the third party widgets against their declarations,
+    /// settings of widgets not in the list are kept as they are.
+    pub fn sanitize_widget_settings<'a>(
+        &mut self,
+        widgets: impl IntoIterator<Item = &'a Widget>,
+    ) -> Vec<WidgetSettingFix> {
+        let mut fixes = Vec::new();
+        for widget in widgets {
+            if let Some(config) = self.by_widget.others.get_mut(&widget.id) {
+                fixes.extend(config.sanitize(&widget.id, None, &widget.settings));
+            }
+            for (monitor_id, monitor) in &mut self.monitors_v3 {
+                if let Some(config) = monitor.by_widget.get_mut(&widget.id) {
+                    fixes.extend(config.sanitize(&widget.id, Some(monitor_id), &widget.settings));
+                }
+            }
+        }
+        fixes
+    }
+
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
 
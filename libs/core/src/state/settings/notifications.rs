@@ -0,0 +1,436 @@
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::system_state::{AppNotification, ToastTemplateType};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NotificationHistorySettings {
+    /// keep a local history of the received notifications
+    pub enabled: bool,
+    /// entries older than this amount of days are removed, 0 means no limit
+    pub max_age_days: u32,
+    /// max amount of entries, oldest are removed first, 0 means no limit
+    pub max_entries: u32,
+}
+
+impl Default for NotificationHistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_age_days: 30,
+            max_entries: 1000,
+        }
+    }
+}
+
+/// What to do with a notification matched by a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(repr(enum = name))]
+pub enum NotificationAction {
+    /// shown as usual
+    Show,
+    /// added to the notifications list, but without popups or sounds
+    Silence,
+    /// only stored on the history
+    HistoryOnly,
+    /// discarded, not even stored on the history
+    Suppress,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRule {
+    /// app user model ids to match, empty means any app
+    #[serde(default)]
+    pub apps: Vec<String>,
+    /// regex to be tested against each text of the toast
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub text: Option<String>,
+    /// toast templates to match, empty means any template
+    #[serde(default)]
+    pub templates: Vec<ToastTemplateType>,
+    pub action: NotificationAction,
+    /// compiled `text`, see [`NotificationRule::compile`]
+    #[serde(skip)]
+    #[schemars(skip)]
+    #[ts(skip)]
+    regex: Option<Regex>,
+}
+
+impl NotificationRule {
+    /// Compiles the `text` regex, should be called once after the rule is loaded.
+    pub fn compile(&mut self) -> Result<(), regex::Error> {
+        self.regex = None;
+        if let Some(pattern) = &self.text {
+            self.regex = Some(Regex::new(pattern)?);
+        }
+        Ok(())
+    }
+
+    /// All the conditions of the rule should match, rules with a text that was not
+    /// compiled or is an invalid regex never match.
+    pub fn matches(&self, notification: &AppNotification) -> bool {
+        if !self.apps.is_empty() && !self.apps.contains(&notification.app_umid) {
+            return false;
+        }
+
+        if !self.templates.is_empty()
+            && !self
+                .templates
+                .contains(&notification.content.visual.binding.template)
+        {
+            return false;
+        }
+
+        if self.text.is_none() {
+            return true;
+        }
+        match &self.regex {
+            Some(regex) => notification
+                .content
+                .texts()
+                .iter()
+                .any(|text| regex.is_match(text)),
+            None => false,
+        }
+    }
+}
+
+/// Time window in which a focus mode is automatically enabled,
+/// if `end` is before `start` the window ends the next day.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusModeSchedule {
+    /// days when the window starts, empty means every day
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// local time as `HH:MM:SS`
+    pub start: NaiveTime,
+    /// local time as `HH:MM:SS`
+    pub end: NaiveTime,
+}
+
+impl FocusModeSchedule {
+    pub fn contains(&self, now: &NaiveDateTime) -> bool {
+        let time = now.time();
+        let today = now.weekday();
+        let on = |day: Weekday| self.days.is_empty() || self.days.contains(&day);
+
+        if self.start <= self.end {
+            on(today) && self.start <= time && time < self.end
+        } else {
+            (on(today) && time >= self.start) || (on(today.pred()) && time < self.end)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusMode {
+    pub id: Uuid,
+    pub name: String,
+    /// react-icon icon name
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub icon: Option<String>,
+    /// evaluated in order, the first matching rule wins
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    /// applied to the notifications not matched by any rule
+    pub default_action: NotificationAction,
+    /// time windows where this mode is automatically enabled
+    #[serde(default)]
+    pub schedule: Vec<FocusModeSchedule>,
+    /// automatically enable this mode while the focused app is fullscreen
+    #[serde(default)]
+    pub on_fullscreen: bool,
+}
+
+/// Why the focus mode was enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+pub enum FocusModeTrigger {
+    /// by the user, using the ui or a hotkey
+    Manual,
+    Fullscreen,
+    Schedule,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct FocusModeState {
+    /// active focus mode, None means that the default rules are being used
+    pub active: Option<Uuid>,
+    pub trigger: Option<FocusModeTrigger>,
+}
+
+/// Everything needed to resolve the focus mode, injected to keep the resolution pure.
+#[derive(Debug, Clone)]
+pub struct FocusModeContext {
+    /// current local date time
+    pub now: NaiveDateTime,
+    /// the focused app is fullscreen
+    pub fullscreen: bool,
+    /// focus mode manually enabled by the user
+    pub manual: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NotificationsSettings {
+    pub history: NotificationHistorySettings,
+    /// rules applied while no focus mode is active, the first matching rule wins
+    pub rules: Vec<NotificationRule>,
+    pub focus_modes: Vec<FocusMode>,
+}
+
+impl NotificationsSettings {
+    /// Compiles the rules of the default rules and focus modes, should be called each time
+    /// the settings are loaded. Returns the errors of the invalid patterns.
+    pub fn compile_rules(&mut self) -> Vec<String> {
+        let focus_rules = self.focus_modes.iter_mut().flat_map(|m| &mut m.rules);
+        self.rules
+            .iter_mut()
+            .chain(focus_rules)
+            .filter_map(|rule| rule.compile().err())
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    /// Priority: manual → fullscreen → schedule.
+    pub fn resolve_focus_mode(&self, ctx: &FocusModeContext) -> FocusModeState {
+        let found = ctx
+            .manual
+            .and_then(|id| self.focus_modes.iter().find(|m| m.id == id))
+            .map(|m| (m, FocusModeTrigger::Manual))
+            .or_else(|| {
+                self.focus_modes
+                    .iter()
+                    .find(|m| ctx.fullscreen && m.on_fullscreen)
+                    .map(|m| (m, FocusModeTrigger::Fullscreen))
+            })
+            .or_else(|| {
+                self.focus_modes
+                    .iter()
+                    .find(|m| m.schedule.iter().any(|s| s.contains(&ctx.now)))
+                    .map(|m| (m, FocusModeTrigger::Schedule))
+            });
+
+        match found {
+            Some((mode, trigger)) => FocusModeState {
+                active: Some(mode.id),
+                trigger: Some(trigger),
+            },
+            None => FocusModeState::default(),
+        }
+    }
+
+    /// Action to be applied to the notification, using the rules of the active focus mode.
+    pub fn evaluate(
+        &self,
+        state: &FocusModeState,
+        notification: &AppNotification,
+    ) -> NotificationAction {
+        let mode = state
+            .active
+            .and_then(|id| self.focus_modes.iter().find(|m| m.id == id));
+
+        let (rules, default) = match mode {
+            Some(mode) => (&mode.rules, mode.default_action),
+            None => (&self.rules, NotificationAction::Show),
+        };
+
+        rules
+            .iter()
+            .find(|rule| rule.matches(notification))
+            .map(|rule| rule.action)
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::system_state::{Toast, ToastBindingChild, ToastText};
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2025-09-01 is a monday
+        NaiveDate::from_ymd_opt(2025, 9, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn notification(umid: &str, text: &str) -> AppNotification {
+        let mut content = Toast::default();
+        content
+            .visual
+            .binding
+            .children
+            .push(ToastBindingChild::Text(ToastText {
+                content: text.to_string(),
+                ..Default::default()
+            }));
+        AppNotification {
+            id: 1,
+            app_umid: umid.to_string(),
+            app_name: umid.to_string(),
+            app_description: String::new(),
+            date: 0,
+            silent: false,
+            widget_id: None,
+            content,
+        }
+    }
+
+    fn rule(apps: &[&str], text: Option<&str>, action: NotificationAction) -> NotificationRule {
+        NotificationRule {
+            apps: apps.iter().map(|a| a.to_string()).collect(),
+            text: text.map(|t| t.to_string()),
+            templates: vec![],
+            action,
+            regex: None,
+        }
+    }
+
+    fn settings() -> NotificationsSettings {
+        let mut settings = NotificationsSettings {
+            rules: vec![rule(&["Spam"], None, NotificationAction::Suppress)],
+            focus_modes: vec![
+                FocusMode {
+                    id: Uuid::from_u128(1),
+                    name: "Night".to_string(),
+                    icon: None,
+                    rules: vec![rule(&[], Some(r"(?i)urgent"), NotificationAction::Show)],
+                    default_action: NotificationAction::HistoryOnly,
+                    schedule: vec![FocusModeSchedule {
+                        days: vec![],
+                        start: time(22),
+                        end: time(7),
+                    }],
+                    on_fullscreen: false,
+                },
+                FocusMode {
+                    id: Uuid::from_u128(2),
+                    name: "Gaming".to_string(),
+                    icon: None,
+                    rules: vec![],
+                    default_action: NotificationAction::Silence,
+                    schedule: vec![],
+                    on_fullscreen: true,
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(settings.compile_rules().is_empty());
+        settings
+    }
+
+    fn ctx(now: NaiveDateTime, fullscreen: bool, manual: Option<u128>) -> FocusModeContext {
+        FocusModeContext {
+            now,
+            fullscreen,
+            manual: manual.map(Uuid::from_u128),
+        }
+    }
+
+    #[test]
+    fn test_schedule_wraps_midnight() {
+        let schedule = FocusModeSchedule {
+            days: vec![Weekday::Fri],
+            start: time(22),
+            end: time(7),
+        };
+        // friday night and saturday morning
+        assert!(schedule.contains(&at(5, 23)));
+        assert!(schedule.contains(&at(6, 3)));
+        assert!(!schedule.contains(&at(6, 8)));
+        // thursday night
+        assert!(!schedule.contains(&at(4, 23)));
+    }
+
+    #[test]
+    fn test_focus_mode_resolution_priority() {
+        let settings = settings();
+        let resolve = |c| settings.resolve_focus_mode(&c);
+
+        assert_eq!(
+            resolve(ctx(at(1, 12), false, None)),
+            FocusModeState::default()
+        );
+        assert_eq!(
+            resolve(ctx(at(1, 23), false, None)),
+            FocusModeState {
+                active: Some(Uuid::from_u128(1)),
+                trigger: Some(FocusModeTrigger::Schedule),
+            }
+        );
+        assert_eq!(
+            resolve(ctx(at(1, 23), true, None)).trigger,
+            Some(FocusModeTrigger::Fullscreen)
+        );
+        assert_eq!(
+            resolve(ctx(at(1, 12), true, Some(1))),
+            FocusModeState {
+                active: Some(Uuid::from_u128(1)),
+                trigger: Some(FocusModeTrigger::Manual),
+            }
+        );
+        // unknown manual modes are ignored
+        assert_eq!(resolve(ctx(at(1, 12), false, Some(9))).active, None);
+    }
+
+    #[test]
+    fn test_rules_evaluation() {
+        let settings = settings();
+        let none = FocusModeState::default();
+        let night = FocusModeState {
+            active: Some(Uuid::from_u128(1)),
+            trigger: Some(FocusModeTrigger::Manual),
+        };
+
+        let spam = notification("Spam", "Buy now");
+        let mail = notification("Mail", "URGENT: server down");
+        let chat = notification("Chat", "hi");
+
+        assert_eq!(
+            settings.evaluate(&none, &spam),
+            NotificationAction::Suppress
+        );
+        assert_eq!(settings.evaluate(&none, &chat), NotificationAction::Show);
+        assert_eq!(settings.evaluate(&night, &mail), NotificationAction::Show);
+        assert_eq!(
+            settings.evaluate(&night, &chat),
+            NotificationAction::HistoryOnly
+        );
+    }
+
+    #[test]
+    fn test_rule_templates_and_invalid_regex() {
+        let mut matching = rule(&[], None, NotificationAction::Silence);
+        matching.templates = vec![ToastTemplateType::ToastText01];
+        assert!(!matching.matches(&notification("Mail", "hi")));
+
+        matching.templates = vec![ToastTemplateType::ToastGeneric];
+        assert!(matching.matches(&notification("Mail", "hi")));
+
+        let mut invalid = rule(&[], Some("(unclosed"), NotificationAction::Suppress);
+        assert!(invalid.compile().is_err());
+        assert!(!invalid.matches(&notification("Mail", "(unclosed")));
+
+        let mut settings = settings();
+        settings.rules.push(invalid);
+        assert_eq!(settings.compile_rules().len(), 1);
+    }
+}
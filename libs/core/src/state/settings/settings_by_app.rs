@@ -22,6 +22,9 @@ pub enum AppExtraFlag {
     /// Pin this app in all the virtual desktops in the wm.
     #[serde(alias = "pinned", alias = "vd-pinned")]
     VdPinned,
+    /// Switch to the bound workspace when a window of this app is opened.
+    #[serde(alias = "follow", alias = "vd-follow")]
+    VdFollow,
     #[serde(other)]
     Unknown,
 }
@@ -173,6 +176,9 @@ pub struct AppConfig {
     pub bound_monitor: Option<usize>,
     /// workspace index that the app should be bound to
     pub bound_workspace: Option<usize>,
+    /// workspace name that the app should be bound to, takes precedence over `bound_workspace`
+    /// so the binding survives reordering of the workspaces.
+    pub bound_workspace_name: Option<String>,
     /// app identifier
     pub identifier: AppIdentifier,
     /// extra specific options/settings for the app
@@ -188,6 +194,13 @@ impl AppConfig {
         self.identifier.prepare();
     }
 
+    /// returns true if windows of this app should be placed on a specific monitor or workspace
+    pub fn has_bindings(&self) -> bool {
+        self.bound_monitor.is_some()
+            || self.bound_workspace.is_some()
+            || self.bound_workspace_name.is_some()
+    }
+
     fn is_false(b: &bool) -> bool {
         !b
     }
@@ -283,6 +296,7 @@ mod tests {
             category: None,
             bound_monitor: None,
             bound_workspace: None,
+            bound_workspace_name: None,
             identifier: AppIdentifier {
                 id: "Windows\\SystemApps".to_string(),
                 kind: AppIdentifierType::Path,
@@ -329,6 +343,7 @@ mod tests {
             category: None,
             bound_monitor: None,
             bound_workspace: None,
+            bound_workspace_name: None,
             identifier: AppIdentifier {
                 id: "Windows\\SystemApps".to_string(),
                 kind: AppIdentifierType::Path,
@@ -67,6 +67,11 @@ pub enum SluHotkeyAction {
     CycleWallpaperNext,
     CycleWallpaperPrev,
     // ==========================
+    /// toggles the focus mode at the index as manual focus mode
+    ToggleFocusMode {
+        index: usize,
+    },
+    // ==========================
     MiscOpenSettings,
     MiscForceRestart,
     MiscForceQuit,
@@ -288,6 +293,8 @@ impl SluShortcutsSettings {
             // wallpaper manager
             SluHotkey::new(CycleWallpaperNext, ["Ctrl", "Win", "Up"]),
             SluHotkey::new(CycleWallpaperPrev, ["Ctrl", "Win", "Down"]),
+            // notifications
+            SluHotkey::new(ToggleFocusMode { index: 0 }, ["Win", "Alt", "N"]),
             // misc
             SluHotkey::new(MiscOpenSettings, ["Win", "K"]),
             SluHotkey::new(MiscForceRestart, ["Ctrl", "Win", "Alt", "R"]).readonly(),
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+/// Value watched by an alert rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SystemAlertMetric {
+    /// average usage of all the cores, between 0 and 100
+    CpuUsage,
+    /// percentage of the memory in use, between 0 and 100
+    MemoryUsage,
+    /// bytes available on the disk, None means the disk with less available space
+    DiskAvailableSpace {
+        #[serde(default)]
+        #[ts(optional = nullable)]
+        mount_point: Option<PathBuf>,
+    },
+    /// charge of the battery, between 0 and 100
+    BatteryPercentage,
+    /// seconds until the battery is empty, only available while discharging
+    BatteryTimeToEmpty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(repr(enum = name))]
+pub enum SystemAlertComparison {
+    Above,
+    Below,
+}
+
+/// How the user is told about a fired alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(repr(enum = name))]
+pub enum SystemAlertAction {
+    Notification,
+    Popup,
+    /// only shown as badge on the toolbar items
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemAlertRule {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default = "SystemAlertRule::default_enabled")]
+    pub enabled: bool,
+    pub metric: SystemAlertMetric,
+    pub comparison: SystemAlertComparison,
+    /// same unit as the metric
+    pub threshold: f64,
+    /// seconds the threshold needs to be crossed before the alert fires
+    #[serde(default)]
+    pub duration_secs: u32,
+    /// min seconds between fires of the alert while the threshold is still crossed,
+    /// 0 means that it fires only once until the value goes back under the threshold
+    #[serde(default)]
+    pub cooldown_secs: u32,
+    pub action: SystemAlertAction,
+}
+
+impl SystemAlertRule {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SystemMetricsSettings {
+    /// seconds covered by each sample of the history, samples taken in between are merged
+    pub resolution_secs: u32,
+    /// samples older than this amount of minutes are discarded
+    pub retention_minutes: u32,
+    /// rules to alert about the system state, e.g. disk almost full or low battery
+    pub alerts: Vec<SystemAlertRule>,
+}
+
+impl Default for SystemMetricsSettings {
+    fn default() -> Self {
+        Self {
+            resolution_secs: 1,
+            retention_minutes: 60,
+            alerts: Vec::new(),
+        }
+    }
+}
+
+impl SystemMetricsSettings {
+    pub fn resolution_millis(&self) -> i64 {
+        self.resolution_secs.max(1) as i64 * 1000
+    }
+
+    /// Max amount of samples to be stored per metric.
+    pub fn history_capacity(&self) -> usize {
+        let retention = self.retention_minutes as usize * 60;
+        (retention / self.resolution_secs.max(1) as usize).max(1)
+    }
+}
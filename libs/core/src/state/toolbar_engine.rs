@@ -0,0 +1,325 @@
+use std::time::{Duration, Instant};
+
+use rquickjs::{CatchResultExt, Context, Runtime};
+use serde_json::{Map, Value};
+
+use crate::utils::TsUnknown;
+
+use super::{RenderedToolbarItem, ToolbarItem};
+
+const DEFAULT_EVALUATION_TIMEOUT_MS: u32 = 100;
+const MAX_EVALUATION_TIMEOUT_MS: u32 = 1000;
+const MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+const MAX_STACK_SIZE: usize = 512 * 1024;
+
+/// Same component creators available on the webview, the toolbar renders the returned objects.
+const COMPONENT_CREATORS: &str = r#"
+const icon = (name, size) => ({ "@component": "Icon", name, size });
+const Icon = (props) => ({ ...props, "@component": "Icon" });
+const AppIcon = (props) => ({ ...props, "@component": "AppIcon" });
+const Image = (props) => ({ ...props, "@component": "Image" });
+const Button = (props) => ({ ...props, "@component": "Button" });
+const Group = (props) => ({ ...props, "@component": "Group" });
+"#;
+
+/// Sandboxed JS engine to evaluate the toolbar items, the code only has access to the
+/// given scope and the standard built-ins, there is no file system, network or webview apis.\
+/// An engine can be reused to render the same item multiple times.
+pub struct ToolbarEngine {
+    context: Context,
+    runtime: Runtime,
+}
+
+impl ToolbarEngine {
+    pub fn new() -> Result<Self, String> {
+        let runtime = Runtime::new().map_err(|e| e.to_string())?;
+        runtime.set_memory_limit(MEMORY_LIMIT);
+        runtime.set_max_stack_size(MAX_STACK_SIZE);
+        let context = Context::full(&runtime).map_err(|e| e.to_string())?;
+        context.with(|ctx| {
+            ctx.eval::<(), _>(COMPONENT_CREATORS)
+                .catch(&ctx)
+                .map_err(|e| e.to_string())
+        })?;
+        Ok(Self { context, runtime })
+    }
+
+    /// Adds the scope to the globals, replacing the values of the previous scope.
+    pub fn set_scope(&self, scope: &Map<String, Value>) -> Result<(), String> {
+        // json is a valid js expression, so the scope can be inlined as object literal
+        let scope = serde_json::to_string(scope).map_err(|e| e.to_string())?;
+        self.context.with(|ctx| {
+            ctx.eval::<(), _>(format!("Object.assign(globalThis, {scope});"))
+                .catch(&ctx)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    /// Evaluates the body of a function, as the ones of the toolbar items, returning the result as json.
+    /// Non serializable results like functions are returned as null.
+    pub fn evaluate(&self, code: &str, timeout: Duration) -> Result<Value, String> {
+        let deadline = Instant::now() + timeout;
+        self.runtime
+            .set_interrupt_handler(Some(Box::new(move || Instant::now() > deadline)));
+
+        let result = self.context.with(|ctx| {
+            let value = ctx
+                .eval::<rquickjs::Value, _>(format!("(function() {{\n{code}\n}})()"))
+                .catch(&ctx)
+                .map_err(|e| e.to_string())?;
+            let json = ctx
+                .json_stringify(value)
+                .catch(&ctx)
+                .map_err(|e| e.to_string())?;
+            match json {
+                Some(json) => {
+                    let json = json.to_string().map_err(|e| e.to_string())?;
+                    serde_json::from_str(&json).map_err(|e| e.to_string())
+                }
+                None => Ok(Value::Null),
+            }
+        });
+
+        self.runtime.set_interrupt_handler(None);
+        if result.is_err() && Instant::now() > deadline {
+            return Err(format!("timed out after {}ms", timeout.as_millis()));
+        }
+        result
+    }
+}
+
+impl ToolbarItem {
+    pub fn evaluation_timeout(&self) -> Duration {
+        let timeout_ms = self
+            .evaluation_timeout_ms
+            .unwrap_or(DEFAULT_EVALUATION_TIMEOUT_MS)
+            .min(MAX_EVALUATION_TIMEOUT_MS);
+        Duration::from_millis(timeout_ms as u64)
+    }
+
+    /// Evaluates the template, tooltip and badge of the item on a new engine,
+    /// failed parts are rendered as null.
+    pub fn render(&self, scope: &Map<String, Value>) -> RenderedToolbarItem {
+        match ToolbarEngine::new() {
+            Ok(engine) => self.render_on(&engine, scope),
+            Err(err) => self.rendered_with_errors(vec![format!("engine: {err}")]),
+        }
+    }
+
+    /// Same as [`ToolbarItem::render`] but reusing the engine of previous renders.
+    pub fn render_on(
+        &self,
+        engine: &ToolbarEngine,
+        scope: &Map<String, Value>,
+    ) -> RenderedToolbarItem {
+        if let Err(err) = engine.set_scope(scope) {
+            return self.rendered_with_errors(vec![format!("scope: {err}")]);
+        }
+
+        let mut errors = Vec::new();
+        let timeout = self.evaluation_timeout();
+        let mut evaluate = |part: &str, code: &str| match engine.evaluate(code, timeout) {
+            Ok(value) => Some(TsUnknown(value)),
+            Err(err) => {
+                errors.push(format!("{part}: {err}"));
+                None
+            }
+        };
+
+        let template = evaluate("template", &self.template).unwrap_or(TsUnknown(Value::Null));
+        let tooltip = self.tooltip.as_ref().and_then(|c| evaluate("tooltip", c));
+        let badge = self.badge.as_ref().and_then(|c| evaluate("badge", c));
+
+        RenderedToolbarItem {
+            id: self.id.clone(),
+            template,
+            tooltip,
+            badge,
+            errors,
+        }
+    }
+
+    fn rendered_with_errors(&self, errors: Vec<String>) -> RenderedToolbarItem {
+        RenderedToolbarItem {
+            id: self.id.clone(),
+            template: TsUnknown(Value::Null),
+            tooltip: None,
+            badge: None,
+            errors,
+        }
+    }
+}
+
+/// Converts a moment.js date format, as the one on the settings, to a chrono format.
+pub fn moment_to_chrono_format(format: &str) -> String {
+    // longest tokens first so `MMMM` is not taken as `MM` + `MM`
+    const TOKENS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("YY", "%y"),
+        ("MMMM", "%B"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("M", "%-m"),
+        ("dddd", "%A"),
+        ("ddd", "%a"),
+        ("DD", "%d"),
+        ("D", "%-d"),
+        ("HH", "%H"),
+        ("H", "%-H"),
+        ("hh", "%I"),
+        ("h", "%-I"),
+        ("mm", "%M"),
+        ("m", "%-M"),
+        ("ss", "%S"),
+        ("s", "%-S"),
+        ("A", "%p"),
+        ("a", "%P"),
+    ];
+
+    let mut result = String::new();
+    let mut rest = format;
+    'outer: while let Some(c) = rest.chars().next() {
+        // escaped text
+        if c == '[' {
+            if let Some(end) = rest.find(']') {
+                result.push_str(&rest[1..end].replace('%', "%%"));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+
+        for (token, replacement) in TOKENS {
+            if let Some(stripped) = rest.strip_prefix(token) {
+                result.push_str(replacement);
+                rest = stripped;
+                continue 'outer;
+            }
+        }
+
+        if c == '%' {
+            result.push('%');
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{handlers::SeelenEvent, state::ToolbarJsScope};
+
+    use super::*;
+
+    fn item(template: &str) -> ToolbarItem {
+        ToolbarItem {
+            id: "item".to_string(),
+            template: template.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn scope(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_render_with_scope() {
+        let scope = scope(json!({
+            "memory": { "total": 100, "free": 25 },
+            "count": 3,
+        }));
+
+        let mut item = item(
+            "return [icon('PiMemory'), Math.round((memory.total - memory.free) * 100 / memory.total) + '%']",
+        );
+        item.tooltip = Some("return `${count} notifications`".to_string());
+        item.badge = Some("return count > 0 ? count : null".to_string());
+
+        let rendered = item.render(&scope);
+        assert!(rendered.errors.is_empty(), "{:?}", rendered.errors);
+        assert_eq!(
+            rendered.template.0,
+            json!([{ "@component": "Icon", "name": "PiMemory" }, "75%"])
+        );
+        assert_eq!(rendered.tooltip, Some(TsUnknown(json!("3 notifications"))));
+        assert_eq!(rendered.badge, Some(TsUnknown(json!(3))));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let rendered = item("return focusedApp.title").render(&Map::new());
+        assert_eq!(rendered.template.0, Value::Null);
+        assert_eq!(rendered.errors.len(), 1);
+        assert!(rendered.errors[0].starts_with("template:"));
+
+        // no access to the webview apis
+        let rendered = item("return typeof fetch + typeof window").render(&Map::new());
+        assert_eq!(rendered.template.0, json!("undefinedundefined"));
+
+        let rendered = item("return () => 1").render(&Map::new());
+        assert!(rendered.errors.is_empty());
+        assert_eq!(rendered.template.0, Value::Null);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut item = item("while (true) {}");
+        item.evaluation_timeout_ms = Some(10);
+        item.tooltip = Some("return 'still works'".to_string());
+
+        let started = Instant::now();
+        let rendered = item.render(&Map::new());
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(rendered.errors, vec!["template: timed out after 10ms"]);
+        assert_eq!(rendered.tooltip, Some(TsUnknown(json!("still works"))));
+    }
+
+    #[test]
+    fn test_engine_reuse() {
+        let engine = ToolbarEngine::new().unwrap();
+        let item = item("return `${count} unread`");
+
+        let rendered = item.render_on(&engine, &scope(json!({ "count": 1 })));
+        assert_eq!(rendered.template.0, json!("1 unread"));
+        let rendered = item.render_on(&engine, &scope(json!({ "count": 2 })));
+        assert_eq!(rendered.template.0, json!("2 unread"));
+    }
+
+    #[test]
+    fn test_timeout_is_limited() {
+        let mut item = item("return 1");
+        assert_eq!(item.evaluation_timeout(), Duration::from_millis(100));
+        item.evaluation_timeout_ms = Some(60_000);
+        assert_eq!(item.evaluation_timeout(), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_scopes_changed_by_events() {
+        assert_eq!(
+            ToolbarJsScope::changed_by(SeelenEvent::SystemMemoryChanged),
+            &[ToolbarJsScope::Memory]
+        );
+        assert!(
+            ToolbarJsScope::changed_by(SeelenEvent::StateSettingsChanged)
+                .contains(&ToolbarJsScope::Date)
+        );
+        assert!(ToolbarJsScope::changed_by(SeelenEvent::GlobalMouseMove).is_empty());
+    }
+
+    #[test]
+    fn test_moment_to_chrono_format() {
+        assert_eq!(
+            moment_to_chrono_format("ddd D MMM, hh:mm A"),
+            "%a %-d %b, %I:%M %p"
+        );
+        assert_eq!(
+            moment_to_chrono_format("YYYY-MM-DD HH:mm:ss"),
+            "%Y-%m-%d %H:%M:%S"
+        );
+        assert_eq!(moment_to_chrono_format("[Today is] dddd"), "Today is %A");
+        assert_eq!(moment_to_chrono_format("100%"), "100%%");
+    }
+}
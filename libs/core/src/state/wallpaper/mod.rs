@@ -1,4 +1,12 @@
-use std::path::Path;
+mod rule;
+mod schedule;
+mod span;
+
+pub use rule::*;
+pub use schedule::*;
+pub use span::*;
+
+use std::path::{Path, PathBuf};
 
 use url::Url;
 use uuid::Uuid;
@@ -29,6 +37,9 @@ pub struct Wallpaper {
     /// Only used if the wallpaper type is `Layered`.\
     /// Custom css that will be applied only on this wallpaper.
     pub css: Option<String>,
+
+    /// Original file this wallpaper was copied from, used by folder based collections.
+    pub origin: Option<PathBuf>,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
@@ -151,6 +162,7 @@ impl Wallpaper {
             } else {
                 None
             },
+            origin: copy.then(|| path.to_path_buf()),
             ..Default::default()
         };
         wallpaper.save()?;
@@ -165,4 +177,12 @@ pub struct WallpaperCollection {
     pub id: Uuid,
     pub name: String,
     pub wallpapers: Vec<WallpaperId>,
+    /// if set, the schedule takes precedence over the interval rotation
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub schedule: Option<WallpaperSchedule>,
+    /// smart collections also include every wallpaper matched by the rule
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub rule: Option<WallpaperCollectionRule>,
 }
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use crate::resource::WallpaperId;
+
+use super::{Wallpaper, WallpaperKind};
+
+/// Rule used by smart collections to include wallpapers without curating them by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WallpaperCollectionRule {
+    /// Every wallpaper imported from this folder, the folder is watched
+    /// and new files will be imported automatically.
+    Folder { path: PathBuf },
+    /// Every wallpaper that has at least one of these tags (case insensitive).
+    Tags { tags: Vec<String> },
+    /// Every wallpaper of this type.
+    Type { r#type: WallpaperKind },
+}
+
+impl WallpaperCollectionRule {
+    pub fn matches(&self, wallpaper: &Wallpaper) -> bool {
+        match self {
+            WallpaperCollectionRule::Folder { path } => wallpaper
+                .origin
+                .as_ref()
+                .is_some_and(|origin| origin.starts_with(path)),
+            WallpaperCollectionRule::Tags { tags } => wallpaper
+                .metadata
+                .tags
+                .iter()
+                .any(|tag| tags.iter().any(|t| t.eq_ignore_ascii_case(tag))),
+            WallpaperCollectionRule::Type { r#type } => &wallpaper.r#type == r#type,
+        }
+    }
+
+    /// Folder to be watched by this rule, if any.
+    pub fn folder(&self) -> Option<&PathBuf> {
+        match self {
+            WallpaperCollectionRule::Folder { path } => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl super::WallpaperCollection {
+    /// Returns the manually added wallpapers followed by the ones matched by the rule,
+    /// matches are sorted by id to keep a stable order between reloads.
+    pub fn resolve<'a, I>(&self, available: I) -> Vec<WallpaperId>
+    where
+        I: IntoIterator<Item = &'a Wallpaper>,
+    {
+        let mut resolved = self.wallpapers.clone();
+        let Some(rule) = &self.rule else {
+            return resolved;
+        };
+
+        let mut matched: Vec<&WallpaperId> = available
+            .into_iter()
+            .filter(|wallpaper| rule.matches(wallpaper))
+            .map(|wallpaper| &wallpaper.id)
+            .collect();
+        matched.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        for id in matched {
+            if !resolved.contains(id) {
+                resolved.push(id.clone());
+            }
+        }
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::WallpaperCollection;
+
+    use super::*;
+
+    fn wallpaper(
+        id: &str,
+        r#type: WallpaperKind,
+        tags: &[&str],
+        origin: Option<&str>,
+    ) -> Wallpaper {
+        let mut wallpaper = Wallpaper {
+            id: id.into(),
+            r#type,
+            origin: origin.map(PathBuf::from),
+            ..Default::default()
+        };
+        wallpaper.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        wallpaper
+    }
+
+    fn available() -> Vec<Wallpaper> {
+        vec![
+            wallpaper("@user/forest", WallpaperKind::Image, &["Nature"], None),
+            wallpaper(
+                "@user/river",
+                WallpaperKind::Video,
+                &["nature", "water"],
+                Some("C:/Pictures/Walls/river.mp4"),
+            ),
+            wallpaper(
+                "@user/city",
+                WallpaperKind::Image,
+                &["urban"],
+                Some("C:/Pictures/Walls/city.png"),
+            ),
+        ]
+    }
+
+    fn resolve(rule: WallpaperCollectionRule, manual: &[&str]) -> Vec<String> {
+        let collection = WallpaperCollection {
+            wallpapers: manual.iter().map(|id| (*id).into()).collect(),
+            rule: Some(rule),
+            ..Default::default()
+        };
+        collection
+            .resolve(available().iter())
+            .iter()
+            .map(|id| id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tags_rule() {
+        let rule = WallpaperCollectionRule::Tags {
+            tags: vec!["nature".to_string()],
+        };
+        assert_eq!(resolve(rule, &[]), vec!["@user/forest", "@user/river"]);
+    }
+
+    #[test]
+    fn test_type_rule() {
+        let rule = WallpaperCollectionRule::Type {
+            r#type: WallpaperKind::Video,
+        };
+        assert_eq!(resolve(rule, &[]), vec!["@user/river"]);
+    }
+
+    #[test]
+    fn test_folder_rule_keeps_manual_entries_first() {
+        let rule = WallpaperCollectionRule::Folder {
+            path: PathBuf::from("C:/Pictures/Walls"),
+        };
+        assert_eq!(
+            resolve(rule, &["@user/river", "@user/forest"]),
+            vec!["@user/river", "@user/forest", "@user/city"]
+        );
+    }
+
+    #[test]
+    fn test_rule_deserialization() {
+        let json = r#"{"kind":"type","type":"Video"}"#;
+        let rule: WallpaperCollectionRule = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            rule,
+            WallpaperCollectionRule::Type {
+                r#type: WallpaperKind::Video
+            }
+        ));
+
+        let json = r#"{"kind":"folder","path":"C:/Pictures"}"#;
+        let rule: WallpaperCollectionRule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.folder(), Some(&PathBuf::from("C:/Pictures")));
+    }
+}
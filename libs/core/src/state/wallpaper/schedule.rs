@@ -0,0 +1,256 @@
+use chrono::{Datelike, Days, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::resource::WallpaperId;
+
+/// Defines how the wallpaper of a collection is chosen, collections without
+/// schedule are rotated using `SeelenWallSettings.interval`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WallpaperSchedule {
+    /// A wallpaper per time window of the day, each slot lasts until the next one starts.
+    TimeOfDay { slots: Vec<WallpaperTimeSlot> },
+    /// A wallpaper per day of the week, days without entry fallback to the rotation.
+    DayOfWeek { days: Vec<WallpaperDaySlot> },
+    /// A wallpaper chosen by the system light/dark theme.
+    Theme {
+        light: Option<WallpaperId>,
+        dark: Option<WallpaperId>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WallpaperTimeSlot {
+    pub start: TimeSlotStart,
+    pub wallpaper: WallpaperId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WallpaperDaySlot {
+    pub day: Weekday,
+    pub wallpaper: WallpaperId,
+}
+
+/// Start of a time slot, as a named period of the day or as an explicit local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(untagged)]
+pub enum TimeSlotStart {
+    Period(DayPeriod),
+    /// local time as `HH:MM:SS`
+    Time(NaiveTime),
+}
+
+impl TimeSlotStart {
+    pub fn time(&self) -> NaiveTime {
+        match self {
+            TimeSlotStart::Period(period) => period.start(),
+            TimeSlotStart::Time(time) => *time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(repr(enum = name))]
+pub enum DayPeriod {
+    Morning,
+    Day,
+    Evening,
+    Night,
+}
+
+impl DayPeriod {
+    pub fn start(&self) -> NaiveTime {
+        let hour = match self {
+            DayPeriod::Morning => 6,
+            DayPeriod::Day => 10,
+            DayPeriod::Evening => 18,
+            DayPeriod::Night => 21,
+        };
+        NaiveTime::from_hms_opt(hour, 0, 0).expect("valid hour")
+    }
+}
+
+/// Everything needed to evaluate a schedule, injected to keep the evaluation pure.
+#[derive(Debug, Clone, Copy)]
+pub struct WallpaperScheduleContext {
+    /// current local date time
+    pub now: NaiveDateTime,
+    pub dark_mode: bool,
+}
+
+impl WallpaperSchedule {
+    /// Returns the wallpaper that should be shown at the given context,
+    /// None means that the schedule has no opinion and the rotation should be used.
+    pub fn resolve(&self, ctx: &WallpaperScheduleContext) -> Option<&WallpaperId> {
+        match self {
+            WallpaperSchedule::TimeOfDay { slots } => {
+                let now = ctx.now.time();
+                let mut sorted: Vec<&WallpaperTimeSlot> = slots.iter().collect();
+                sorted.sort_by_key(|slot| slot.start.time());
+                // before the first slot of the day, the last slot of the previous day still applies
+                sorted
+                    .iter()
+                    .rev()
+                    .find(|slot| slot.start.time() <= now)
+                    .or(sorted.last())
+                    .map(|slot| &slot.wallpaper)
+            }
+            WallpaperSchedule::DayOfWeek { days } => {
+                let today = ctx.now.weekday();
+                days.iter()
+                    .find(|slot| slot.day == today)
+                    .map(|slot| &slot.wallpaper)
+            }
+            WallpaperSchedule::Theme { light, dark } => {
+                if ctx.dark_mode {
+                    dark.as_ref()
+                } else {
+                    light.as_ref()
+                }
+            }
+        }
+    }
+
+    /// Next local date time at which the result of `resolve` could change because of the clock,
+    /// None if the schedule doesn't depend on the time.
+    pub fn next_change(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            WallpaperSchedule::TimeOfDay { slots } => {
+                let time = now.time();
+                let mut starts: Vec<NaiveTime> = slots.iter().map(|s| s.start.time()).collect();
+                starts.sort();
+
+                if let Some(next) = starts.iter().find(|start| **start > time) {
+                    return Some(now.date().and_time(*next));
+                }
+                let first = starts.first()?;
+                Some(now.date().checked_add_days(Days::new(1))?.and_time(*first))
+            }
+            WallpaperSchedule::DayOfWeek { .. } => Some(
+                now.date()
+                    .checked_add_days(Days::new(1))?
+                    .and_time(NaiveTime::MIN),
+            ),
+            WallpaperSchedule::Theme { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        // 2025-09-01 is a monday
+        NaiveDate::from_ymd_opt(2025, 9, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    fn ctx(now: NaiveDateTime) -> WallpaperScheduleContext {
+        WallpaperScheduleContext {
+            now,
+            dark_mode: false,
+        }
+    }
+
+    fn time_of_day() -> WallpaperSchedule {
+        WallpaperSchedule::TimeOfDay {
+            slots: vec![
+                WallpaperTimeSlot {
+                    start: TimeSlotStart::Period(DayPeriod::Night),
+                    wallpaper: "@user/night".into(),
+                },
+                WallpaperTimeSlot {
+                    start: TimeSlotStart::Period(DayPeriod::Morning),
+                    wallpaper: "@user/morning".into(),
+                },
+                WallpaperTimeSlot {
+                    start: TimeSlotStart::Time(NaiveTime::from_hms_opt(12, 30, 0).unwrap()),
+                    wallpaper: "@user/noon".into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_time_of_day_resolution() {
+        let schedule = time_of_day();
+        let resolve = |now| schedule.resolve(&ctx(now)).map(|w| w.to_string());
+
+        assert_eq!(resolve(at(1, 6, 0)).as_deref(), Some("@user/morning"));
+        assert_eq!(resolve(at(1, 12, 29)).as_deref(), Some("@user/morning"));
+        assert_eq!(resolve(at(1, 12, 30)).as_deref(), Some("@user/noon"));
+        assert_eq!(resolve(at(1, 22, 0)).as_deref(), Some("@user/night"));
+        // wraps around midnight
+        assert_eq!(resolve(at(1, 3, 0)).as_deref(), Some("@user/night"));
+    }
+
+    #[test]
+    fn test_time_of_day_next_change() {
+        let schedule = time_of_day();
+        assert_eq!(schedule.next_change(at(1, 7, 0)), Some(at(1, 12, 30)));
+        assert_eq!(schedule.next_change(at(1, 21, 0)), Some(at(2, 6, 0)));
+        assert_eq!(schedule.next_change(at(1, 3, 0)), Some(at(1, 6, 0)));
+
+        let empty = WallpaperSchedule::TimeOfDay { slots: vec![] };
+        assert_eq!(empty.resolve(&ctx(at(1, 3, 0))), None);
+        assert_eq!(empty.next_change(at(1, 3, 0)), None);
+    }
+
+    #[test]
+    fn test_day_of_week_resolution() {
+        let schedule = WallpaperSchedule::DayOfWeek {
+            days: vec![WallpaperDaySlot {
+                day: Weekday::Sat,
+                wallpaper: "@user/weekend".into(),
+            }],
+        };
+
+        let saturday = at(6, 10, 0);
+        assert_eq!(
+            schedule.resolve(&ctx(saturday)).map(|w| w.to_string()),
+            Some("@user/weekend".to_string())
+        );
+        assert_eq!(schedule.resolve(&ctx(at(1, 10, 0))), None);
+        assert_eq!(schedule.next_change(saturday), Some(at(7, 0, 0)));
+    }
+
+    #[test]
+    fn test_theme_resolution() {
+        let schedule = WallpaperSchedule::Theme {
+            light: Some("@user/light".into()),
+            dark: None,
+        };
+
+        let mut context = ctx(at(1, 10, 0));
+        assert_eq!(
+            schedule.resolve(&context).map(|w| w.to_string()),
+            Some("@user/light".to_string())
+        );
+        context.dark_mode = true;
+        assert_eq!(schedule.resolve(&context), None);
+        assert_eq!(schedule.next_change(context.now), None);
+    }
+
+    #[test]
+    fn test_schedule_deserialization() {
+        let json = r#"{"kind":"timeOfDay","slots":[{"start":"Evening","wallpaper":"@user/evening"},{"start":"07:45:00","wallpaper":"@user/breakfast"}]}"#;
+        let schedule: WallpaperSchedule = serde_json::from_str(json).unwrap();
+        let WallpaperSchedule::TimeOfDay { slots } = schedule else {
+            panic!("expected time of day schedule");
+        };
+        assert_eq!(slots[0].start, TimeSlotStart::Period(DayPeriod::Evening));
+        assert_eq!(
+            slots[1].start,
+            TimeSlotStart::Time(NaiveTime::from_hms_opt(7, 45, 0).unwrap())
+        );
+
+        let json = r#"{"kind":"dayOfWeek","days":[{"day":"Mon","wallpaper":"@user/evening"}]}"#;
+        assert!(serde_json::from_str::<WallpaperSchedule>(json).is_ok());
+    }
+}
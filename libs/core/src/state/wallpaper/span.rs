@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use crate::{
+    rect::Point,
+    system_state::{MonitorId, PhysicalMonitor},
+};
+
+/// Rect in DPI independent pixels (physical pixels divided by the monitor scale factor).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SpanRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Layout of a single wallpaper spanned across all the monitors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WallpaperSpan {
+    /// width of the whole canvas covered by the wallpaper
+    pub width: f64,
+    /// height of the whole canvas covered by the wallpaper
+    pub height: f64,
+    /// part of the canvas to be rendered by each monitor
+    pub monitors: HashMap<MonitorId, SpanRect>,
+}
+
+/// Monitor bounds on a single axis, `cross` is the range on the other axis.
+struct AxisRange {
+    start: i32,
+    end: i32,
+    cross: (i32, i32),
+    scale: f64,
+}
+
+impl WallpaperSpan {
+    /// Computes the crop of each monitor, sizes are normalized by the scale factor so
+    /// the wallpaper keeps its physical proportions on monitors with different DPIs.
+    /// Offsets are applied after the layout, in DPI independent pixels.
+    pub fn compute(monitors: &[PhysicalMonitor], offsets: &HashMap<MonitorId, Point>) -> Self {
+        if monitors.is_empty() {
+            return Self::default();
+        }
+
+        let scale = |m: &PhysicalMonitor| {
+            if m.scale_factor > 0.0 {
+                m.scale_factor
+            } else {
+                1.0
+            }
+        };
+
+        let horizontal: Vec<AxisRange> = monitors
+            .iter()
+            .map(|m| AxisRange {
+                start: m.rect.left,
+                end: m.rect.right,
+                cross: (m.rect.top, m.rect.bottom),
+                scale: scale(m),
+            })
+            .collect();
+        let vertical: Vec<AxisRange> = monitors
+            .iter()
+            .map(|m| AxisRange {
+                start: m.rect.top,
+                end: m.rect.bottom,
+                cross: (m.rect.left, m.rect.right),
+                scale: scale(m),
+            })
+            .collect();
+
+        let xs = Self::layout_axis(&horizontal);
+        let ys = Self::layout_axis(&vertical);
+
+        let mut rects: Vec<SpanRect> = monitors
+            .iter()
+            .enumerate()
+            .map(|(idx, m)| {
+                let offset = offsets.get(&m.id).cloned().unwrap_or_default();
+                SpanRect {
+                    x: xs[idx] + offset.x as f64,
+                    y: ys[idx] + offset.y as f64,
+                    width: m.rect.width() as f64 / scale(m),
+                    height: m.rect.height() as f64 / scale(m),
+                }
+            })
+            .collect();
+
+        let min_x = rects.iter().map(|r| r.x).fold(f64::INFINITY, f64::min);
+        let min_y = rects.iter().map(|r| r.y).fold(f64::INFINITY, f64::min);
+        for rect in &mut rects {
+            rect.x -= min_x;
+            rect.y -= min_y;
+        }
+
+        Self {
+            width: rects.iter().map(|r| r.x + r.width).fold(0.0, f64::max),
+            height: rects.iter().map(|r| r.y + r.height).fold(0.0, f64::max),
+            monitors: monitors
+                .iter()
+                .zip(rects)
+                .map(|(m, rect)| (m.id.clone(), rect))
+                .collect(),
+        }
+    }
+
+    /// Returns the DPI independent start of each range. Each monitor is placed after its
+    /// nearest previous neighbour (overlapping on the other axis), so monitors touching
+    /// in physical pixels keep touching and gaps are scaled by the neighbour DPI.
+    fn layout_axis(ranges: &[AxisRange]) -> Vec<f64> {
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_by_key(|idx| ranges[*idx].start);
+
+        let mut positions = vec![0.0; ranges.len()];
+        let mut placed: Vec<usize> = Vec::new();
+
+        for idx in order {
+            let current = &ranges[idx];
+            let neighbour = placed
+                .iter()
+                .filter(|other| {
+                    let other = &ranges[**other];
+                    other.end <= current.start
+                        && other.cross.0 < current.cross.1
+                        && current.cross.0 < other.cross.1
+                })
+                .max_by_key(|other| ranges[**other].end);
+
+            positions[idx] = match neighbour {
+                Some(n) => {
+                    let other = &ranges[*n];
+                    positions[*n] + (current.start - other.start) as f64 / other.scale
+                }
+                None => current.start as f64 / current.scale,
+            };
+            placed.push(idx);
+        }
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rect::Rect;
+
+    use super::*;
+
+    fn monitor(
+        id: &str,
+        left: i32,
+        top: i32,
+        width: i32,
+        height: i32,
+        scale: f64,
+    ) -> PhysicalMonitor {
+        PhysicalMonitor {
+            id: MonitorId(id.to_string()),
+            name: id.to_string(),
+            rect: Rect {
+                left,
+                top,
+                right: left + width,
+                bottom: top + height,
+            },
+            scale_factor: scale,
+            is_primary: left == 0 && top == 0,
+        }
+    }
+
+    fn rect_of(span: &WallpaperSpan, id: &str) -> SpanRect {
+        span.monitors[&MonitorId(id.to_string())]
+    }
+
+    #[test]
+    fn test_span_same_dpi() {
+        let monitors = [
+            monitor("left", -1920, 0, 1920, 1080, 1.0),
+            monitor("main", 0, 0, 1920, 1080, 1.0),
+        ];
+        let span = WallpaperSpan::compute(&monitors, &HashMap::new());
+
+        assert_eq!(span.width, 3840.0);
+        assert_eq!(span.height, 1080.0);
+        assert_eq!(rect_of(&span, "left").x, 0.0);
+        assert_eq!(rect_of(&span, "main").x, 1920.0);
+    }
+
+    #[test]
+    fn test_span_mixed_dpi_keeps_monitors_touching() {
+        let monitors = [
+            monitor("4k", 0, 0, 3840, 2160, 2.0),
+            monitor("fhd", 3840, 0, 1920, 1080, 1.0),
+        ];
+        let span = WallpaperSpan::compute(&monitors, &HashMap::new());
+
+        assert_eq!(
+            rect_of(&span, "4k"),
+            SpanRect {
+                x: 0.0,
+                y: 0.0,
+                width: 1920.0,
+                height: 1080.0
+            }
+        );
+        assert_eq!(rect_of(&span, "fhd").x, 1920.0);
+        assert_eq!(span.width, 3840.0);
+    }
+
+    #[test]
+    fn test_span_gaps_and_offsets() {
+        let monitors = [
+            monitor("4k", 0, 0, 3840, 2160, 2.0),
+            monitor("fhd", 4000, 100, 1920, 1080, 1.0),
+        ];
+        let offsets = HashMap::from([(MonitorId("fhd".to_string()), Point::new(10, -100))]);
+        let span = WallpaperSpan::compute(&monitors, &offsets);
+
+        // 160 physical pixels of gap on a 2x monitor
+        let fhd = rect_of(&span, "fhd");
+        assert_eq!(fhd.x, 2010.0);
+        assert_eq!(fhd.y, 0.0);
+        assert_eq!(span.width, 3930.0);
+        assert_eq!(span.height, 1080.0);
+    }
+
+    #[test]
+    fn test_span_stacked_monitors() {
+        let monitors = [
+            monitor("main", 0, 0, 2560, 1440, 1.25),
+            monitor("bottom", 0, 1440, 1920, 1080, 1.0),
+        ];
+        let span = WallpaperSpan::compute(&monitors, &HashMap::new());
+
+        assert_eq!(rect_of(&span, "bottom").y, 1152.0);
+        assert_eq!(rect_of(&span, "bottom").x, 0.0);
+        assert_eq!(span.width, 2048.0);
+        assert_eq!(span.height, 2232.0);
+    }
+}
@@ -1,10 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use ts_rs::TS;
 
-use crate::resource::ResourceText;
+use crate::{resource::ResourceText, utils::TsUnknown};
 
 /// The Widget Settings Declaration is a list of configuration definitions.
 /// Each definition can be either a group (with nested items) or a direct configuration item.
@@ -34,6 +35,66 @@ impl WidgetSettingsDeclarationList {
         false
     }
 
+    /// All the items of the declaration, including the ones inside groups.
+    pub fn items(&self) -> Vec<&WidgetSettingItem> {
+        fn collect<'a>(
+            definition: &'a WidgetConfigDefinition,
+            items: &mut Vec<&'a WidgetSettingItem>,
+        ) {
+            match definition {
+                WidgetConfigDefinition::Group(group) => {
+                    group.items.iter().for_each(|item| collect(item, items));
+                }
+                WidgetConfigDefinition::Item(item) => items.push(item),
+            }
+        }
+
+        let mut items = Vec::new();
+        self.0
+            .iter()
+            .for_each(|definition| collect(definition, &mut items));
+        items
+    }
+
+    /// The default values of all the items.
+    pub fn default_values(&self) -> HashMap<String, Value> {
+        self.items()
+            .into_iter()
+            .map(|item| (item.get_key().to_string(), item.default_value()))
+            .collect()
+    }
+
+    /// Keys of the items that are hidden with the given values, the values should already
+    /// include the defaults. Items inside hidden groups are also hidden.
+    pub fn hidden_keys(&self, values: &HashMap<String, Value>) -> HashSet<&str> {
+        fn collect<'a>(
+            definition: &'a WidgetConfigDefinition,
+            parent_hidden: bool,
+            values: &HashMap<String, Value>,
+            hidden: &mut HashSet<&'a str>,
+        ) {
+            match definition {
+                WidgetConfigDefinition::Group(group) => {
+                    let group_hidden = parent_hidden || !group.is_visible(values);
+                    for item in &group.items {
+                        collect(item, group_hidden, values, hidden);
+                    }
+                }
+                WidgetConfigDefinition::Item(item) => {
+                    if parent_hidden || !item.base().is_visible(values) {
+                        hidden.insert(item.get_key());
+                    }
+                }
+            }
+        }
+
+        let mut hidden = HashSet::new();
+        for definition in &self.0 {
+            collect(definition, false, values, &mut hidden);
+        }
+        hidden
+    }
+
     fn collect_keys_recursive<'a>(
         definition: &'a WidgetConfigDefinition,
         seen: &mut HashSet<&'a str>,
@@ -64,7 +125,7 @@ impl WidgetSettingsDeclarationList {
 pub enum WidgetConfigDefinition {
     /// A group that contains nested configuration items.
     /// Groups are used to organize related settings with headers.
-    Group(WidgetConfigGroup),
+    Group(Box<WidgetConfigGroup>),
     /// A direct configuration item (untagged variant for simpler JSON structure)
     #[serde(untagged)]
     Item(Box<WidgetSettingItem>),
@@ -80,6 +141,22 @@ pub struct WidgetConfigGroup {
     pub description: Option<ResourceText>,
     /// List of items or nested groups in this group
     pub items: Vec<WidgetConfigDefinition>,
+    /// Whether the items can be hidden by clicking the group header
+    #[serde(default)]
+    pub collapsible: bool,
+    /// Whether a collapsible group starts collapsed
+    #[serde(default)]
+    pub collapsed: bool,
+    /// Condition to show the group, the items of hidden groups are hidden too
+    pub visible_if: Option<WidgetSettingCondition>,
+}
+
+impl WidgetConfigGroup {
+    pub fn is_visible(&self, values: &HashMap<String, Value>) -> bool {
+        self.visible_if
+            .as_ref()
+            .is_none_or(|condition| condition.is_met(values))
+    }
 }
 
 impl<'de> Deserialize<'de> for WidgetConfigDefinition {
@@ -97,7 +174,7 @@ impl<'de> Deserialize<'de> for WidgetConfigDefinition {
 
         // Try to deserialize as a group first
         if let Ok(parsed) = GroupVariant::deserialize(value.clone()) {
-            return Ok(WidgetConfigDefinition::Group(parsed.group));
+            return Ok(WidgetConfigDefinition::Group(Box::new(parsed.group)));
         }
 
         // Otherwise deserialize as an item
@@ -154,6 +231,133 @@ impl WidgetSettingItem {
             WidgetSettingItem::Color(item) => &item.base.key,
         }
     }
+
+    /// Returns the fields shared across all setting items
+    pub fn base(&self) -> &WidgetSettingBase {
+        match self {
+            WidgetSettingItem::Switch(item) => &item.base,
+            WidgetSettingItem::Select(item) => &item.base,
+            WidgetSettingItem::InputText(item) => &item.base,
+            WidgetSettingItem::InputNumber(item) => &item.base,
+            WidgetSettingItem::Range(item) => &item.base,
+            WidgetSettingItem::Color(item) => &item.base,
+        }
+    }
+
+    pub fn default_value(&self) -> Value {
+        match self {
+            WidgetSettingItem::Switch(item) => item.default_value.into(),
+            WidgetSettingItem::Select(item) => item.default_value.clone().into(),
+            WidgetSettingItem::InputText(item) => item.default_value.clone().into(),
+            WidgetSettingItem::InputNumber(item) => item.default_value.into(),
+            WidgetSettingItem::Range(item) => item.default_value.into(),
+            WidgetSettingItem::Color(item) => item.default_value.clone().into(),
+        }
+    }
+
+    /// Checks a stored value against this declaration.
+    pub fn check_value(&self, value: &Value) -> WidgetSettingCheck {
+        let checked = match self {
+            WidgetSettingItem::Switch(_) => match value {
+                Value::Bool(_) => return WidgetSettingCheck::Valid,
+                Value::String(s) => s.parse::<bool>().ok().map(Value::from),
+                Value::Number(n) => match n.as_i64() {
+                    Some(0) => Some(false.into()),
+                    Some(1) => Some(true.into()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            WidgetSettingItem::Select(item) => {
+                let selected = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Number(_) | Value::Bool(_) => value.to_string(),
+                    _ => return WidgetSettingCheck::Invalid,
+                };
+                item.options
+                    .iter()
+                    .any(|option| option.value == selected)
+                    .then(|| selected.into())
+            }
+            WidgetSettingItem::InputText(item) => {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Number(_) | Value::Bool(_) => value.to_string(),
+                    _ => return WidgetSettingCheck::Invalid,
+                };
+                let len = text.chars().count() as u32;
+                if item.min_length.is_some_and(|min| len < min) {
+                    None
+                } else {
+                    match item.max_length {
+                        Some(max) if len > max => {
+                            Some(text.chars().take(max as usize).collect::<String>().into())
+                        }
+                        _ => Some(text.into()),
+                    }
+                }
+            }
+            WidgetSettingItem::InputNumber(WidgetSettingInputNumber { min, max, .. })
+            | WidgetSettingItem::Range(WidgetSettingRange { min, max, .. }) => {
+                let number = match value {
+                    Value::Number(n) => n.as_f64(),
+                    Value::String(s) => s.trim().parse::<f64>().ok(),
+                    _ => None,
+                };
+                number.filter(|n| n.is_finite()).map(|n| {
+                    let n = min.map_or(n, |min| n.max(min));
+                    let n = max.map_or(n, |max| n.min(max));
+                    match value.as_f64() {
+                        Some(original) if original == n => value.clone(),
+                        _ => number_like(n, value),
+                    }
+                })
+            }
+            WidgetSettingItem::Color(item) => value.as_str().and_then(|color| {
+                let hex = color.strip_prefix('#')?;
+                if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                match hex.len() {
+                    3 | 6 => Some(color.into()),
+                    4 | 8 if item.allow_alpha => Some(color.into()),
+                    // alpha channel is removed if not allowed
+                    4 => Some(color[..4].into()),
+                    8 => Some(color[..7].into()),
+                    _ => None,
+                }
+            }),
+        };
+
+        match checked {
+            Some(checked) if &checked == value => WidgetSettingCheck::Valid,
+            Some(checked) => WidgetSettingCheck::Coerced(checked),
+            None => WidgetSettingCheck::Invalid,
+        }
+    }
+}
+
+/// The number with the json kind of the original value, so integers are kept as integers.
+fn number_like(n: f64, original: &Value) -> Value {
+    let is_integer = match original {
+        Value::Number(number) => number.is_i64() || number.is_u64(),
+        Value::String(s) => s.trim().parse::<i64>().is_ok(),
+        _ => false,
+    };
+    if is_integer && n.fract() == 0.0 && (i64::MIN as f64..=i64::MAX as f64).contains(&n) {
+        return Value::from(n as i64);
+    }
+    n.into()
+}
+
+/// Result of checking a stored setting value against its declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WidgetSettingCheck {
+    Valid,
+    /// The value can be converted to a valid one, ex: a number out of bounds is clamped.
+    Coerced(Value),
+    /// The value should be reset to the default one.
+    Invalid,
 }
 
 /// Common fields shared across all widget setting items
@@ -179,6 +383,103 @@ pub struct WidgetSettingBase {
     /// Keys of settings that must be enabled for this item to be active.\
     /// Uses JavaScript truthy logic (!!value) to determine if dependency is met.
     pub dependencies: Vec<String>,
+
+    /// Condition over the values of other settings to show this item.\
+    /// Hidden items keep their stored values.
+    pub visible_if: Option<WidgetSettingCondition>,
+}
+
+impl WidgetSettingBase {
+    /// Whether the dependencies and the `visibleIf` condition are met with the given values.
+    pub fn is_visible(&self, values: &HashMap<String, Value>) -> bool {
+        self.dependencies
+            .iter()
+            .all(|key| values.get(key).is_some_and(is_truthy))
+            && self
+                .visible_if
+                .as_ref()
+                .is_none_or(|condition| condition.is_met(values))
+    }
+}
+
+/// Expression over the values of the widget settings, used to show or hide items and groups.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(untagged)]
+pub enum WidgetSettingCondition {
+    /// All the conditions must be met
+    All { all: Vec<WidgetSettingCondition> },
+    /// At least one of the conditions must be met
+    Any { any: Vec<WidgetSettingCondition> },
+    /// The condition must not be met
+    Not { not: Box<WidgetSettingCondition> },
+    /// Checks the value of a setting
+    Value(WidgetSettingValueCondition),
+}
+
+impl WidgetSettingCondition {
+    pub fn is_met(&self, values: &HashMap<String, Value>) -> bool {
+        match self {
+            WidgetSettingCondition::All { all } => all.iter().all(|c| c.is_met(values)),
+            WidgetSettingCondition::Any { any } => any.iter().any(|c| c.is_met(values)),
+            WidgetSettingCondition::Not { not } => !not.is_met(values),
+            WidgetSettingCondition::Value(condition) => condition.is_met(values),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WidgetSettingValueCondition {
+    /// Key of the setting to check
+    pub key: String,
+    /// The value must be equal to this one
+    pub equals: Option<TsUnknown>,
+    /// The value must be one of these
+    pub one_of: Option<Vec<TsUnknown>>,
+}
+
+impl WidgetSettingValueCondition {
+    /// Without `equals` or `oneOf` the value only needs to be truthy, as on JavaScript.
+    pub fn is_met(&self, values: &HashMap<String, Value>) -> bool {
+        let value = values.get(&self.key).unwrap_or(&Value::Null);
+        match (&self.equals, &self.one_of) {
+            (None, None) => is_truthy(value),
+            (equals, one_of) => {
+                equals
+                    .as_ref()
+                    .is_none_or(|expected| loose_eq(value, &expected.0))
+                    && one_of.as_ref().is_none_or(|options| {
+                        options.iter().any(|option| loose_eq(value, &option.0))
+                    })
+            }
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0 && !n.is_nan()),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+/// Deep equality where numbers are compared by value, so `1` is equal to `1.0`.
+fn loose_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| loose_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| loose_eq(a, b)))
+        }
+        _ => a == b,
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
@@ -281,3 +582,272 @@ pub enum WidgetSelectSubtype {
     /// Render as inline buttons/tabs
     Inline,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn item(declaration: Value) -> WidgetSettingItem {
+        serde_json::from_value(declaration).unwrap()
+    }
+
+    #[test]
+    fn test_check_switch() {
+        let switch = item(json!({ "type": "switch", "key": "k", "defaultValue": true }));
+        assert_eq!(switch.check_value(&json!(false)), WidgetSettingCheck::Valid);
+        assert_eq!(
+            switch.check_value(&json!("true")),
+            WidgetSettingCheck::Coerced(json!(true))
+        );
+        assert_eq!(
+            switch.check_value(&json!(0)),
+            WidgetSettingCheck::Coerced(json!(false))
+        );
+        assert_eq!(switch.check_value(&json!(5)), WidgetSettingCheck::Invalid);
+        assert_eq!(switch.default_value(), json!(true));
+    }
+
+    #[test]
+    fn test_check_select() {
+        let select = item(json!({
+            "type": "select",
+            "key": "k",
+            "defaultValue": "a",
+            "options": [{ "label": "A", "value": "a" }, { "label": "One", "value": "1" }],
+        }));
+        assert_eq!(select.check_value(&json!("a")), WidgetSettingCheck::Valid);
+        assert_eq!(
+            select.check_value(&json!(1)),
+            WidgetSettingCheck::Coerced(json!("1"))
+        );
+        assert_eq!(select.check_value(&json!("b")), WidgetSettingCheck::Invalid);
+        assert_eq!(
+            select.check_value(&json!(["a"])),
+            WidgetSettingCheck::Invalid
+        );
+    }
+
+    #[test]
+    fn test_check_text() {
+        let text = item(json!({
+            "type": "text",
+            "key": "k",
+            "minLength": 2,
+            "maxLength": 4,
+        }));
+        assert_eq!(text.check_value(&json!("abc")), WidgetSettingCheck::Valid);
+        assert_eq!(
+            text.check_value(&json!("abcdef")),
+            WidgetSettingCheck::Coerced(json!("abcd"))
+        );
+        assert_eq!(
+            text.check_value(&json!(123)),
+            WidgetSettingCheck::Coerced(json!("123"))
+        );
+        assert_eq!(text.check_value(&json!("a")), WidgetSettingCheck::Invalid);
+        assert_eq!(text.check_value(&Value::Null), WidgetSettingCheck::Invalid);
+    }
+
+    #[test]
+    fn test_check_number() {
+        let number = item(json!({ "type": "number", "key": "k", "min": 1 }));
+        assert_eq!(number.check_value(&json!(1)), WidgetSettingCheck::Valid);
+        assert_eq!(number.check_value(&json!(100.5)), WidgetSettingCheck::Valid);
+        assert_eq!(
+            number.check_value(&json!(-3)),
+            WidgetSettingCheck::Coerced(json!(1))
+        );
+        assert_eq!(
+            number.check_value(&json!(-3.5)),
+            WidgetSettingCheck::Coerced(json!(1.0))
+        );
+        assert_eq!(
+            number.check_value(&json!(" 7 ")),
+            WidgetSettingCheck::Coerced(json!(7))
+        );
+        assert_eq!(
+            number.check_value(&json!("7.5")),
+            WidgetSettingCheck::Coerced(json!(7.5))
+        );
+        assert_eq!(
+            number.check_value(&json!("seven")),
+            WidgetSettingCheck::Invalid
+        );
+    }
+
+    #[test]
+    fn test_check_range() {
+        let range = item(json!({
+            "type": "range",
+            "key": "k",
+            "defaultValue": 5,
+            "min": 0,
+            "max": 10,
+        }));
+        assert_eq!(range.check_value(&json!(10)), WidgetSettingCheck::Valid);
+        assert_eq!(range.check_value(&json!(3u64)), WidgetSettingCheck::Valid);
+        // integers are kept as integers
+        assert_eq!(
+            range.check_value(&json!(20)),
+            WidgetSettingCheck::Coerced(json!(10))
+        );
+        assert_eq!(
+            range.check_value(&json!(10.5)),
+            WidgetSettingCheck::Coerced(json!(10.0))
+        );
+        assert_eq!(range.check_value(&json!(true)), WidgetSettingCheck::Invalid);
+        assert_eq!(range.default_value(), json!(5.0));
+    }
+
+    #[test]
+    fn test_check_color() {
+        let color = item(json!({ "type": "color", "key": "k", "defaultValue": "#000000" }));
+        assert_eq!(
+            color.check_value(&json!("#ff00AA")),
+            WidgetSettingCheck::Valid
+        );
+        assert_eq!(color.check_value(&json!("#fff")), WidgetSettingCheck::Valid);
+        assert_eq!(
+            color.check_value(&json!("#ff00aa80")),
+            WidgetSettingCheck::Coerced(json!("#ff00aa"))
+        );
+        assert_eq!(
+            color.check_value(&json!("red")),
+            WidgetSettingCheck::Invalid
+        );
+        assert_eq!(
+            color.check_value(&json!("#ggg")),
+            WidgetSettingCheck::Invalid
+        );
+
+        let color = item(json!({ "type": "color", "key": "k", "allowAlpha": true }));
+        assert_eq!(
+            color.check_value(&json!("#ff00aa80")),
+            WidgetSettingCheck::Valid
+        );
+    }
+
+    #[test]
+    fn test_items_inside_groups() {
+        let list: WidgetSettingsDeclarationList = serde_json::from_value(json!([
+            { "type": "switch", "key": "a" },
+            { "group": { "label": "Group", "items": [{ "type": "range", "key": "b" }] } },
+        ]))
+        .unwrap();
+        let keys: Vec<&str> = list.items().iter().map(|item| item.get_key()).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let values: HashMap<String, Value> = serde_json::from_value(json!({
+            "enabled": true,
+            "mode": "compact",
+            "size": 2,
+            "label": "",
+        }))
+        .unwrap();
+        let is_met = |condition: Value| {
+            serde_json::from_value::<WidgetSettingCondition>(condition)
+                .unwrap()
+                .is_met(&values)
+        };
+
+        assert!(is_met(json!({ "key": "enabled" })));
+        assert!(!is_met(json!({ "key": "label" })));
+        assert!(!is_met(json!({ "key": "missing" })));
+        assert!(is_met(json!({ "key": "mode", "equals": "compact" })));
+        assert!(is_met(json!({ "key": "size", "equals": 2.0 })));
+        assert!(is_met(
+            json!({ "key": "mode", "oneOf": ["full", "compact"] })
+        ));
+        assert!(!is_met(json!({ "key": "mode", "oneOf": ["full"] })));
+        assert!(is_met(json!({ "not": { "key": "label" } })));
+        assert!(is_met(json!({
+            "all": [{ "key": "enabled" }, { "any": [{ "key": "label" }, { "key": "size" }] }],
+        })));
+        assert!(!is_met(
+            json!({ "all": [{ "key": "enabled" }, { "key": "label" }] })
+        ));
+
+        // typos are not ignored
+        assert!(serde_json::from_value::<WidgetSettingCondition>(
+            json!({ "key": "mode", "equal": "compact" })
+        )
+        .is_err());
+    }
+
+    /// Same vectors as `declaration.test.ts`, both implementations must agree.
+    #[test]
+    fn test_shared_condition_vectors() {
+        let vectors: Value = serde_json::from_str(
+            &std::fs::read_to_string("./mocks/widget_settings/conditions.json").unwrap(),
+        )
+        .unwrap();
+
+        for case in vectors["conditions"].as_array().unwrap() {
+            let values: HashMap<String, Value> =
+                serde_json::from_value(case["values"].clone()).unwrap();
+            let condition: WidgetSettingCondition =
+                serde_json::from_value(case["condition"].clone()).unwrap();
+            assert_eq!(
+                condition.is_met(&values),
+                case["met"].as_bool().unwrap(),
+                "{}",
+                case["name"]
+            );
+        }
+
+        for case in vectors["hidden"].as_array().unwrap() {
+            let list: WidgetSettingsDeclarationList =
+                serde_json::from_value(case["declaration"].clone()).unwrap();
+            let mut values = list.default_values();
+            values.extend(
+                serde_json::from_value::<HashMap<String, Value>>(case["values"].clone()).unwrap(),
+            );
+            let mut hidden: Vec<&str> = list.hidden_keys(&values).into_iter().collect();
+            hidden.sort();
+            assert_eq!(json!(hidden), case["hidden"], "{}", case["name"]);
+        }
+    }
+
+    #[test]
+    fn test_hidden_keys() {
+        let list: WidgetSettingsDeclarationList = serde_json::from_value(json!([
+            { "type": "switch", "key": "advanced" },
+            { "type": "select", "key": "mode", "defaultValue": "full" },
+            {
+                "type": "range",
+                "key": "size",
+                "visibleIf": { "key": "mode", "equals": "compact" },
+            },
+            {
+                "group": {
+                    "label": "Advanced",
+                    "collapsible": true,
+                    "visibleIf": { "key": "advanced" },
+                    "items": [
+                        { "type": "switch", "key": "debug" },
+                        { "type": "text", "key": "log", "dependencies": ["debug"] },
+                    ],
+                },
+            },
+        ]))
+        .unwrap();
+
+        let mut values = list.default_values();
+        let mut hidden: Vec<&str> = list.hidden_keys(&values).into_iter().collect();
+        hidden.sort();
+        assert_eq!(hidden, vec!["debug", "log", "size"]);
+
+        values.insert("advanced".to_string(), json!(true));
+        values.insert("mode".to_string(), json!("compact"));
+        let hidden: Vec<&str> = list.hidden_keys(&values).into_iter().collect();
+        assert_eq!(hidden, vec!["log"]);
+
+        values.insert("debug".to_string(), json!(true));
+        assert!(list.hidden_keys(&values).is_empty());
+    }
+}
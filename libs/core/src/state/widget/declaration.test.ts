@@ -0,0 +1,33 @@
+import type { WidgetSettingCondition, WidgetSettingsDeclarationList } from "@seelen-ui/types";
+import { assertEquals } from "@std/assert";
+import { getHiddenWidgetSettings, getWidgetSettingsDefaultValues, isWidgetSettingConditionMet } from "./declaration.ts";
+// same vectors as `test_shared_condition_vectors` on declaration.rs, both implementations must agree
+import vectors from "../../../mocks/widget_settings/conditions.json" with { type: "json" };
+
+interface ConditionVector {
+  name: string;
+  values: Record<string, unknown>;
+  condition: WidgetSettingCondition;
+  met: boolean;
+}
+
+interface HiddenVector {
+  name: string;
+  declaration: WidgetSettingsDeclarationList;
+  values: Record<string, unknown>;
+  hidden: string[];
+}
+
+for (const vector of vectors.conditions as unknown as ConditionVector[]) {
+  Deno.test(`isWidgetSettingConditionMet - ${vector.name}`, () => {
+    assertEquals(isWidgetSettingConditionMet(vector.condition, vector.values), vector.met);
+  });
+}
+
+for (const vector of vectors.hidden as unknown as HiddenVector[]) {
+  Deno.test(`getHiddenWidgetSettings - ${vector.name}`, () => {
+    const values = { ...getWidgetSettingsDefaultValues(vector.declaration), ...vector.values };
+    const hidden = [...getHiddenWidgetSettings(vector.declaration, values)].sort();
+    assertEquals(hidden, vector.hidden);
+  });
+}
@@ -0,0 +1,120 @@
+import type {
+  WidgetConfigDefinition,
+  WidgetConfigGroup,
+  WidgetSettingCondition,
+  WidgetSettingItem,
+  WidgetSettingsDeclarationList,
+} from "@seelen-ui/types";
+
+type SettingsValues = Record<string, unknown>;
+
+function getDefinitionDefaultValues(definition: WidgetConfigDefinition): Record<string, unknown> {
+  const config: Record<string, unknown> = {};
+
+  // Check if it's a group (has "group" property)
+  if ("group" in definition) {
+    // Recursively process all items in the group
+    for (const item of definition.group.items) {
+      Object.assign(config, getDefinitionDefaultValues(item));
+    }
+  } else {
+    // It's a setting item, extract key and defaultValue
+    const item = definition as WidgetSettingItem;
+    if ("key" in item && "defaultValue" in item) {
+      config[item.key] = item.defaultValue;
+    }
+  }
+
+  return config;
+}
+
+/** The default values of all the items, including the ones inside groups */
+export function getWidgetSettingsDefaultValues(declaration: WidgetSettingsDeclarationList): SettingsValues {
+  const config: SettingsValues = {};
+  for (const definition of declaration) {
+    Object.assign(config, getDefinitionDefaultValues(definition));
+  }
+  return config;
+}
+
+// same evaluation as `WidgetSettingCondition::is_met` on declaration.rs, both should be kept in sync
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+/** Deep equality where the key order of the objects does not matter, missing values are equal to null */
+function looseEquals(a: unknown, b: unknown): boolean {
+  if (Array.isArray(a) || Array.isArray(b)) {
+    return Array.isArray(a) && Array.isArray(b) && a.length === b.length &&
+      a.every((item, idx) => looseEquals(item, b[idx]));
+  }
+  if (isPlainObject(a) && isPlainObject(b)) {
+    const keys = Object.keys(a);
+    return keys.length === Object.keys(b).length &&
+      keys.every((key) => Object.hasOwn(b, key) && looseEquals(a[key], b[key]));
+  }
+  return (a ?? null) === (b ?? null);
+}
+
+export function isWidgetSettingConditionMet(condition: WidgetSettingCondition, values: SettingsValues): boolean {
+  if ("all" in condition) {
+    return condition.all.every((c) => isWidgetSettingConditionMet(c, values));
+  }
+  if ("any" in condition) {
+    return condition.any.some((c) => isWidgetSettingConditionMet(c, values));
+  }
+  if ("not" in condition) {
+    return !isWidgetSettingConditionMet(condition.not, values);
+  }
+
+  const value = values[condition.key];
+  if (condition.equals == null && condition.oneOf == null) {
+    return !!value;
+  }
+  return (
+    (condition.equals == null || looseEquals(value, condition.equals)) &&
+    (condition.oneOf == null || condition.oneOf.some((option) => looseEquals(value, option)))
+  );
+}
+
+/** Whether the dependencies and the `visibleIf` condition of the item are met */
+export function isWidgetSettingVisible(item: WidgetSettingItem, values: SettingsValues): boolean {
+  const dependenciesMet = (item.dependencies ?? []).every((key) => !!values[key]);
+  return dependenciesMet && (!item.visibleIf || isWidgetSettingConditionMet(item.visibleIf, values));
+}
+
+export function isWidgetConfigGroupVisible(group: WidgetConfigGroup, values: SettingsValues): boolean {
+  return !group.visibleIf || isWidgetSettingConditionMet(group.visibleIf, values);
+}
+
+/**
+ * Keys of the items that are hidden with the given values, the values should already include the defaults.
+ * Items inside hidden groups are also hidden.
+ */
+export function getHiddenWidgetSettings(
+  declaration: WidgetSettingsDeclarationList,
+  values: SettingsValues,
+): Set<string> {
+  const hidden = new Set<string>();
+
+  const collect = (definition: WidgetConfigDefinition, parentHidden: boolean) => {
+    if ("group" in definition) {
+      const groupHidden = parentHidden || !isWidgetConfigGroupVisible(definition.group, values);
+      for (const item of definition.group.items) {
+        collect(item, groupHidden);
+      }
+      return;
+    }
+
+    const item = definition as WidgetSettingItem;
+    if (parentHidden || !isWidgetSettingVisible(item, values)) {
+      hidden.add(item.key);
+    }
+  };
+
+  for (const definition of declaration) {
+    collect(definition, false);
+  }
+  return hidden;
+}
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::resource::{InternalResourceMetadata, WidgetId};
+
+use super::{Widget, WidgetLoader};
+
+/// How the running instances of a widget are updated to a new version of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidgetReloadKind {
+    Unchanged,
+    /// Only the css changed, so it can be injected again on the running webviews.
+    Css,
+    /// The js or html changed, the webviews are reloaded.
+    Page,
+    /// The declaration changed, ex: instances mode, so the instances are created again.
+    Instances,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetReloadError {
+    pub widget_id: Option<WidgetId>,
+    /// Changed file that could not be loaded.
+    pub path: PathBuf,
+    pub error: String,
+}
+
+impl Widget {
+    /// Compares the widget against a new version of it, loaded from the same folder.
+    pub fn reload_kind(&self, new: &Widget) -> WidgetReloadKind {
+        if declaration(self) != declaration(new) {
+            return WidgetReloadKind::Instances;
+        }
+        if self.js != new.js || self.html != new.html {
+            return WidgetReloadKind::Page;
+        }
+        if self.css != new.css {
+            // only third party widgets take the css from the definition
+            return match self.loader {
+                WidgetLoader::ThirdParty => WidgetReloadKind::Css,
+                _ => WidgetReloadKind::Page,
+            };
+        }
+        WidgetReloadKind::Unchanged
+    }
+}
+
+/// The widget without the code and file info, serialized for comparison.
+fn declaration(widget: &Widget) -> Option<serde_json::Value> {
+    let mut widget = widget.clone();
+    widget.js = None;
+    widget.css = None;
+    widget.html = None;
+    widget.metadata.internal = InternalResourceMetadata::default();
+    serde_json::to_value(widget).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::state::WidgetInstanceMode;
+
+    fn widget() -> Widget {
+        Widget {
+            id: "@user/widget".into(),
+            loader: WidgetLoader::ThirdParty,
+            js: Some("console.log(1)".to_string()),
+            css: Some("body { color: red; }".to_string()),
+            html: Some("<div></div>".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reload_kind() {
+        let old = widget();
+
+        let mut new = widget();
+        new.metadata.internal.written_at = Utc::now();
+        assert_eq!(old.reload_kind(&new), WidgetReloadKind::Unchanged);
+
+        new.css = Some("body { color: blue; }".to_string());
+        assert_eq!(old.reload_kind(&new), WidgetReloadKind::Css);
+
+        new.js = None;
+        assert_eq!(old.reload_kind(&new), WidgetReloadKind::Page);
+
+        new.instances = WidgetInstanceMode::Multiple;
+        assert_eq!(old.reload_kind(&new), WidgetReloadKind::Instances);
+    }
+
+    #[test]
+    fn test_css_of_internal_widgets_reloads_page() {
+        let mut old = widget();
+        old.loader = WidgetLoader::InternalReact;
+        let mut new = old.clone();
+        new.css = None;
+        assert_eq!(old.reload_kind(&new), WidgetReloadKind::Page);
+    }
+}
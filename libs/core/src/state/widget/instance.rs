@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::resource::WidgetId;
+
+use super::{Widget, WidgetCrashInfo, WidgetStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub enum WidgetInstanceType {
+    /// Instances defined in user settings
+    Static,
+    /// Instances created dynamically during runtime
+    Runtime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetInstanceInfo {
+    /// Decoded webview label, ex: `@seelen/widget?instanceId=...`
+    pub label: String,
+    pub instance_type: WidgetInstanceType,
+    pub status: WidgetStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetInfo {
+    pub id: WidgetId,
+    pub enabled: bool,
+    pub lazy: bool,
+    /// Loaded instances, always empty for disabled widgets.
+    pub instances: Vec<WidgetInstanceInfo>,
+}
+
+/// Creates the webviews of the widget instances.
+pub trait WidgetWebviewFactory {
+    type Webview;
+
+    fn create(
+        &self,
+        widget: &Widget,
+        instance: &WidgetInstanceState<Self::Webview>,
+    ) -> Result<Self::Webview, String>;
+}
+
+/// Lifecycle of a widget instance, independent of how its webview is created.
+#[derive(Debug)]
+pub struct WidgetInstanceState<W> {
+    label: String,
+    instance_type: WidgetInstanceType,
+    status: WidgetStatus,
+    webview: Option<W>,
+}
+
+impl<W> WidgetInstanceState<W> {
+    pub fn new(label: impl Into<String>, instance_type: WidgetInstanceType) -> Self {
+        Self {
+            label: label.into(),
+            instance_type,
+            status: WidgetStatus::Pending,
+            webview: None,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn instance_type(&self) -> WidgetInstanceType {
+        self.instance_type
+    }
+
+    pub fn status(&self) -> &WidgetStatus {
+        &self.status
+    }
+
+    pub fn set_status(&mut self, status: WidgetStatus) {
+        self.status = status;
+    }
+
+    pub fn webview(&self) -> Option<&W> {
+        self.webview.as_ref()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.webview.is_some() && self.status == WidgetStatus::Ready
+    }
+
+    /// Creates the webview if the instance is pending, returns true if it was created.
+    pub fn start<F>(&mut self, widget: &Widget, factory: &F) -> Result<bool, String>
+    where
+        F: WidgetWebviewFactory<Webview = W>,
+    {
+        if self.status != WidgetStatus::Pending {
+            return Ok(false);
+        }
+
+        self.set_status(WidgetStatus::Creating);
+        match factory.create(widget, self) {
+            Ok(webview) => {
+                self.webview = Some(webview);
+                self.set_status(WidgetStatus::Mounting);
+                Ok(true)
+            }
+            Err(err) => {
+                // the crash history is completed by the supervisor of the widget
+                self.set_status(WidgetStatus::CrashedOnCreation(WidgetCrashInfo {
+                    last_error: Some(err.clone()),
+                    ..Default::default()
+                }));
+                Err(err)
+            }
+        }
+    }
+
+    /// Handles the destruction of the webview, returns true if the instance should be removed.
+    pub fn on_destroyed(&mut self) -> bool {
+        match self.instance_type {
+            WidgetInstanceType::Runtime => true,
+            WidgetInstanceType::Static => {
+                self.webview = None;
+                self.set_status(WidgetStatus::Pending);
+                false
+            }
+        }
+    }
+
+    pub fn info(&self) -> WidgetInstanceInfo {
+        WidgetInstanceInfo {
+            label: self.label.clone(),
+            instance_type: self.instance_type,
+            status: self.status.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeFactory {
+        fail: bool,
+        created: RefCell<Vec<String>>,
+    }
+
+    impl WidgetWebviewFactory for FakeFactory {
+        type Webview = String;
+
+        fn create(
+            &self,
+            _widget: &Widget,
+            instance: &WidgetInstanceState<String>,
+        ) -> Result<String, String> {
+            assert_eq!(instance.status(), &WidgetStatus::Creating);
+            if self.fail {
+                return Err("no webview2 runtime".to_string());
+            }
+            self.created.borrow_mut().push(instance.label().to_string());
+            Ok(instance.label().to_string())
+        }
+    }
+
+    #[test]
+    fn test_start_creates_webview_once() {
+        let factory = FakeFactory::default();
+        let widget = Widget::default();
+        let mut instance = WidgetInstanceState::new("@user/w", WidgetInstanceType::Static);
+
+        assert_eq!(instance.start(&widget, &factory), Ok(true));
+        assert_eq!(instance.status(), &WidgetStatus::Mounting);
+        assert!(!instance.is_ready());
+
+        instance.set_status(WidgetStatus::Ready);
+        assert!(instance.is_ready());
+        assert_eq!(instance.start(&widget, &factory), Ok(false));
+        assert_eq!(*factory.created.borrow(), vec!["@user/w"]);
+    }
+
+    #[test]
+    fn test_start_failure() {
+        let factory = FakeFactory {
+            fail: true,
+            ..Default::default()
+        };
+        let mut instance =
+            WidgetInstanceState::<String>::new("@user/w", WidgetInstanceType::Static);
+
+        assert_eq!(
+            instance.start(&Widget::default(), &factory),
+            Err("no webview2 runtime".to_string())
+        );
+        assert_eq!(
+            instance.status(),
+            &WidgetStatus::CrashedOnCreation(WidgetCrashInfo {
+                last_error: Some("no webview2 runtime".to_string()),
+                ..Default::default()
+            })
+        );
+        assert!(instance.webview().is_none());
+    }
+
+    #[test]
+    fn test_destroyed() {
+        let factory = FakeFactory::default();
+        let widget = Widget::default();
+
+        let mut instance = WidgetInstanceState::new("@user/w", WidgetInstanceType::Static);
+        instance.start(&widget, &factory).unwrap();
+        assert!(!instance.on_destroyed());
+        assert_eq!(
+            instance.info(),
+            WidgetInstanceInfo {
+                label: "@user/w".to_string(),
+                instance_type: WidgetInstanceType::Static,
+                status: WidgetStatus::Pending,
+            }
+        );
+        // static instances can be started again
+        assert_eq!(instance.start(&widget, &factory), Ok(true));
+
+        let mut instance =
+            WidgetInstanceState::new("@user/w?instanceId=1", WidgetInstanceType::Runtime);
+        instance.start(&widget, &factory).unwrap();
+        assert!(instance.on_destroyed());
+    }
+}
@@ -1,5 +1,14 @@
 pub mod context_menu;
 pub mod declaration;
+mod hot_reload;
+mod instance;
+mod registry;
+mod supervisor;
+
+pub use hot_reload::*;
+pub use instance::*;
+pub use registry::*;
+pub use supervisor::*;
 
 use std::{collections::HashMap, path::Path};
 
@@ -38,6 +47,8 @@ pub struct Widget {
     pub lazy: bool,
     /// How many instances are allowed of this widget.
     pub instances: WidgetInstanceMode,
+    /// What to do when an instance fails to be created or its webview is destroyed.
+    pub restart_policy: WidgetRestartPolicy,
     /// If true, the widget will not be shown on the Settings Navigation as a Tab, but it will
     /// still be available on the widgets full list.
     pub hidden: bool,
@@ -223,8 +234,8 @@ pub enum Alignment {
     End,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
-#[cfg_attr(feature = "gen-binds", ts(export, repr(enum = name)))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[cfg_attr(feature = "gen-binds", ts(export))]
 pub enum WidgetStatus {
     /// Widget has been registered, but not yet loaded
     Pending,
@@ -234,6 +245,6 @@ pub enum WidgetStatus {
     Mounting,
     /// Widget loaded and is ready
     Ready,
-    /// Webview window failed to be created.
-    CrashedOnCreation,
+    /// Webview window failed to be created, with the crash history of the instance.
+    CrashedOnCreation(WidgetCrashInfo),
 }
@@ -3,11 +3,9 @@ import {
   type Rect,
   type ThirdPartyWidgetSettings,
   type Widget as IWidget,
-  type WidgetConfigDefinition,
   type WidgetId,
   WidgetPreset,
-  type WidgetSettingItem,
-  WidgetStatus,
+  type WidgetStatus,
   type WidgetTriggerPayload,
 } from "@seelen-ui/types";
 import { invoke, SeelenCommand, SeelenEvent } from "../../handlers/mod.ts";
@@ -19,6 +17,9 @@ import { adjustPositionByPlacement, fitIntoMonitor, initMonitorsState } from "./
 import { startThemingTool } from "../theme/theming.ts";
 import type { InitWidgetOptions, ReadyWidgetOptions, WidgetInformation } from "./interfaces.ts";
 import { getCurrentWebview } from "@tauri-apps/api/webview";
+import { getWidgetSettingsDefaultValues } from "./declaration.ts";
+
+export * from "./declaration.ts";
 
 interface WidgetInternalState {
   hwnd: number;
@@ -119,9 +120,7 @@ export class Widget {
   /** Returns the default config of the widget, declared on the widget definition */
   public getDefaultConfig(): ThirdPartyWidgetSettings {
     const config: ThirdPartyWidgetSettings = { enabled: true };
-    for (const definition of this.def.settings) {
-      Object.assign(config, getDefinitionDefaultValues(definition));
-    }
+    Object.assign(config, getWidgetSettingsDefaultValues(this.def.settings));
     return config;
   }
 
@@ -321,7 +320,8 @@ export class Widget {
     }
 
     // this will mark the widget as ready, and send pending trigger event if exists
-    await invoke(SeelenCommand.SetCurrentWidgetStatus, { status: WidgetStatus.Ready });
+    const status: WidgetStatus = "Ready";
+    await invoke(SeelenCommand.SetCurrentWidgetStatus, { status });
   }
 
   public onTrigger(cb: (args: WidgetTriggerPayload) => void): void {
@@ -396,23 +396,3 @@ function getDecodedWebviewLabel(): [WidgetId, string | undefined] {
   }
   return [id as WidgetId, query];
 }
-
-function getDefinitionDefaultValues(definition: WidgetConfigDefinition): Record<string, unknown> {
-  const config: Record<string, unknown> = {};
-
-  // Check if it's a group (has "group" property)
-  if ("group" in definition) {
-    // Recursively process all items in the group
-    for (const item of definition.group.items) {
-      Object.assign(config, getDefinitionDefaultValues(item));
-    }
-  } else {
-    // It's a setting item, extract key and defaultValue
-    const item = definition as WidgetSettingItem;
-    if ("key" in item && "defaultValue" in item) {
-      config[item.key] = item.defaultValue;
-    }
-  }
-
-  return config;
-}
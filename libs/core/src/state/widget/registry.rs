@@ -0,0 +1,521 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use crate::resource::WidgetId;
+
+use super::{
+    Widget, WidgetInfo, WidgetInstanceInfo, WidgetInstanceState, WidgetInstanceType, WidgetStatus,
+    WidgetSupervisor, WidgetWebviewFactory,
+};
+
+/// Instances of a loaded widget by label, with the crash history used to restart them.
+#[derive(Debug)]
+pub struct WidgetGroup<W> {
+    definition: Arc<Widget>,
+    instances: BTreeMap<String, WidgetInstanceState<W>>,
+    supervisor: WidgetSupervisor,
+}
+
+impl<W> WidgetGroup<W> {
+    pub fn new(definition: Arc<Widget>, static_labels: impl IntoIterator<Item = String>) -> Self {
+        let instances = static_labels
+            .into_iter()
+            .map(|label| {
+                let instance = WidgetInstanceState::new(label.clone(), WidgetInstanceType::Static);
+                (label, instance)
+            })
+            .collect();
+        Self {
+            supervisor: WidgetSupervisor::new(definition.restart_policy),
+            definition,
+            instances,
+        }
+    }
+
+    pub fn definition(&self) -> &Arc<Widget> {
+        &self.definition
+    }
+
+    /// Used on hot reload, the running instances are kept.
+    pub fn set_definition(&mut self, definition: Arc<Widget>) {
+        self.definition = definition;
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.instances.keys().cloned().collect()
+    }
+
+    pub fn contains(&self, label: &str) -> bool {
+        self.instances.contains_key(label)
+    }
+
+    pub fn is_ready(&self, label: &str) -> bool {
+        self.instances.get(label).is_some_and(|i| i.is_ready())
+    }
+
+    pub fn instances_mut(&mut self) -> impl Iterator<Item = &mut WidgetInstanceState<W>> {
+        self.instances.values_mut()
+    }
+
+    pub fn set_status(&mut self, label: &str, status: WidgetStatus) {
+        let Some(instance) = self.instances.get_mut(label) else {
+            return;
+        };
+        if status == WidgetStatus::Ready {
+            self.supervisor.on_ready(label);
+        }
+        instance.set_status(status);
+    }
+
+    /// Creates the webview of the instance if it is pending, failures are recorded to be
+    /// restarted as the restart policy of the widget allows.
+    pub fn start<F>(&mut self, label: &str, factory: &F, now: i64) -> Result<bool, String>
+    where
+        F: WidgetWebviewFactory<Webview = W>,
+    {
+        let instance = self
+            .instances
+            .get_mut(label)
+            .ok_or_else(|| format!("Instance {label} not found"))?;
+        let result = instance.start(&self.definition, factory);
+        if let Err(err) = &result {
+            self.supervisor.on_failure(label, err.clone(), now);
+        }
+        result
+    }
+
+    /// Starts all the pending instances, returns the labels and errors of the failed ones.
+    pub fn start_all<F>(&mut self, factory: &F, now: i64) -> Vec<(String, String)>
+    where
+        F: WidgetWebviewFactory<Webview = W>,
+    {
+        let mut failed = Vec::new();
+        for label in self.labels() {
+            if let Err(err) = self.start(&label, factory, now) {
+                failed.push((label, err));
+            }
+        }
+        failed
+    }
+
+    /// Starts again the crashed or destroyed instances, as the restart policy of the widget allows.
+    /// Returns the labels and errors of the instances that failed again.
+    pub fn restart_due<F>(&mut self, factory: &F, now: i64) -> Vec<(String, String)>
+    where
+        F: WidgetWebviewFactory<Webview = W>,
+    {
+        let mut failed = Vec::new();
+        for label in self.supervisor.due(now) {
+            let Some(instance) = self.instances.get_mut(&label) else {
+                continue;
+            };
+            if matches!(instance.status(), WidgetStatus::CrashedOnCreation(_)) {
+                instance.set_status(WidgetStatus::Pending);
+            }
+            if let Err(err) = self.start(&label, factory, now) {
+                failed.push((label, err));
+            }
+        }
+        failed
+    }
+
+    /// Returns false if the instance already exists.
+    pub fn add_runtime_instance(&mut self, label: &str) -> bool {
+        if self.instances.contains_key(label) {
+            return false;
+        }
+        let instance = WidgetInstanceState::new(label, WidgetInstanceType::Runtime);
+        self.instances.insert(label.to_string(), instance);
+        true
+    }
+
+    /// Static instances can't be removed, as they are defined in user settings.
+    pub fn remove_runtime_instance(&mut self, label: &str) -> Result<(), String> {
+        let instance = self.instances.get(label).ok_or("Instance not found")?;
+        if instance.instance_type() != WidgetInstanceType::Runtime {
+            return Err("Only runtime instances can be removed".to_string());
+        }
+        self.instances.remove(label);
+        self.supervisor.forget(label);
+        Ok(())
+    }
+
+    /// Handles the destruction of the webview, runtime instances are removed and static ones
+    /// are reset to pending, to be restarted as the restart policy of the widget allows.
+    pub fn on_destroyed(&mut self, label: &str, now: i64) {
+        let Some(instance) = self.instances.get_mut(label) else {
+            return;
+        };
+        if instance.on_destroyed() {
+            self.instances.remove(label);
+            self.supervisor.forget(label);
+        } else {
+            self.supervisor.on_exit(label, now);
+        }
+    }
+
+    pub fn instances_info(&self) -> Vec<WidgetInstanceInfo> {
+        self.instances
+            .values()
+            .map(|instance| {
+                let mut info = instance.info();
+                if let WidgetStatus::CrashedOnCreation(crash) = &mut info.status {
+                    if let Some(tracked) = self.supervisor.crash_info(&info.label) {
+                        crash.clone_from(tracked);
+                    }
+                }
+                info
+            })
+            .collect()
+    }
+
+    pub fn info(&self) -> WidgetInfo {
+        WidgetInfo {
+            id: self.definition.id.clone(),
+            enabled: true,
+            lazy: self.definition.lazy,
+            instances: self.instances_info(),
+        }
+    }
+}
+
+/// Loaded widgets by id, used by the widget manager and the widget cli commands.
+/// Generic over the webview, so the commands can be run without creating real webviews.
+#[derive(Debug)]
+pub struct WidgetRegistry<W> {
+    groups: HashMap<WidgetId, WidgetGroup<W>>,
+}
+
+impl<W> Default for WidgetRegistry<W> {
+    fn default() -> Self {
+        Self {
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl<W> WidgetRegistry<W> {
+    pub fn group(&self, widget_id: &WidgetId) -> Option<&WidgetGroup<W>> {
+        self.groups.get(widget_id)
+    }
+
+    pub fn group_mut(&mut self, widget_id: &WidgetId) -> Option<&mut WidgetGroup<W>> {
+        self.groups.get_mut(widget_id)
+    }
+
+    pub fn groups_mut(&mut self) -> impl Iterator<Item = &mut WidgetGroup<W>> {
+        self.groups.values_mut()
+    }
+
+    pub fn contains(&self, widget_id: &WidgetId) -> bool {
+        self.groups.contains_key(widget_id)
+    }
+
+    /// Releases the instances of the widget, returns false if it was not loaded.
+    pub fn unload(&mut self, widget_id: &WidgetId) -> bool {
+        self.groups.remove(widget_id).is_some()
+    }
+
+    /// Keeps a group for each of the given widgets, creating the missing ones with their static
+    /// instances and releasing the ones no longer given, e.g. removed or disabled widgets.
+    pub fn sync<L>(&mut self, enabled: &[Arc<Widget>], static_labels: L)
+    where
+        L: Fn(&Widget) -> Vec<String>,
+    {
+        self.groups
+            .retain(|id, _| enabled.iter().any(|widget| &widget.id == id));
+        for widget in enabled {
+            if !self.groups.contains_key(&widget.id) {
+                let group = WidgetGroup::new(widget.clone(), static_labels(widget));
+                self.groups.insert(widget.id.clone(), group);
+            }
+        }
+    }
+
+    /// Starts the pending instances of the widgets that are not lazy.
+    pub fn start_eager<F>(&mut self, factory: &F, now: i64) -> Vec<(String, String)>
+    where
+        F: WidgetWebviewFactory<Webview = W>,
+    {
+        let mut failed = Vec::new();
+        for group in self.groups.values_mut() {
+            if !group.definition.lazy {
+                failed.extend(group.start_all(factory, now));
+            }
+        }
+        failed
+    }
+
+    /// Destroys all the instances of the widget and creates them again as pending.
+    pub fn reload<L>(&mut self, widget_id: &WidgetId, static_labels: L) -> Result<(), String>
+    where
+        L: Fn(&Widget) -> Vec<String>,
+    {
+        let group = self
+            .groups
+            .remove(widget_id)
+            .ok_or_else(|| format!("Widget {widget_id} is not loaded"))?;
+        let definition = group.definition.clone();
+        // the old webviews are dropped before creating the new ones
+        drop(group);
+        let group = WidgetGroup::new(definition.clone(), static_labels(&definition));
+        self.groups.insert(widget_id.clone(), group);
+        Ok(())
+    }
+
+    /// Info of the given widgets sorted by id, disabled ones are listed without instances.
+    pub fn list(&self, widgets: &[(Arc<Widget>, bool)]) -> Vec<WidgetInfo> {
+        let mut list: Vec<WidgetInfo> = widgets
+            .iter()
+            .map(|(widget, enabled)| {
+                let info = self.groups.get(&widget.id).map(|g| g.info());
+                WidgetInfo {
+                    enabled: *enabled,
+                    ..info.unwrap_or_else(|| WidgetInfo {
+                        id: widget.id.clone(),
+                        enabled: false,
+                        lazy: widget.lazy,
+                        instances: Vec::new(),
+                    })
+                }
+            })
+            .collect();
+        list.sort_by_key(|w| w.id.to_string());
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::state::{WidgetCrashInfo, WidgetInstanceMode, WidgetRestartPolicy};
+
+    /// Webview of the fake factory, registers its destruction like the real ones do.
+    #[derive(Debug)]
+    struct FakeWebview {
+        label: String,
+        destroyed: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Drop for FakeWebview {
+        fn drop(&mut self) {
+            self.destroyed.borrow_mut().push(self.label.clone());
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeFactory {
+        failing: RefCell<Vec<String>>,
+        created: RefCell<Vec<String>>,
+        destroyed: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl WidgetWebviewFactory for FakeFactory {
+        type Webview = FakeWebview;
+
+        fn create(
+            &self,
+            _widget: &Widget,
+            instance: &WidgetInstanceState<FakeWebview>,
+        ) -> Result<FakeWebview, String> {
+            let label = instance.label().to_string();
+            if self.failing.borrow().contains(&label) {
+                return Err("no webview2 runtime".to_string());
+            }
+            self.created.borrow_mut().push(label.clone());
+            Ok(FakeWebview {
+                label,
+                destroyed: self.destroyed.clone(),
+            })
+        }
+    }
+
+    fn widget(id: &str, instances: WidgetInstanceMode, lazy: bool) -> Arc<Widget> {
+        Arc::new(Widget {
+            id: id.into(),
+            instances,
+            lazy,
+            restart_policy: WidgetRestartPolicy::OnFailure { max_retries: 1 },
+            ..Default::default()
+        })
+    }
+
+    /// Same as the app, single instance widgets have a static instance labeled as the widget.
+    fn static_labels(widget: &Widget) -> Vec<String> {
+        match widget.instances {
+            WidgetInstanceMode::Single => vec![widget.id.to_string()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn statuses(registry: &WidgetRegistry<FakeWebview>, id: &str) -> Vec<(String, WidgetStatus)> {
+        registry
+            .group(&id.into())
+            .unwrap()
+            .instances_info()
+            .into_iter()
+            .map(|i| (i.label, i.status))
+            .collect()
+    }
+
+    #[test]
+    fn test_list() {
+        let factory = FakeFactory::default();
+        let weg = widget("@user/weg", WidgetInstanceMode::Single, false);
+        let notes = widget("@user/notes", WidgetInstanceMode::Multiple, true);
+
+        let mut registry = WidgetRegistry::default();
+        registry.sync(std::slice::from_ref(&weg), static_labels);
+        registry.start_eager(&factory, 0);
+
+        let list = registry.list(&[(weg.clone(), true), (notes.clone(), false)]);
+        assert_eq!(
+            list,
+            vec![
+                WidgetInfo {
+                    id: notes.id.clone(),
+                    enabled: false,
+                    lazy: true,
+                    instances: Vec::new(),
+                },
+                WidgetInfo {
+                    id: weg.id.clone(),
+                    enabled: true,
+                    lazy: false,
+                    instances: vec![WidgetInstanceInfo {
+                        label: "@user/weg".to_string(),
+                        instance_type: WidgetInstanceType::Static,
+                        status: WidgetStatus::Mounting,
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enable_and_disable() {
+        let factory = FakeFactory::default();
+        let weg = widget("@user/weg", WidgetInstanceMode::Single, false);
+        let lazy = widget("@user/lazy", WidgetInstanceMode::Single, true);
+
+        let mut registry = WidgetRegistry::default();
+        registry.sync(&[weg.clone(), lazy.clone()], static_labels);
+        registry.start_eager(&factory, 0);
+        assert_eq!(*factory.created.borrow(), vec!["@user/weg"]);
+        assert_eq!(
+            statuses(&registry, "@user/lazy"),
+            vec![("@user/lazy".to_string(), WidgetStatus::Pending)]
+        );
+
+        // enabling again doesn't recreate the running instances
+        registry.sync(&[weg.clone(), lazy.clone()], static_labels);
+        registry.start_eager(&factory, 0);
+        assert_eq!(factory.created.borrow().len(), 1);
+
+        registry.sync(std::slice::from_ref(&lazy), static_labels);
+        assert!(!registry.contains(&weg.id));
+        assert_eq!(*factory.destroyed.borrow(), vec!["@user/weg"]);
+    }
+
+    #[test]
+    fn test_reload() {
+        let factory = FakeFactory::default();
+        let weg = widget("@user/weg", WidgetInstanceMode::Single, false);
+
+        let mut registry = WidgetRegistry::default();
+        assert!(registry.reload(&weg.id, static_labels).is_err());
+
+        registry.sync(std::slice::from_ref(&weg), static_labels);
+        registry.start_eager(&factory, 0);
+        registry.reload(&weg.id, static_labels).unwrap();
+        assert_eq!(*factory.destroyed.borrow(), vec!["@user/weg"]);
+        assert_eq!(
+            statuses(&registry, "@user/weg"),
+            vec![("@user/weg".to_string(), WidgetStatus::Pending)]
+        );
+
+        registry.start_eager(&factory, 0);
+        assert_eq!(*factory.created.borrow(), vec!["@user/weg", "@user/weg"]);
+    }
+
+    #[test]
+    fn test_add_and_remove_instances() {
+        let factory = FakeFactory::default();
+        let weg = widget("@user/weg", WidgetInstanceMode::Single, false);
+        let notes = widget("@user/notes", WidgetInstanceMode::Multiple, true);
+
+        let mut registry = WidgetRegistry::default();
+        registry.sync(&[weg.clone(), notes.clone()], static_labels);
+
+        let group = registry.group_mut(&notes.id).unwrap();
+        let label = "@user/notes?instanceId=1";
+        assert!(group.add_runtime_instance(label));
+        assert!(!group.add_runtime_instance(label));
+        assert_eq!(group.start(label, &factory, 0), Ok(true));
+        assert_eq!(
+            group.instances_info(),
+            vec![WidgetInstanceInfo {
+                label: label.to_string(),
+                instance_type: WidgetInstanceType::Runtime,
+                status: WidgetStatus::Mounting,
+            }]
+        );
+
+        group.remove_runtime_instance(label).unwrap();
+        assert!(group.instances_info().is_empty());
+        assert_eq!(*factory.destroyed.borrow(), vec![label]);
+        assert!(group.remove_runtime_instance(label).is_err());
+
+        // static instances are defined by the settings
+        let group = registry.group_mut(&weg.id).unwrap();
+        assert!(group.remove_runtime_instance("@user/weg").is_err());
+        assert!(group.contains("@user/weg"));
+    }
+
+    #[test]
+    fn test_crashes_and_restarts() {
+        let factory = FakeFactory::default();
+        factory.failing.borrow_mut().push("@user/weg".to_string());
+        let weg = widget("@user/weg", WidgetInstanceMode::Single, false);
+
+        let mut registry = WidgetRegistry::default();
+        registry.sync(std::slice::from_ref(&weg), static_labels);
+        assert_eq!(
+            registry.start_eager(&factory, 0),
+            vec![("@user/weg".to_string(), "no webview2 runtime".to_string())]
+        );
+        assert_eq!(
+            statuses(&registry, "@user/weg"),
+            vec![(
+                "@user/weg".to_string(),
+                WidgetStatus::CrashedOnCreation(WidgetCrashInfo {
+                    crashes: 1,
+                    retries: 0,
+                    last_error: Some("no webview2 runtime".to_string()),
+                    next_restart_at: Some(1000),
+                })
+            )]
+        );
+
+        factory.failing.borrow_mut().clear();
+        let group = registry.group_mut(&weg.id).unwrap();
+        assert!(group.restart_due(&factory, 999).is_empty());
+        assert_eq!(factory.created.borrow().len(), 0);
+        assert!(group.restart_due(&factory, 1000).is_empty());
+        assert_eq!(*factory.created.borrow(), vec!["@user/weg"]);
+
+        group.set_status("@user/weg", WidgetStatus::Ready);
+        assert!(group.is_ready("@user/weg"));
+
+        // destroyed static instances are kept as pending
+        group.on_destroyed("@user/weg", 2000);
+        assert_eq!(
+            statuses(&registry, "@user/weg"),
+            vec![("@user/weg".to_string(), WidgetStatus::Pending)]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const RESTART_BACKOFF_BASE_MS: i64 = 1000;
+const RESTART_BACKOFF_MAX_MS: i64 = 5 * 60 * 1000;
+
+/// What to do when an instance of the widget fails to be created or its webview is destroyed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(
+    tag = "mode",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum WidgetRestartPolicy {
+    /// Failed instances stay crashed and destroyed ones wait for a trigger to be created again.
+    #[default]
+    Never,
+    /// Instances that failed to be created are retried, up to `max_retries` consecutive times.
+    OnFailure { max_retries: u32 },
+    /// Instances are restarted on failure and also when their webview is destroyed.
+    Always,
+}
+
+/// Crash history of a widget instance.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetCrashInfo {
+    /// Total failures of the instance.
+    pub crashes: u32,
+    /// Consecutive restarts since the instance was ready for the last time.
+    pub retries: u32,
+    pub last_error: Option<String>,
+    /// Timestamp in milliseconds when the instance will be restarted.
+    pub next_restart_at: Option<i64>,
+}
+
+/// Decides when the instances of a widget should be restarted, following its restart policy
+/// with exponential backoff between consecutive restarts. Instances are identified by label.
+#[derive(Debug, Default)]
+pub struct WidgetSupervisor {
+    policy: WidgetRestartPolicy,
+    instances: HashMap<String, WidgetCrashInfo>,
+}
+
+impl WidgetSupervisor {
+    pub fn new(policy: WidgetRestartPolicy) -> Self {
+        Self {
+            policy,
+            instances: HashMap::new(),
+        }
+    }
+
+    pub fn crash_info(&self, label: &str) -> Option<&WidgetCrashInfo> {
+        self.instances.get(label)
+    }
+
+    /// The instance failed to be created.
+    pub fn on_failure(&mut self, label: &str, error: impl Into<String>, now: i64) {
+        let info = self.instances.entry(label.to_string()).or_default();
+        info.crashes += 1;
+        info.last_error = Some(error.into());
+
+        let can_restart = match self.policy {
+            WidgetRestartPolicy::Never => false,
+            WidgetRestartPolicy::OnFailure { max_retries } => info.retries < max_retries,
+            WidgetRestartPolicy::Always => true,
+        };
+        info.next_restart_at = can_restart.then(|| now + Self::backoff(info.retries));
+    }
+
+    /// The webview of the instance was destroyed without being requested.
+    pub fn on_exit(&mut self, label: &str, now: i64) {
+        if self.policy != WidgetRestartPolicy::Always {
+            return;
+        }
+        let info = self.instances.entry(label.to_string()).or_default();
+        info.next_restart_at = Some(now + Self::backoff(info.retries));
+    }
+
+    /// The instance is working, so the next failure starts the backoff again.
+    pub fn on_ready(&mut self, label: &str) {
+        if let Some(info) = self.instances.get_mut(label) {
+            info.retries = 0;
+            info.next_restart_at = None;
+        }
+    }
+
+    /// Instances that were removed should not be restarted.
+    pub fn forget(&mut self, label: &str) {
+        self.instances.remove(label);
+    }
+
+    /// Labels of the instances to be restarted now, they are counted as retries.
+    pub fn due(&mut self, now: i64) -> Vec<String> {
+        let mut due = Vec::new();
+        for (label, info) in &mut self.instances {
+            if info.next_restart_at.is_some_and(|at| at <= now) {
+                info.next_restart_at = None;
+                info.retries += 1;
+                due.push(label.clone());
+            }
+        }
+        due
+    }
+
+    fn backoff(retries: u32) -> i64 {
+        RESTART_BACKOFF_BASE_MS
+            .saturating_mul(1 << retries.min(30))
+            .min(RESTART_BACKOFF_MAX_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never() {
+        let mut supervisor = WidgetSupervisor::new(WidgetRestartPolicy::Never);
+        supervisor.on_failure("w", "no runtime", 0);
+        supervisor.on_exit("w", 0);
+        assert!(supervisor.due(i64::MAX).is_empty());
+
+        let info = supervisor.crash_info("w").unwrap();
+        assert_eq!(info.crashes, 1);
+        assert_eq!(info.last_error.as_deref(), Some("no runtime"));
+    }
+
+    #[test]
+    fn test_on_failure_backoff_and_max_retries() {
+        let mut supervisor =
+            WidgetSupervisor::new(WidgetRestartPolicy::OnFailure { max_retries: 2 });
+
+        supervisor.on_exit("w", 0);
+        assert!(supervisor.crash_info("w").is_none());
+
+        supervisor.on_failure("w", "error", 0);
+        assert!(supervisor.due(999).is_empty());
+        assert_eq!(supervisor.due(1000), vec!["w"]);
+
+        supervisor.on_failure("w", "error", 1000);
+        assert!(supervisor.due(2999).is_empty());
+        assert_eq!(supervisor.due(3000), vec!["w"]);
+
+        supervisor.on_failure("w", "error", 3000);
+        assert!(supervisor.due(i64::MAX).is_empty());
+        assert_eq!(supervisor.crash_info("w").unwrap().crashes, 3);
+    }
+
+    #[test]
+    fn test_always_and_ready_reset() {
+        let mut supervisor = WidgetSupervisor::new(WidgetRestartPolicy::Always);
+
+        for i in 0..20 {
+            supervisor.on_failure("w", "error", 0);
+            assert_eq!(supervisor.due(i64::MAX), vec!["w"], "retry {i}");
+        }
+        supervisor.on_failure("w", "error", 0);
+        assert_eq!(
+            supervisor.crash_info("w").unwrap().next_restart_at,
+            Some(RESTART_BACKOFF_MAX_MS)
+        );
+
+        supervisor.on_ready("w");
+        supervisor.on_exit("w", 0);
+        assert_eq!(supervisor.due(1000), vec!["w"]);
+
+        supervisor.on_exit("w", 0);
+        supervisor.forget("w");
+        assert!(supervisor.due(i64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_policy_serialization() {
+        let policy: WidgetRestartPolicy =
+            serde_json::from_str(r#"{ "mode": "onFailure", "maxRetries": 3 }"#).unwrap();
+        assert_eq!(policy, WidgetRestartPolicy::OnFailure { max_retries: 3 });
+    }
+}
@@ -2,7 +2,9 @@ use std::collections::{HashMap, HashSet};
 
 use uuid::Uuid;
 
-use crate::{error::Result, identifier_impl, resource::WallpaperId, system_state::MonitorId};
+use crate::{
+    error::Result, identifier_impl, rect::Rect, resource::WallpaperId, system_state::MonitorId,
+};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, TS)]
 #[serde(default, rename_all = "camelCase")]
@@ -78,6 +80,33 @@ impl VirtualDesktopMonitor {
             .expect("current workspace not found")
     }
 
+    /// Find a workspace by name or by index, name is checked first.
+    pub fn find_workspace(&self, index: Option<usize>, name: Option<&str>) -> Option<&WorkspaceId> {
+        let by_name = name.and_then(|name| {
+            self.workspaces
+                .iter()
+                .find(|w| w.name.as_deref() == Some(name))
+        });
+        by_name
+            .or_else(|| index.and_then(|idx| self.workspaces.get(idx)))
+            .map(|w| &w.id)
+    }
+
+    /// Direction of a switch between two workspaces of this monitor, based on their order.
+    pub fn switch_direction(
+        &self,
+        from: &WorkspaceId,
+        to: &WorkspaceId,
+    ) -> Option<WorkspaceSwitchDirection> {
+        let from_idx = self.workspaces.iter().position(|w| &w.id == from)?;
+        let to_idx = self.workspaces.iter().position(|w| &w.id == to)?;
+        match to_idx.cmp(&from_idx) {
+            std::cmp::Ordering::Greater => Some(WorkspaceSwitchDirection::Next),
+            std::cmp::Ordering::Less => Some(WorkspaceSwitchDirection::Previous),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
     /// Set the current workspace, return error if the workspace doesn't exist
     pub fn set_active_workspace(&mut self, workspace_id: &WorkspaceId) -> Result<()> {
         if self.workspaces.iter().any(|w| &w.id == workspace_id) {
@@ -176,3 +205,91 @@ impl DesktopWorkspace {
 pub struct WorkspaceId(pub String);
 
 identifier_impl!(WorkspaceId, String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+pub enum WorkspaceSwitchDirection {
+    Next,
+    Previous,
+}
+
+/// Payload emitted each time a monitor changes its active workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WorkspaceSwitch {
+    pub monitor: MonitorId,
+    pub from: WorkspaceId,
+    pub to: WorkspaceId,
+    /// based on the workspaces order, None if it can't be determined
+    pub direction: Option<WorkspaceSwitchDirection>,
+}
+
+/// Last known state of a workspace, used to render the workspaces overview
+/// without having to restore the windows of hidden workspaces.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WorkspaceSnapshot {
+    /// windows of the workspace, ordered from back to front
+    pub windows: Vec<WorkspaceWindowSnapshot>,
+    /// unix timestamp in milliseconds
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceWindowSnapshot {
+    pub hwnd: isize,
+    /// last rect of the window while it was visible
+    pub rect: Rect,
+    /// hash of the last captured `UserAppWindowPreview` of the window
+    pub preview_hash: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workspace_prefers_name_over_index() {
+        let mut monitor = VirtualDesktopMonitor::create();
+        monitor.add_workspace();
+        let named = monitor.add_workspace();
+        monitor
+            .rename_workspace(&named, Some("Code".to_string()))
+            .unwrap();
+
+        let second = monitor.workspaces[1].id.clone();
+        assert_eq!(monitor.find_workspace(Some(1), None), Some(&second));
+        assert_eq!(monitor.find_workspace(Some(1), Some("Code")), Some(&named));
+        // unknown names fallback to the index
+        assert_eq!(
+            monitor.find_workspace(Some(1), Some("Music")),
+            Some(&second)
+        );
+        assert_eq!(monitor.find_workspace(Some(10), None), None);
+        assert_eq!(monitor.find_workspace(None, None), None);
+    }
+
+    #[test]
+    fn test_switch_direction() {
+        let mut monitor = VirtualDesktopMonitor::create();
+        let first = monitor.workspaces[0].id.clone();
+        let second = monitor.add_workspace();
+
+        assert_eq!(
+            monitor.switch_direction(&first, &second),
+            Some(WorkspaceSwitchDirection::Next)
+        );
+        assert_eq!(
+            monitor.switch_direction(&second, &first),
+            Some(WorkspaceSwitchDirection::Previous)
+        );
+        assert_eq!(monitor.switch_direction(&first, &first), None);
+        assert_eq!(
+            monitor.switch_direction(&first, &WorkspaceId("unknown".to_string())),
+            None
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use std::{collections::HashMap, path::Path};
+
+use super::StartMenuItem;
+
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+/// max bonus given by the frecency to a match, so frequently used apps can
+/// outrank better matches of apps never used, but never a non matching one.
+const FRECENCY_WEIGHT: f64 = 0.3;
+
+impl StartMenuItem {
+    /// Stable identifier of the item, used to store launches and aliases.
+    pub fn search_key(&self) -> String {
+        if let Some(umid) = &self.umid {
+            return umid.clone();
+        }
+        self.target
+            .as_ref()
+            .unwrap_or(&self.path)
+            .to_string_lossy()
+            .to_lowercase()
+    }
+
+    /// Whether the program (as used by the `Run` command) launches this item.
+    pub fn is_launched_by(&self, program: &Path) -> bool {
+        if !self.path.as_os_str().is_empty() && self.path == program {
+            return true;
+        }
+        if self.target.as_deref().is_some_and(|t| t == program) {
+            return true;
+        }
+        let program = program.to_string_lossy();
+        self.umid.as_ref().is_some_and(|umid| {
+            program
+                .strip_prefix("shell:AppsFolder\\")
+                .is_some_and(|p| p.eq_ignore_ascii_case(umid))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLaunchStats {
+    pub count: u32,
+    /// unix timestamp in milliseconds
+    pub last_launch: i64,
+}
+
+/// Launch statistics and user aliases of the start menu items, by `StartMenuItem::search_key`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppSearchData {
+    pub launches: HashMap<String, AppLaunchStats>,
+    pub aliases: HashMap<String, Vec<String>>,
+}
+
+impl AppSearchData {
+    pub fn record_launch(&mut self, key: String, now: i64) {
+        let stats = self.launches.entry(key).or_default();
+        stats.count += 1;
+        stats.last_launch = now;
+    }
+
+    /// Launch count weighted by how recent was the last launch.
+    pub fn frecency(&self, key: &str, now: i64) -> f64 {
+        let Some(stats) = self.launches.get(key) else {
+            return 0.0;
+        };
+        let age_days = (now - stats.last_launch) / MILLIS_PER_DAY;
+        let weight = match age_days {
+            ..4 => 1.0,
+            4..14 => 0.7,
+            14..31 => 0.5,
+            31..90 => 0.3,
+            _ => 0.1,
+        };
+        stats.count as f64 * weight
+    }
+}
+
+struct IndexedApp {
+    item: StartMenuItem,
+    key: String,
+    /// normalized searchable texts with their weight
+    fields: Vec<(String, f64)>,
+}
+
+/// Fuzzy search index over the start menu items.
+pub struct AppSearchIndex {
+    apps: Vec<IndexedApp>,
+}
+
+impl AppSearchIndex {
+    pub fn new(items: &[StartMenuItem], data: &AppSearchData) -> Self {
+        let apps = items
+            .iter()
+            .map(|item| {
+                let key = item.search_key();
+                let mut fields = vec![(normalize(&item.display_name), 1.0)];
+                if let Some(aliases) = data.aliases.get(&key) {
+                    fields.extend(aliases.iter().map(|alias| (normalize(alias), 1.0)));
+                }
+                if let Some(exe) = item.target.as_ref().and_then(|t| t.file_stem()) {
+                    fields.push((normalize(&exe.to_string_lossy()), 0.8));
+                }
+                if let Some(umid) = &item.umid {
+                    fields.push((normalize(umid), 0.6));
+                }
+                IndexedApp {
+                    item: item.clone(),
+                    key,
+                    fields,
+                }
+            })
+            .collect();
+        Self { apps }
+    }
+
+    /// Returns the items matching the query, best first. An empty query returns the
+    /// most used items. `now` is an unix timestamp in milliseconds.
+    pub fn search(
+        &self,
+        query: &str,
+        data: &AppSearchData,
+        now: i64,
+        limit: usize,
+    ) -> Vec<StartMenuItem> {
+        self.search_scored(query, data, now, limit)
+            .into_iter()
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    /// Same as `search` but including the score of each item, between 0 and 1.3
+    /// as it includes the frecency bonus.
+    pub fn search_scored(
+        &self,
+        query: &str,
+        data: &AppSearchData,
+        now: i64,
+        limit: usize,
+    ) -> Vec<(StartMenuItem, f64)> {
+        let query = normalize(query);
+
+        let mut results: Vec<(f64, &IndexedApp)> = self
+            .apps
+            .iter()
+            .filter_map(|app| {
+                let score = if query.is_empty() {
+                    0.0
+                } else {
+                    app.fields
+                        .iter()
+                        .map(|(text, weight)| match_score(&query, text) * weight)
+                        .fold(0.0, f64::max)
+                };
+                if !query.is_empty() && score <= 0.0 {
+                    return None;
+                }
+                let frecency = data.frecency(&app.key, now);
+                Some((
+                    score + FRECENCY_WEIGHT * (1.0 - 1.0 / (1.0 + frecency)),
+                    app,
+                ))
+            })
+            .collect();
+
+        results.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| a.item.display_name.cmp(&b.item.display_name))
+        });
+        results
+            .into_iter()
+            .take(limit)
+            .map(|(score, app)| (app.item.clone(), score))
+            .collect()
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+/// Score between 0 and 1 of how well the query matches the text, using the same
+/// rules as the start menu search. 0 means no match.
+pub fn fuzzy_match_score(query: &str, text: &str) -> f64 {
+    let query = normalize(query);
+    if query.is_empty() {
+        return 0.0;
+    }
+    match_score(&query, &normalize(text))
+}
+
+/// Score between 0 and 1 of how well the query matches the text, both normalized.
+fn match_score(query: &str, text: &str) -> f64 {
+    if text == query {
+        return 1.0;
+    }
+    if text.starts_with(query) {
+        return 0.9;
+    }
+
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.iter().any(|w| w.starts_with(query)) {
+        return 0.8;
+    }
+    if text.contains(query) {
+        return 0.7;
+    }
+
+    // typo tolerance, compared against the start of each word
+    let query_len = query.chars().count();
+    let max_typos = match query_len {
+        0..3 => 0,
+        3..7 => 1,
+        _ => 2,
+    };
+    if max_typos > 0 {
+        let best = words
+            .iter()
+            .map(|word| {
+                let prefix: String = word.chars().take(query_len).collect();
+                osa_distance(query, &prefix)
+            })
+            .min();
+        if let Some(distance) = best.filter(|d| *d <= max_typos) {
+            return 0.6 - 0.1 * distance as f64;
+        }
+    }
+
+    // initials or scattered characters, e.g. "vsc" for "visual studio code"
+    if is_subsequence(query, text) {
+        return 0.3 * query_len as f64 / text.chars().count() as f64 + 0.1;
+    }
+    0.0
+}
+
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text = text.chars();
+    query.chars().all(|q| text.any(|t| t == q))
+}
+
+/// Optimal string alignment distance (levenshtein plus transpositions).
+fn osa_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const NOW: i64 = 1_000 * MILLIS_PER_DAY;
+
+    fn item(name: &str, exe: &str) -> StartMenuItem {
+        StartMenuItem {
+            path: PathBuf::from(format!("C:\\Start Menu\\{name}.lnk")),
+            umid: None,
+            toast_activator: None,
+            target: Some(PathBuf::from(format!("C:\\Apps\\{exe}.exe"))),
+            display_name: name.to_string(),
+        }
+    }
+
+    fn items() -> Vec<StartMenuItem> {
+        vec![
+            item("Visual Studio Code", "Code"),
+            item("Firefox", "firefox"),
+            item("File Explorer", "explorer"),
+            item("Notepad", "notepad"),
+            item("Notepad++", "notepad++"),
+        ]
+    }
+
+    fn names(results: &[StartMenuItem]) -> Vec<&str> {
+        results.iter().map(|i| i.display_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_match_quality_order() {
+        let data = AppSearchData::default();
+        let index = AppSearchIndex::new(&items(), &data);
+
+        assert_eq!(
+            names(&index.search("notepad", &data, NOW, 10)),
+            vec!["Notepad", "Notepad++"]
+        );
+        assert_eq!(
+            names(&index.search("code", &data, NOW, 10)),
+            vec!["Visual Studio Code"]
+        );
+        assert_eq!(
+            names(&index.search("vsc", &data, NOW, 10)),
+            vec!["Visual Studio Code"]
+        );
+    }
+
+    #[test]
+    fn test_typo_tolerance() {
+        let data = AppSearchData::default();
+        let index = AppSearchIndex::new(&items(), &data);
+
+        assert_eq!(
+            names(&index.search("firfox", &data, NOW, 10)),
+            vec!["Firefox"]
+        );
+        assert_eq!(
+            names(&index.search("exlporer", &data, NOW, 10)),
+            vec!["File Explorer"]
+        );
+        assert!(index.search("zzz", &data, NOW, 10).is_empty());
+    }
+
+    #[test]
+    fn test_frecency_and_aliases() {
+        let all = items();
+        let mut data = AppSearchData::default();
+        for _ in 0..5 {
+            data.record_launch(all[4].search_key(), NOW);
+        }
+        data.aliases
+            .insert(all[1].search_key(), vec!["browser".to_string()]);
+        let index = AppSearchIndex::new(&all, &data);
+
+        assert_eq!(
+            names(&index.search("notepad", &data, NOW, 10)),
+            vec!["Notepad++", "Notepad"]
+        );
+        assert_eq!(
+            names(&index.search("browser", &data, NOW, 10)),
+            vec!["Firefox"]
+        );
+        assert_eq!(names(&index.search("", &data, NOW, 1)), vec!["Notepad++"]);
+
+        // old launches lose weight
+        let later = NOW + 100 * MILLIS_PER_DAY;
+        assert!(
+            data.frecency(&all[4].search_key(), later) < data.frecency(&all[4].search_key(), NOW)
+        );
+    }
+
+    #[test]
+    fn test_launch_matching() {
+        let mut uwp = item("Calculator", "calc");
+        uwp.umid = Some("Microsoft.WindowsCalculator_8wekyb3d8bbwe!App".to_string());
+        uwp.target = None;
+        uwp.path = PathBuf::new();
+
+        assert!(uwp.is_launched_by(Path::new(
+            "shell:AppsFolder\\Microsoft.WindowsCalculator_8wekyb3d8bbwe!App"
+        )));
+        assert!(!uwp.is_launched_by(Path::new("")));
+
+        let firefox = item("Firefox", "firefox");
+        assert!(firefox.is_launched_by(Path::new("C:\\Apps\\firefox.exe")));
+        assert!(firefox.is_launched_by(Path::new("C:\\Start Menu\\Firefox.lnk")));
+        assert!(!firefox.is_launched_by(Path::new("C:\\Apps\\notepad.exe")));
+    }
+}
@@ -0,0 +1,270 @@
+/// What happens when a launcher result is selected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub enum LauncherAction {
+    /// runs a program, a shortcut or an `shell:AppsFolder\umid` path
+    Run {
+        program: String,
+        #[serde(default)]
+        #[ts(optional = nullable)]
+        args: Option<String>,
+    },
+    /// opens a file or folder with its default app
+    Open { path: String },
+    /// opens the url on the default browser
+    Url { url: String },
+    /// copies the text to the clipboard
+    Copy { text: String },
+    /// executes a Seelen UI cli command, e.g: `["widget", "reload", "@seelen/weg"]`
+    Cli { args: Vec<String> },
+}
+
+impl LauncherAction {
+    /// Replaces `{query}` on the texts of the action, url encoded on urls.\
+    /// On programs the query is only replaced on the args, as a single quoted argument,
+    /// and on cli commands it is always part of a single argument.
+    pub fn with_query(&self, query: &str) -> Self {
+        let replace = |text: &str| text.replace("{query}", query);
+        match self {
+            Self::Run { program, args } => {
+                let quoted = quote_windows_arg(query);
+                Self::Run {
+                    program: program.clone(),
+                    args: args.as_ref().map(|args| args.replace("{query}", &quoted)),
+                }
+            }
+            Self::Open { path } => Self::Open {
+                path: replace(path),
+            },
+            Self::Url { url } => {
+                let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes())
+                    .collect::<String>()
+                    .replace('+', "%20");
+                Self::Url {
+                    url: url.replace("{query}", &encoded),
+                }
+            }
+            Self::Copy { text } => Self::Copy {
+                text: replace(text),
+            },
+            Self::Cli { args } => Self::Cli {
+                args: args.iter().map(|arg| replace(arg)).collect(),
+            },
+        }
+    }
+}
+
+/// Quotes the text to be parsed as a single argument by `CommandLineToArgvW`.
+fn quote_windows_arg(text: &str) -> String {
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in text.chars() {
+        match c {
+            '\\' => {
+                backslashes += 1;
+                continue;
+            }
+            // the backslashes before a quote and the quote itself are escaped
+            '"' => quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1)),
+            _ => quoted.extend(std::iter::repeat_n('\\', backslashes)),
+        }
+        backslashes = 0;
+        quoted.push(c);
+    }
+    // the backslashes before the closing quote are escaped too
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct LauncherResult {
+    /// id of the provider that created the result
+    pub provider: String,
+    pub title: String,
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub subtitle: Option<String>,
+    /// react icon name, url or path of the icon
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub icon: Option<String>,
+    /// relevance of the result, between 0 and 1
+    pub score: f64,
+    pub action: LauncherAction,
+}
+
+impl LauncherResult {
+    /// Sorts the results of all the providers by score, keeping the provider order on ties,
+    /// and removes the ones with the same action as a better result.
+    pub fn merge(mut results: Vec<LauncherResult>, limit: usize) -> Vec<LauncherResult> {
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let mut merged: Vec<LauncherResult> = Vec::new();
+        for result in results {
+            if merged.len() >= limit {
+                break;
+            }
+            if !merged.iter().any(|r| r.action == result.action) {
+                merged.push(result);
+            }
+        }
+        merged
+    }
+}
+
+/// Provider triggered by a keyword at the start of the query, e.g: `yt lofi music`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct LauncherKeywordProvider {
+    pub keyword: String,
+    /// title of the result, `{query}` is replaced by the text after the keyword
+    pub title: String,
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub icon: Option<String>,
+    /// `{query}` on the action is replaced by the text after the keyword
+    pub action: LauncherAction,
+}
+
+impl LauncherKeywordProvider {
+    /// Returns the text after the keyword if the query starts with it.
+    pub fn strip_keyword<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let (keyword, rest) = query.trim_start().split_once(char::is_whitespace)?;
+        if !keyword.eq_ignore_ascii_case(&self.keyword) {
+            return None;
+        }
+        Some(rest.trim()).filter(|rest| !rest.is_empty())
+    }
+
+    pub fn resolve(&self, provider: &str, query: &str) -> Option<LauncherResult> {
+        let query = self.strip_keyword(query)?;
+        Some(LauncherResult {
+            provider: provider.to_string(),
+            title: self.title.replace("{query}", query),
+            subtitle: self.subtitle.as_ref().map(|s| s.replace("{query}", query)),
+            icon: self.icon.clone(),
+            score: 1.0,
+            action: self.action.with_query(query),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(provider: &str, score: f64, action: LauncherAction) -> LauncherResult {
+        LauncherResult {
+            provider: provider.to_string(),
+            title: provider.to_string(),
+            subtitle: None,
+            icon: None,
+            score,
+            action,
+        }
+    }
+
+    #[test]
+    fn test_keyword_provider() {
+        let provider = LauncherKeywordProvider {
+            keyword: "yt".to_string(),
+            title: "{query}".to_string(),
+            subtitle: Some("Search on YouTube".to_string()),
+            icon: None,
+            action: LauncherAction::Url {
+                url: "https://www.youtube.com/results?search_query={query}".to_string(),
+            },
+        };
+
+        let result = provider.resolve("web-search", "YT  lofi & chill ").unwrap();
+        assert_eq!(result.title, "lofi & chill");
+        assert_eq!(
+            result.action,
+            LauncherAction::Url {
+                url: "https://www.youtube.com/results?search_query=lofi%20%26%20chill".to_string()
+            }
+        );
+
+        assert!(provider.resolve("web-search", "yt").is_none());
+        assert!(provider.resolve("web-search", "yt   ").is_none());
+        assert!(provider.resolve("web-search", "ytmusic song").is_none());
+    }
+
+    #[test]
+    fn test_action_query_replacement() {
+        let action = LauncherAction::Cli {
+            args: vec![
+                "widget".to_string(),
+                "trigger".to_string(),
+                "{query}".to_string(),
+            ],
+        };
+        assert_eq!(
+            action.with_query("@seelen/weg"),
+            LauncherAction::Cli {
+                args: vec![
+                    "widget".to_string(),
+                    "trigger".to_string(),
+                    "@seelen/weg".to_string()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_run_query_is_a_single_argument() {
+        let action = LauncherAction::Run {
+            program: "{query}.exe".to_string(),
+            args: Some("--search {query}".to_string()),
+        };
+        assert_eq!(
+            action.with_query(r#"a" & calc "b\"#),
+            LauncherAction::Run {
+                program: "{query}.exe".to_string(),
+                args: Some(r#"--search "a\" & calc \"b\\""#.to_string()),
+            }
+        );
+
+        assert_eq!(quote_windows_arg("lofi music"), r#""lofi music""#);
+        assert_eq!(quote_windows_arg(r"C:\dir\file"), r#""C:\dir\file""#);
+        assert_eq!(quote_windows_arg(r#"\""#), r#""\\\"""#);
+    }
+
+    #[test]
+    fn test_merge_results() {
+        let open = |path: &str| LauncherAction::Open {
+            path: path.to_string(),
+        };
+        let results = vec![
+            result("apps", 0.7, open("a")),
+            result("files", 0.9, open("b")),
+            result("files", 0.7, open("c")),
+            result("files", 0.5, open("a")),
+            result("calculator", 1.0, open("d")),
+        ];
+
+        let merged = LauncherResult::merge(results.clone(), 10);
+        let order: Vec<_> = merged
+            .iter()
+            .map(|r| (r.provider.as_str(), r.score))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("calculator", 1.0),
+                ("files", 0.9),
+                ("apps", 0.7),
+                ("files", 0.7)
+            ]
+        );
+
+        assert_eq!(LauncherResult::merge(results, 2).len(), 2);
+    }
+}
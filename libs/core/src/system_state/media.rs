@@ -13,15 +13,84 @@ pub struct MediaPlayerTimeline {
     pub start: i64,
     /// The total duration of the media item in nanoseconds
     pub end: i64,
-    /// Current playback position in nanoseconds
+    /// Playback position in nanoseconds at `last_updated_time`, it is not updated while playing
+    /// so the current position should be interpolated using the playback rate.
     pub position: i64,
     /// The earliest timestamp at which the current media item can currently seek to. (in nanoseconds)
     pub min_seek: i64,
     /// The furthest timestamp at which the content can currently seek to. (in nanoseconds)
     pub max_seek: i64,
+    /// Windows universal time (100ns ticks since 1601-01-01) of the last position update
     pub last_updated_time: i64,
 }
 
+/// Windows universal time of the unix epoch.
+const UNIX_EPOCH_UNIVERSAL_TIME: i64 = 116_444_736_000_000_000;
+
+impl MediaPlayerTimeline {
+    /// Converts an unix timestamp in milliseconds to windows universal time.
+    pub fn universal_time_from_unix_millis(millis: i64) -> i64 {
+        UNIX_EPOCH_UNIVERSAL_TIME + millis * 10_000
+    }
+
+    /// Estimated position at `now` (universal time), based on the last reported position,
+    /// clamped to the media duration.
+    pub fn position_at(&self, now: i64, playing: bool, rate: f64) -> i64 {
+        if !playing || self.last_updated_time <= 0 {
+            return self.position;
+        }
+        let elapsed_ns = now.saturating_sub(self.last_updated_time).max(0) as f64 * 100.0;
+        let position = self.position + (elapsed_ns * rate) as i64;
+        if self.end > self.start {
+            position.clamp(self.start, self.end)
+        } else {
+            position.max(self.start)
+        }
+    }
+
+    /// Clamps the position to the seekable range.
+    pub fn clamp_seek(&self, position: i64) -> i64 {
+        if self.max_seek > self.min_seek {
+            position.clamp(self.min_seek, self.max_seek)
+        } else {
+            position.max(self.min_seek)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(repr(enum = name))]
+pub enum MediaRepeatMode {
+    None,
+    Track,
+    List,
+}
+
+impl MediaRepeatMode {
+    /// None → List → Track → None, same order as most players.
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::List,
+            Self::List => Self::Track,
+            Self::Track => Self::None,
+        }
+    }
+}
+
+/// Controls enabled by the player for the current media.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaPlayerControls {
+    pub play_pause: bool,
+    pub next: bool,
+    pub previous: bool,
+    pub seek: bool,
+    pub shuffle: bool,
+    pub repeat: bool,
+    pub playback_rate: bool,
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaPlayer {
@@ -33,6 +102,13 @@ pub struct MediaPlayer {
     pub timeline: MediaPlayerTimeline,
     pub playing: bool,
     pub default: bool,
+    pub controls: MediaPlayerControls,
+    /// None if the player doesn't report it
+    pub shuffle: Option<bool>,
+    /// None if the player doesn't report it
+    pub repeat_mode: Option<MediaRepeatMode>,
+    /// None if the player doesn't report it
+    pub playback_rate: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -68,3 +144,63 @@ pub struct MediaDevice {
     pub volume: f32,
     pub muted: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    fn timeline(position: i64) -> MediaPlayerTimeline {
+        MediaPlayerTimeline {
+            start: 0,
+            end: 180 * SECOND,
+            position,
+            min_seek: 0,
+            max_seek: 180 * SECOND,
+            last_updated_time: MediaPlayerTimeline::universal_time_from_unix_millis(10_000),
+        }
+    }
+
+    #[test]
+    fn test_position_interpolation() {
+        let timeline = timeline(30 * SECOND);
+        let now = MediaPlayerTimeline::universal_time_from_unix_millis(12_500);
+
+        assert_eq!(
+            timeline.position_at(now, true, 1.0),
+            32 * SECOND + SECOND / 2
+        );
+        assert_eq!(timeline.position_at(now, true, 2.0), 35 * SECOND);
+        assert_eq!(timeline.position_at(now, false, 1.0), 30 * SECOND);
+
+        let far = MediaPlayerTimeline::universal_time_from_unix_millis(10_000_000);
+        assert_eq!(timeline.position_at(far, true, 1.0), 180 * SECOND);
+        // clock skew
+        let past = MediaPlayerTimeline::universal_time_from_unix_millis(5_000);
+        assert_eq!(timeline.position_at(past, true, 1.0), 30 * SECOND);
+    }
+
+    #[test]
+    fn test_seek_and_repeat() {
+        let timeline = timeline(0);
+        assert_eq!(timeline.clamp_seek(-5), 0);
+        assert_eq!(timeline.clamp_seek(200 * SECOND), 180 * SECOND);
+        assert_eq!(timeline.clamp_seek(60 * SECOND), 60 * SECOND);
+
+        let mut mode = MediaRepeatMode::None;
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            mode = mode.next();
+            seen.push(mode);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                MediaRepeatMode::List,
+                MediaRepeatMode::Track,
+                MediaRepeatMode::None
+            ]
+        );
+    }
+}
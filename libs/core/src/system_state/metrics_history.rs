@@ -0,0 +1,298 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::state::SystemMetricsSettings;
+
+use super::{Core, Disk, Memory, NetworkStatistics};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(repr(enum = name))]
+pub enum SystemMetric {
+    /// average usage of all the cores, between 0 and 100
+    CpuUsage,
+    /// bytes of memory in use
+    MemoryUsed,
+    /// bytes of swap in use
+    SwapUsed,
+    /// bytes read from all the disks since the previous sample
+    DiskRead,
+    /// bytes written to all the disks since the previous sample
+    DiskWritten,
+    /// bytes received on all the interfaces since the previous sample
+    NetworkReceived,
+    /// bytes transmitted on all the interfaces since the previous sample
+    NetworkTransmitted,
+}
+
+impl SystemMetric {
+    pub fn cpu_usage(cores: &[Core]) -> f64 {
+        if cores.is_empty() {
+            return 0.0;
+        }
+        cores.iter().map(|c| c.usage as f64).sum::<f64>() / cores.len() as f64
+    }
+
+    /// Values of all the metrics from a snapshot of the system.
+    pub fn values(
+        cores: &[Core],
+        memory: &Memory,
+        disks: &[Disk],
+        networks: &[NetworkStatistics],
+    ) -> [(Self, f64); 7] {
+        let disk_read = disks.iter().map(|d| d.read_bytes).sum::<u64>();
+        let disk_written = disks.iter().map(|d| d.written_bytes).sum::<u64>();
+        let received = networks.iter().map(|n| n.received).sum::<u64>();
+        let transmitted = networks.iter().map(|n| n.transmitted).sum::<u64>();
+        [
+            (Self::CpuUsage, Self::cpu_usage(cores)),
+            (
+                Self::MemoryUsed,
+                memory.total.saturating_sub(memory.free) as f64,
+            ),
+            (
+                Self::SwapUsed,
+                memory.swap_total.saturating_sub(memory.swap_free) as f64,
+            ),
+            (Self::DiskRead, disk_read as f64),
+            (Self::DiskWritten, disk_written as f64),
+            (Self::NetworkReceived, received as f64),
+            (Self::NetworkTransmitted, transmitted as f64),
+        ]
+    }
+}
+
+/// Aggregation of the samples taken on a period of time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct MetricBucket {
+    /// unix timestamp in milliseconds of the start of the bucket
+    pub start: i64,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    /// amount of samples merged on this bucket
+    pub count: u32,
+}
+
+impl MetricBucket {
+    fn new(start: i64, value: f64) -> Self {
+        Self {
+            start,
+            min: value,
+            avg: value,
+            max: value,
+            count: 1,
+        }
+    }
+
+    fn merge(&mut self, other: &MetricBucket) {
+        let count = self.count + other.count;
+        self.avg = (self.avg * self.count as f64 + other.avg * other.count as f64) / count as f64;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count = count;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export, optional_fields = nullable))]
+pub struct MetricsHistoryQuery {
+    pub metric: SystemMetric,
+    /// inclusive lower bound, unix timestamp in milliseconds
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// max amount of buckets to return, samples are merged on wider buckets to fit
+    #[serde(default)]
+    pub max_points: Option<usize>,
+}
+
+/// Ring buffers of the system metrics, one bucket per resolution period.
+#[derive(Debug, Clone)]
+pub struct MetricsHistory {
+    resolution: i64,
+    capacity: usize,
+    series: HashMap<SystemMetric, VecDeque<MetricBucket>>,
+}
+
+impl MetricsHistory {
+    pub fn new(settings: &SystemMetricsSettings) -> Self {
+        Self {
+            resolution: settings.resolution_millis(),
+            capacity: settings.history_capacity(),
+            series: HashMap::new(),
+        }
+    }
+
+    /// Updates resolution and retention, the history is cleared if the resolution changes.
+    pub fn configure(&mut self, settings: &SystemMetricsSettings) {
+        let resolution = settings.resolution_millis();
+        if resolution != self.resolution {
+            self.resolution = resolution;
+            self.series.clear();
+        }
+        self.capacity = settings.history_capacity();
+        for buckets in self.series.values_mut() {
+            while buckets.len() > self.capacity {
+                buckets.pop_front();
+            }
+        }
+    }
+
+    /// Adds a sample taken at the unix timestamp in milliseconds.
+    pub fn record(&mut self, metric: SystemMetric, timestamp: i64, value: f64) {
+        let start = timestamp - timestamp.rem_euclid(self.resolution);
+        let sample = MetricBucket::new(start, value);
+        let buckets = self.series.entry(metric).or_default();
+
+        match buckets.back_mut() {
+            Some(last) if last.start == start => last.merge(&sample),
+            // ignore samples from the past, e.g. after a system clock change
+            Some(last) if last.start > start => {}
+            _ => {
+                buckets.push_back(sample);
+                if buckets.len() > self.capacity {
+                    buckets.pop_front();
+                }
+            }
+        }
+    }
+
+    pub fn query(&self, query: &MetricsHistoryQuery) -> Vec<MetricBucket> {
+        let Some(buckets) = self.series.get(&query.metric) else {
+            return Vec::new();
+        };
+
+        let since = query.since.unwrap_or(i64::MIN);
+        let first = buckets.partition_point(|b| b.start < since);
+        let buckets = buckets.range(first..);
+
+        let max_points = query.max_points.unwrap_or(usize::MAX).max(1);
+        if buckets.len() <= max_points {
+            return buckets.copied().collect();
+        }
+
+        let Some(base) = buckets.clone().next().map(|b| b.start) else {
+            return Vec::new();
+        };
+        let Some(last) = buckets.clone().next_back().map(|b| b.start) else {
+            return Vec::new();
+        };
+
+        // width rounded up to a multiple of the resolution
+        let periods = (last - base) / self.resolution + 1;
+        let width = (periods as usize).div_ceil(max_points) as i64 * self.resolution;
+
+        let mut result: Vec<MetricBucket> = Vec::new();
+        for bucket in buckets {
+            let start = base + (bucket.start - base) / width * width;
+            match result.last_mut() {
+                Some(last) if last.start == start => last.merge(bucket),
+                _ => result.push(MetricBucket { start, ..*bucket }),
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(resolution_secs: u32, retention_minutes: u32) -> MetricsHistory {
+        MetricsHistory::new(&SystemMetricsSettings {
+            resolution_secs,
+            retention_minutes,
+            ..Default::default()
+        })
+    }
+
+    fn query(
+        metric: SystemMetric,
+        since: Option<i64>,
+        max_points: Option<usize>,
+    ) -> MetricsHistoryQuery {
+        MetricsHistoryQuery {
+            metric,
+            since,
+            max_points,
+        }
+    }
+
+    #[test]
+    fn test_record_merges_samples_on_resolution() {
+        let mut history = history(5, 1);
+        for (secs, value) in [(0, 10.0), (2, 30.0), (4, 20.0), (5, 50.0), (3, 99.0)] {
+            history.record(SystemMetric::CpuUsage, secs * 1000, value);
+        }
+
+        let buckets = history.query(&query(SystemMetric::CpuUsage, None, None));
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].start, 0);
+        assert_eq!(
+            (buckets[0].min, buckets[0].avg, buckets[0].max),
+            (10.0, 20.0, 30.0)
+        );
+        assert_eq!(buckets[0].count, 3);
+        // late sample of a previous bucket is ignored
+        assert_eq!(buckets[1], MetricBucket::new(5000, 50.0));
+        assert!(history
+            .query(&query(SystemMetric::MemoryUsed, None, None))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_retention() {
+        let mut history = history(30, 1);
+        for secs in 0..5 {
+            history.record(SystemMetric::DiskRead, secs * 30_000, secs as f64);
+        }
+        let starts = |h: &MetricsHistory| -> Vec<i64> {
+            h.query(&query(SystemMetric::DiskRead, None, None))
+                .iter()
+                .map(|b| b.start)
+                .collect()
+        };
+        assert_eq!(starts(&history), vec![90_000, 120_000]);
+
+        history.configure(&SystemMetricsSettings {
+            resolution_secs: 30,
+            retention_minutes: 0,
+            ..Default::default()
+        });
+        assert_eq!(starts(&history), vec![120_000]);
+
+        history.configure(&SystemMetricsSettings {
+            resolution_secs: 1,
+            retention_minutes: 1,
+            ..Default::default()
+        });
+        assert!(starts(&history).is_empty());
+    }
+
+    #[test]
+    fn test_downsampling() {
+        let mut history = history(1, 60);
+        for secs in 0..10 {
+            history.record(SystemMetric::NetworkReceived, secs * 1000, secs as f64);
+        }
+
+        let buckets = history.query(&query(SystemMetric::NetworkReceived, Some(2000), Some(3)));
+        let summary: Vec<_> = buckets
+            .iter()
+            .map(|b| (b.start, b.min, b.avg, b.max, b.count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2000, 2.0, 3.0, 4.0, 3),
+                (5000, 5.0, 6.0, 7.0, 3),
+                (8000, 8.0, 8.5, 9.0, 2),
+            ]
+        );
+
+        let all = history.query(&query(SystemMetric::NetworkReceived, None, Some(10)));
+        assert_eq!(all.len(), 10);
+    }
+}
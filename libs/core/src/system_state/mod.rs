@@ -1,27 +1,44 @@
+mod app_search;
 mod bluetooth;
 mod components;
 mod language;
+mod launcher;
 mod media;
+mod metrics_history;
 mod monitors;
 mod network;
 mod notification;
+mod notification_group;
+mod notification_history;
+mod notification_widget;
+mod notification_xml;
 mod power;
+mod processes;
 mod radios;
+mod system_alerts;
 mod tray;
 mod ui_colors;
 mod user;
 mod user_apps;
 mod win_explorer;
 
+pub use app_search::*;
 pub use bluetooth::*;
 pub use components::*;
 pub use language::*;
+pub use launcher::*;
 pub use media::*;
+pub use metrics_history::*;
 pub use monitors::*;
 pub use network::*;
 pub use notification::*;
+pub use notification_group::*;
+pub use notification_history::*;
+pub use notification_widget::*;
 pub use power::*;
+pub use processes::*;
 pub use radios::*;
+pub use system_alerts::*;
 pub use tray::*;
 pub use ui_colors::*;
 pub use user::*;
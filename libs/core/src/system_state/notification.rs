@@ -3,7 +3,9 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-#[derive(Debug, Clone, Serialize, TS)]
+use crate::resource::WidgetId;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "gen-binds", ts(export))]
 pub struct AppNotification {
@@ -11,7 +13,15 @@ pub struct AppNotification {
     pub app_umid: String,
     pub app_name: String,
     pub app_description: String,
+    /// windows file time, 100-nanosecond intervals since 1601-01-01 (UTC)
     pub date: i64,
+    /// added by a silencing notification rule, should be listed without popups or sounds
+    #[serde(default)]
+    pub silent: bool,
+    /// widget that posted the notification, None for system notifications
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub widget_id: Option<WidgetId>,
     pub content: Toast,
 }
 
@@ -22,19 +32,60 @@ pub struct Toast {
     pub header: Option<ToastHeader>,
     pub visual: ToastVisual,
     pub actions: Option<ToastActions>,
+    pub audio: Option<ToastAudio>,
     #[serde(rename = "@launch")]
     pub launch: Option<String>,
     #[serde(rename = "@activationType")]
     pub activation_type: ToastActionActivationType,
     #[serde(rename = "@duration")]
     pub duration: ToastDuration,
+    #[serde(rename = "@scenario")]
+    pub scenario: Option<ToastScenario>,
+    /// ISO 8601 date overriding the time when the notification was received
+    #[serde(rename = "@displayTimestamp")]
+    pub display_timestamp: Option<String>,
+    #[serde(rename = "@useButtonStyle")]
+    pub use_button_style: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+pub enum ToastScenario {
+    #[serde(alias = "default")]
+    Default,
+    #[serde(alias = "alarm")]
+    Alarm,
+    #[serde(alias = "reminder")]
+    Reminder,
+    #[serde(alias = "incomingCall")]
+    IncomingCall,
+    #[serde(alias = "urgent")]
+    Urgent,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Specifies a sound to play when a toast notification is displayed.
+///
+/// https://learn.microsoft.com/en-us/uwp/schemas/tiles/toastschema/element-audio
+#[derive(Debug, Default, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ToastAudio {
+    #[serde(rename = "@src")]
+    pub src: Option<String>,
+    #[serde(rename = "@loop")]
+    pub r#loop: bool,
+    #[serde(rename = "@silent")]
+    pub silent: bool,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, TS)]
 #[ts(repr(enum = name))]
 pub enum ToastDuration {
     #[default]
+    #[serde(alias = "short")]
     Short,
+    #[serde(alias = "long")]
     Long,
     #[serde(other)]
     Unknown,
@@ -56,42 +107,41 @@ pub struct ToastHeader {
 }
 
 /// https://learn.microsoft.com/en-us/uwp/schemas/tiles/toastschema/element-visual
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, TS)]
 #[serde(default)]
 pub struct ToastVisual {
     pub binding: ToastBinding,
+    /// windows uses "ms-appx:///" if not set
     #[serde(rename = "@baseUri")]
-    pub base_uri: String,
+    pub base_uri: Option<String>,
     #[serde(rename = "@lang")]
-    pub lang: String,
+    pub lang: Option<String>,
     #[serde(rename = "@version")]
-    pub version: u32,
+    pub version: Option<u32>,
     #[serde(rename = "@addImageQuery")]
     pub add_image_query: bool,
 }
 
-impl Default for ToastVisual {
-    fn default() -> Self {
-        ToastVisual {
-            binding: Default::default(),
-            base_uri: "ms-appx:///".to_owned(),
-            lang: "none".to_owned(),
-            version: 1,
-            add_image_query: false,
-        }
-    }
-}
-
+/// https://learn.microsoft.com/en-us/uwp/schemas/tiles/toastschema/element-binding
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 #[serde(default)]
 pub struct ToastBinding {
     #[serde(rename = "@template")]
     pub template: ToastTemplateType,
+    /// template to use if the given one is not supported
+    #[serde(rename = "@fallback")]
+    pub fallback: Option<String>,
+    #[serde(rename = "@baseUri")]
+    pub base_uri: Option<String>,
+    #[serde(rename = "@lang")]
+    pub lang: Option<String>,
+    #[serde(rename = "@addImageQuery")]
+    pub add_image_query: bool,
     #[serde(rename = "$value")]
     pub children: Vec<ToastBindingChild>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[ts(repr(enum = name))]
 pub enum ToastTemplateType {
     ToastImageAndText01,
@@ -124,6 +174,34 @@ pub struct ToastText {
     pub id: Option<u32>,
     #[serde(rename = "$value")]
     pub content: String,
+    #[serde(rename = "@lang")]
+    pub lang: Option<String>,
+    #[serde(rename = "@placement")]
+    pub placement: Option<ToastTextPlacement>,
+    /// text style, as "caption", "body" or "title"
+    #[serde(rename = "@hint-style")]
+    pub hint_style: Option<String>,
+    /// horizontal alignment, as "left", "center" or "right"
+    #[serde(rename = "@hint-align")]
+    pub hint_align: Option<String>,
+    #[serde(rename = "@hint-wrap")]
+    pub hint_wrap: Option<bool>,
+    #[serde(rename = "@hint-maxLines")]
+    pub hint_max_lines: Option<u32>,
+    #[serde(rename = "@hint-minLines")]
+    pub hint_min_lines: Option<u32>,
+    #[serde(rename = "@hint-callScenarioCenterAlign")]
+    pub hint_call_scenario_center_align: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(repr(enum = name))]
+pub enum ToastTextPlacement {
+    /// the text is shown as the source of the notification, below the other texts
+    #[serde(alias = "attribution")]
+    Attribution,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -140,11 +218,18 @@ pub struct ToastImage {
     pub placement: Option<ToastImagePlacement>,
     #[serde(rename = "@hint-crop")]
     pub hint_crop: Option<ToastImageCropType>,
+    #[serde(rename = "@hint-removeMargin")]
+    pub hint_remove_margin: Option<bool>,
+    /// horizontal alignment, as "stretch", "left", "center" or "right"
+    #[serde(rename = "@hint-align")]
+    pub hint_align: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(repr(enum = name))]
 pub enum ToastImageCropType {
+    #[serde(alias = "none")]
+    None,
     #[serde(alias = "circle")]
     Circle,
     #[serde(other)]
@@ -178,6 +263,12 @@ pub struct ToastGroup {
 pub struct ToastSubGroup {
     #[serde(rename = "$value")]
     pub children: Vec<ToastSubGroupChild>,
+    /// width of the column relative to the other subgroups
+    #[serde(rename = "@hint-weight")]
+    pub hint_weight: Option<u32>,
+    /// vertical alignment, as "top", "center" or "bottom"
+    #[serde(rename = "@hint-textStacking")]
+    pub hint_text_stacking: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -233,6 +324,9 @@ pub struct ToastInput {
     /// Text displayed as a label for the input.
     #[serde(rename = "@title")]
     pub title: Option<String>,
+    /// Id of the selection selected by default.
+    #[serde(rename = "@defaultInput")]
+    pub default_input: Option<String>,
     /// Options for the input if it is of type selection.
     #[serde(default)]
     pub selection: Vec<ToastInputSelection>,
@@ -279,7 +373,7 @@ pub struct ToastAction {
     /// this is used as button icon
     #[serde(rename = "@imageUri")]
     pub image_uri: Option<String>,
-    #[serde(rename = "@hint-inputid")]
+    #[serde(rename = "@hint-inputid", alias = "@hint-inputId")]
     pub hint_inputid: Option<String>,
     #[serde(rename = "@hint-buttonStyle")]
     pub hint_button_style: Option<ToastActionButtonStyle>,
@@ -291,7 +385,7 @@ pub struct ToastAction {
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(repr(enum = name))]
 pub enum ToastActionButtonStyle {
-    #[serde(alias = "success")]
+    #[serde(alias = "success", alias = "Success")]
     Sucess,
     #[serde(alias = "critical")]
     Critical,
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use super::AppNotification;
+
+/// Identifies a group of notifications, an app group if `header_id` is None,
+/// or a header group (`ToastHeader.id`) inside an app group otherwise.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct NotificationGroupId {
+    pub app_umid: String,
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub header_id: Option<String>,
+}
+
+impl NotificationGroupId {
+    pub fn contains(&self, notification: &AppNotification) -> bool {
+        if self.app_umid != notification.app_umid {
+            return false;
+        }
+        match &self.header_id {
+            Some(id) => notification
+                .content
+                .header
+                .as_ref()
+                .is_some_and(|h| &h.id == id),
+            None => true,
+        }
+    }
+}
+
+/// Notifications sharing the same `ToastHeader`, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct NotificationHeaderGroup {
+    pub id: NotificationGroupId,
+    pub title: String,
+    pub count: usize,
+    /// date of the newest notification in the group
+    pub newest: i64,
+    pub collapsed: bool,
+    pub notifications: Vec<AppNotification>,
+}
+
+/// Notifications of a single app.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct NotificationAppGroup {
+    pub id: NotificationGroupId,
+    pub app_name: String,
+    /// amount of notifications of the app, including the ones in header groups
+    pub count: usize,
+    /// date of the newest notification of the app
+    pub newest: i64,
+    pub collapsed: bool,
+    pub headers: Vec<NotificationHeaderGroup>,
+    /// notifications without header, newest first
+    pub notifications: Vec<AppNotification>,
+}
+
+/// newest first, ties are resolved by id so the order doesn't depend on the input order.
+fn sort_newest_first(notifications: &mut [AppNotification]) {
+    notifications.sort_by(|a, b| b.date.cmp(&a.date).then(b.id.cmp(&a.id)));
+}
+
+impl NotificationAppGroup {
+    /// Groups the notifications by app and then by header. Groups are sorted by their
+    /// newest notification, ties are resolved by id so the result is stable for the same input.
+    pub fn group(
+        notifications: &[AppNotification],
+        collapsed: &HashSet<NotificationGroupId>,
+    ) -> Vec<Self> {
+        let mut notifications = notifications.to_vec();
+        sort_newest_first(&mut notifications);
+
+        let mut groups: Vec<Self> = Vec::new();
+        for notification in notifications {
+            let idx = match groups
+                .iter()
+                .position(|g| g.id.app_umid == notification.app_umid)
+            {
+                Some(idx) => idx,
+                None => {
+                    let id = NotificationGroupId {
+                        app_umid: notification.app_umid.clone(),
+                        header_id: None,
+                    };
+                    groups.push(Self {
+                        collapsed: collapsed.contains(&id),
+                        id,
+                        app_name: notification.app_name.clone(),
+                        count: 0,
+                        newest: notification.date,
+                        headers: Vec::new(),
+                        notifications: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+
+            let group = &mut groups[idx];
+            group.count += 1;
+
+            let Some(header) = notification.content.header.clone() else {
+                group.notifications.push(notification);
+                continue;
+            };
+
+            match group
+                .headers
+                .iter_mut()
+                .find(|h| h.id.header_id.as_deref() == Some(header.id.as_str()))
+            {
+                Some(header_group) => {
+                    header_group.count += 1;
+                    header_group.notifications.push(notification);
+                }
+                None => {
+                    let id = NotificationGroupId {
+                        app_umid: notification.app_umid.clone(),
+                        header_id: Some(header.id),
+                    };
+                    group.headers.push(NotificationHeaderGroup {
+                        collapsed: collapsed.contains(&id),
+                        id,
+                        title: header.title,
+                        count: 1,
+                        newest: notification.date,
+                        notifications: vec![notification],
+                    });
+                }
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_state::{Toast, ToastHeader};
+
+    use super::*;
+
+    fn notification(id: u32, umid: &str, date: i64, header: Option<&str>) -> AppNotification {
+        let content = Toast {
+            header: header.map(|h| ToastHeader {
+                id: h.to_string(),
+                title: h.to_uppercase(),
+                arguments: String::new(),
+                activation_type: Default::default(),
+            }),
+            ..Default::default()
+        };
+        AppNotification {
+            id,
+            app_umid: umid.to_string(),
+            app_name: umid.to_string(),
+            app_description: String::new(),
+            date,
+            silent: false,
+            widget_id: None,
+            content,
+        }
+    }
+
+    fn sample() -> Vec<AppNotification> {
+        vec![
+            notification(1, "Mail", 10, None),
+            notification(2, "Chat", 20, Some("general")),
+            notification(3, "Chat", 30, Some("random")),
+            notification(4, "Chat", 40, Some("general")),
+            notification(5, "Mail", 50, Some("inbox")),
+        ]
+    }
+
+    #[test]
+    fn test_grouping() {
+        let groups = NotificationAppGroup::group(&sample(), &HashSet::new());
+
+        let apps: Vec<_> = groups.iter().map(|g| g.id.app_umid.as_str()).collect();
+        assert_eq!(apps, vec!["Mail", "Chat"]);
+
+        let mail = &groups[0];
+        assert_eq!(mail.count, 2);
+        assert_eq!(mail.newest, 50);
+        assert_eq!(mail.notifications.len(), 1);
+        assert_eq!(mail.headers[0].title, "INBOX");
+
+        let chat = &groups[1];
+        assert_eq!(chat.count, 3);
+        let headers: Vec<_> = chat
+            .headers
+            .iter()
+            .map(|h| (h.id.header_id.as_deref().unwrap(), h.count, h.newest))
+            .collect();
+        assert_eq!(headers, vec![("general", 2, 40), ("random", 1, 30)]);
+        let ids: Vec<_> = chat.headers[0].notifications.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![4, 2]);
+    }
+
+    #[test]
+    fn test_grouping_is_stable() {
+        let mut reversed = sample();
+        reversed.reverse();
+        // same date on different apps
+        reversed.push(notification(6, "Calendar", 50, None));
+
+        let mut input = sample();
+        input.push(notification(6, "Calendar", 50, None));
+
+        let a = NotificationAppGroup::group(&input, &HashSet::new());
+        let b = NotificationAppGroup::group(&reversed, &HashSet::new());
+        let order = |groups: &[NotificationAppGroup]| {
+            groups
+                .iter()
+                .map(|g| g.id.app_umid.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(order(&a), order(&b));
+        assert_eq!(order(&a), vec!["Calendar", "Mail", "Chat"]);
+    }
+
+    #[test]
+    fn test_collapse_and_contains() {
+        let chat_general = NotificationGroupId {
+            app_umid: "Chat".to_string(),
+            header_id: Some("general".to_string()),
+        };
+        let mail = NotificationGroupId {
+            app_umid: "Mail".to_string(),
+            header_id: None,
+        };
+        let collapsed = HashSet::from([chat_general.clone(), mail.clone()]);
+        let groups = NotificationAppGroup::group(&sample(), &collapsed);
+
+        assert!(groups[0].collapsed);
+        assert!(!groups[1].collapsed);
+        assert!(groups[1].headers[0].collapsed);
+        assert!(!groups[1].headers[1].collapsed);
+
+        let contained: Vec<_> = sample()
+            .into_iter()
+            .filter(|n| chat_general.contains(n))
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(contained, vec![2, 4]);
+        assert_eq!(sample().iter().filter(|n| mail.contains(n)).count(), 2);
+    }
+}
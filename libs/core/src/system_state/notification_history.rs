@@ -0,0 +1,278 @@
+use crate::state::NotificationHistorySettings;
+
+use super::{AppNotification, Toast, ToastBindingChild, ToastSubGroupChild};
+
+/// 100-nanosecond intervals per millisecond, unit used by `AppNotification.date` (windows file time).
+const FILE_TIME_TICKS_PER_MILLI: i64 = 10_000;
+/// milliseconds between 1601-01-01 (windows file time epoch) and 1970-01-01
+const FILE_TIME_EPOCH_DIFF_MILLIS: i64 = 11_644_473_600_000;
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Converts a unix timestamp in milliseconds to the unit used by `AppNotification.date`.
+pub fn file_time_from_unix_millis(millis: i64) -> i64 {
+    (millis + FILE_TIME_EPOCH_DIFF_MILLIS) * FILE_TIME_TICKS_PER_MILLI
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export, optional_fields = nullable))]
+pub struct NotificationHistoryQuery {
+    /// only notifications of this app user model id
+    pub app_umid: Option<String>,
+    /// inclusive lower bound, same unit as `AppNotification.date`
+    pub since: Option<i64>,
+    /// inclusive upper bound, same unit as `AppNotification.date`
+    pub until: Option<i64>,
+    /// case insensitive text to be searched on the app name and the toast texts
+    pub text: Option<String>,
+    /// max amount of results
+    pub limit: Option<usize>,
+}
+
+impl NotificationHistoryQuery {
+    pub fn matches(&self, notification: &AppNotification) -> bool {
+        if self
+            .app_umid
+            .as_ref()
+            .is_some_and(|umid| umid != &notification.app_umid)
+        {
+            return false;
+        }
+        if self.since.is_some_and(|since| notification.date < since)
+            || self.until.is_some_and(|until| notification.date > until)
+        {
+            return false;
+        }
+
+        let Some(text) = self.text.as_ref().map(|t| t.trim().to_lowercase()) else {
+            return true;
+        };
+        if text.is_empty() || notification.app_name.to_lowercase().contains(&text) {
+            return true;
+        }
+        notification
+            .content
+            .texts()
+            .iter()
+            .any(|t| t.to_lowercase().contains(&text))
+    }
+}
+
+impl Toast {
+    /// All the texts of the toast, including headers, groups and progress bars.
+    pub fn texts(&self) -> Vec<&str> {
+        let mut texts = Vec::new();
+        if let Some(header) = &self.header {
+            texts.push(header.title.as_str());
+        }
+        for child in &self.visual.binding.children {
+            match child {
+                ToastBindingChild::Text(text) => texts.push(text.content.as_str()),
+                ToastBindingChild::Group(group) => {
+                    for subgroup in &group.subgroup {
+                        for child in &subgroup.children {
+                            if let ToastSubGroupChild::Text(text) = child {
+                                texts.push(text.content.as_str());
+                            }
+                        }
+                    }
+                }
+                ToastBindingChild::Progress(progress) => {
+                    if let Some(title) = &progress.title {
+                        texts.push(title.as_str());
+                    }
+                    texts.push(progress.status.as_str());
+                }
+                ToastBindingChild::Image(_) => {}
+            }
+        }
+        texts
+    }
+}
+
+/// Local record of the received notifications, kept after they are removed
+/// from the Action Center. Entries are sorted from newest to oldest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationHistory {
+    pub entries: Vec<AppNotification>,
+}
+
+impl NotificationHistory {
+    /// Adds the notification to the history, returns false if it was already recorded.
+    pub fn record(&mut self, notification: AppNotification) -> bool {
+        if self.entries.iter().any(|n| {
+            n.id == notification.id
+                && n.date == notification.date
+                && n.app_umid == notification.app_umid
+        }) {
+            return false;
+        }
+        let idx = self.entries.partition_point(|n| n.date > notification.date);
+        self.entries.insert(idx, notification);
+        true
+    }
+
+    /// Removes the entries older than the max age and the ones over the max count,
+    /// `now` is a windows file time, as `AppNotification.date`.
+    pub fn apply_retention(&mut self, settings: &NotificationHistorySettings, now: i64) {
+        if settings.max_age_days > 0 {
+            let max_age = settings.max_age_days as i64 * MILLIS_PER_DAY * FILE_TIME_TICKS_PER_MILLI;
+            self.entries.retain(|n| now - n.date <= max_age);
+        }
+        if settings.max_entries > 0 {
+            self.entries.truncate(settings.max_entries as usize);
+        }
+    }
+
+    pub fn query(&self, query: &NotificationHistoryQuery) -> Vec<AppNotification> {
+        self.entries
+            .iter()
+            .filter(|n| query.matches(n))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_state::{ToastGroup, ToastSubGroup, ToastText};
+
+    use super::*;
+
+    const DAY: i64 = MILLIS_PER_DAY * FILE_TIME_TICKS_PER_MILLI;
+
+    fn text(content: &str) -> ToastText {
+        ToastText {
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn notification(id: u32, umid: &str, date: i64, texts: &[&str]) -> AppNotification {
+        let mut content = Toast::default();
+        for t in texts {
+            content
+                .visual
+                .binding
+                .children
+                .push(ToastBindingChild::Text(text(t)));
+        }
+        AppNotification {
+            id,
+            app_umid: umid.to_string(),
+            app_name: umid.to_string(),
+            app_description: String::new(),
+            date,
+            silent: false,
+            widget_id: None,
+            content,
+        }
+    }
+
+    fn history() -> NotificationHistory {
+        let mut history = NotificationHistory::default();
+        history.record(notification(1, "Mail", DAY, &["Weekly report"]));
+        history.record(notification(2, "Chat", 3 * DAY, &["Lunch?", "at 1pm"]));
+        history.record(notification(3, "Mail", 2 * DAY, &["Invoice ready"]));
+        history
+    }
+
+    fn ids(entries: &[AppNotification]) -> Vec<u32> {
+        entries.iter().map(|n| n.id).collect()
+    }
+
+    #[test]
+    fn test_record_keeps_order_and_dedupes() {
+        let mut history = history();
+        assert_eq!(ids(&history.entries), vec![2, 3, 1]);
+        assert!(!history.record(notification(3, "Mail", 2 * DAY, &["Invoice ready"])));
+        assert_eq!(history.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_query() {
+        let history = history();
+        let query = |q: NotificationHistoryQuery| ids(&history.query(&q));
+
+        assert_eq!(
+            query(NotificationHistoryQuery {
+                app_umid: Some("Mail".to_string()),
+                ..Default::default()
+            }),
+            vec![3, 1]
+        );
+        assert_eq!(
+            query(NotificationHistoryQuery {
+                since: Some(2 * DAY),
+                until: Some(3 * DAY),
+                ..Default::default()
+            }),
+            vec![2, 3]
+        );
+        assert_eq!(
+            query(NotificationHistoryQuery {
+                text: Some("1PM".to_string()),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+        assert_eq!(
+            query(NotificationHistoryQuery {
+                limit: Some(1),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_query_searches_groups() {
+        let mut history = NotificationHistory::default();
+        let mut grouped = notification(1, "Weather", DAY, &[]);
+        grouped
+            .content
+            .visual
+            .binding
+            .children
+            .push(ToastBindingChild::Group(ToastGroup {
+                subgroup: vec![ToastSubGroup {
+                    children: vec![ToastSubGroupChild::Text(text("Sunny"))],
+                    ..Default::default()
+                }],
+            }));
+        history.record(grouped);
+
+        let query = NotificationHistoryQuery {
+            text: Some("sunny".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(history.query(&query).len(), 1);
+    }
+
+    #[test]
+    fn test_retention() {
+        let mut history = history();
+        let settings = NotificationHistorySettings {
+            enabled: true,
+            max_age_days: 1,
+            max_entries: 0,
+        };
+        history.apply_retention(&settings, 3 * DAY);
+        assert_eq!(ids(&history.entries), vec![2, 3]);
+
+        let settings = NotificationHistorySettings {
+            enabled: true,
+            max_age_days: 0,
+            max_entries: 1,
+        };
+        history.apply_retention(&settings, 3 * DAY);
+        assert_eq!(ids(&history.entries), vec![2]);
+    }
+
+    #[test]
+    fn test_file_time_conversion() {
+        assert_eq!(file_time_from_unix_millis(0), 116_444_736_000_000_000);
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use super::{
+    Toast, ToastAction, ToastActionActivationType, ToastActions, ToastActionsChild,
+    ToastBindingChild, ToastImage, ToastImagePlacement, ToastText,
+};
+
+/// Notification posted by a widget, shown by Seelen along the system notifications.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetNotification {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    /// url or path of the image to be used as app logo
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub image: Option<String>,
+    /// buttons of the notification, up to five
+    #[serde(default)]
+    pub actions: Vec<WidgetNotificationAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetNotificationAction {
+    /// sent back to the widget when the action is clicked
+    pub id: String,
+    pub label: String,
+}
+
+/// Sent to the widget that posted the notification when one of its actions is clicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct WidgetNotificationActivated {
+    pub notification_id: u32,
+    pub action_id: String,
+    /// values of the toast inputs by input id
+    pub inputs: HashMap<String, String>,
+}
+
+impl WidgetNotificationActivated {
+    /// Arguments used by the toast actions of widget notifications.
+    pub fn encode_arguments(notification_id: u32, action_id: &str) -> String {
+        format!("{notification_id}:{action_id}")
+    }
+
+    /// Inverse of `encode_arguments`, returns None if the arguments were not created by it.
+    pub fn decode(arguments: &str, inputs: HashMap<String, String>) -> Option<Self> {
+        let (notification_id, action_id) = arguments.split_once(':')?;
+        Some(Self {
+            notification_id: notification_id.parse().ok()?,
+            action_id: action_id.to_string(),
+            inputs,
+        })
+    }
+}
+
+impl WidgetNotification {
+    pub const MAX_ACTIONS: usize = 5;
+
+    /// Builds the toast to be shown, `notification_id` is used to identify the action clicks.
+    pub fn to_toast(&self, notification_id: u32) -> Toast {
+        let mut toast = Toast::default();
+
+        let children = &mut toast.visual.binding.children;
+        if let Some(image) = &self.image {
+            children.push(ToastBindingChild::Image(ToastImage {
+                id: None,
+                src: image.clone(),
+                alt: None,
+                add_image_query: false,
+                placement: Some(ToastImagePlacement::AppLogoOverride),
+                hint_crop: None,
+                hint_remove_margin: None,
+                hint_align: None,
+            }));
+        }
+        for content in [&self.title, &self.body] {
+            if !content.is_empty() {
+                children.push(ToastBindingChild::Text(ToastText {
+                    content: content.clone(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if !self.actions.is_empty() {
+            toast.actions = Some(ToastActions {
+                children: self
+                    .actions
+                    .iter()
+                    .take(Self::MAX_ACTIONS)
+                    .map(|action| {
+                        ToastActionsChild::Action(ToastAction {
+                            content: action.label.clone(),
+                            arguments: WidgetNotificationActivated::encode_arguments(
+                                notification_id,
+                                &action.id,
+                            ),
+                            activation_type: ToastActionActivationType::Background,
+                            after_activation_behavior: Default::default(),
+                            placement: None,
+                            image_uri: None,
+                            hint_inputid: None,
+                            hint_button_style: None,
+                            hint_tooltip: None,
+                        })
+                    })
+                    .collect(),
+            });
+        }
+        toast
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widget_notification_to_toast() {
+        let notification = WidgetNotification {
+            title: "Build finished".to_string(),
+            body: String::new(),
+            image: Some("https://example.com/logo.png".to_string()),
+            actions: (0..7)
+                .map(|i| WidgetNotificationAction {
+                    id: format!("action-{i}"),
+                    label: format!("Action {i}"),
+                })
+                .collect(),
+        };
+
+        let toast = notification.to_toast(42);
+        assert_eq!(toast.texts(), vec!["Build finished"]);
+        assert!(matches!(
+            toast.visual.binding.children[0],
+            ToastBindingChild::Image(_)
+        ));
+
+        let actions = toast.actions.unwrap().children;
+        assert_eq!(actions.len(), WidgetNotification::MAX_ACTIONS);
+        let ToastActionsChild::Action(first) = &actions[0] else {
+            panic!("expected an action");
+        };
+        assert_eq!(first.arguments, "42:action-0");
+    }
+
+    #[test]
+    fn test_action_arguments_round_trip() {
+        let inputs = HashMap::from([("reply".to_string(), "hi".to_string())]);
+        let arguments = WidgetNotificationActivated::encode_arguments(7, "reply:send");
+
+        assert_eq!(
+            WidgetNotificationActivated::decode(&arguments, inputs.clone()),
+            Some(WidgetNotificationActivated {
+                notification_id: 7,
+                action_id: "reply:send".to_string(),
+                inputs: inputs.clone(),
+            })
+        );
+        assert_eq!(
+            WidgetNotificationActivated::decode("action=reply", inputs),
+            None
+        );
+    }
+}
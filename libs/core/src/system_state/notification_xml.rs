@@ -0,0 +1,610 @@
+// Serialization of toasts back to xml, following https://learn.microsoft.com/en-us/uwp/schemas/tiles/toastschema/schema-root
+// The serde implementations can't be used for this as they are also used to share the toasts with the UI.
+// Attributes set to the default value of the schema are omitted.
+
+use super::{
+    Toast, ToastAction, ToastActionActivationType, ToastActionAfterActivationBehavior,
+    ToastActionButtonStyle, ToastActionPlacement, ToastActionsChild, ToastBindingChild,
+    ToastDuration, ToastHeader, ToastImage, ToastImageCropType, ToastImagePlacement, ToastInput,
+    ToastInputType, ToastProgress, ToastScenario, ToastSubGroupChild, ToastTemplateType, ToastText,
+    ToastTextPlacement,
+};
+
+/// Minimal xml tree, only the needed by the toast schema.
+struct XmlElement {
+    name: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    text: Option<String>,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            attributes: Vec::new(),
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.attributes.push((name, value.to_string()));
+        self
+    }
+
+    fn opt_attr(self, name: &'static str, value: Option<impl ToString>) -> Self {
+        match value {
+            Some(value) => self.attr(name, value),
+            None => self,
+        }
+    }
+
+    fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_owned());
+        self
+    }
+
+    fn child(mut self, child: XmlElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(self.name);
+        for (name, value) in &self.attributes {
+            out.push_str(&format!(" {name}=\"{}\"", escape(value)));
+        }
+
+        if self.text.is_none() && self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+
+        out.push('>');
+        if let Some(text) = &self.text {
+            out.push_str(&escape(text));
+        }
+        for child in &self.children {
+            child.write(out);
+        }
+        out.push_str(&format!("</{}>", self.name));
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl ToastActionActivationType {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Foreground => Some("foreground"),
+            Self::Background => Some("background"),
+            Self::Protocol => Some("protocol"),
+            Self::System => Some("system"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastScenario {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Default => Some("default"),
+            Self::Alarm => Some("alarm"),
+            Self::Reminder => Some("reminder"),
+            Self::IncomingCall => Some("incomingCall"),
+            Self::Urgent => Some("urgent"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastActionActivationType {
+    /// Foreground is the default of the schema, so it is omitted.
+    fn as_non_default_xml_value(&self) -> Option<&'static str> {
+        self.as_xml_value().filter(|v| *v != "foreground")
+    }
+}
+
+impl ToastDuration {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Short => Some("short"),
+            Self::Long => Some("long"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastTemplateType {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::ToastImageAndText01 => Some("ToastImageAndText01"),
+            Self::ToastImageAndText02 => Some("ToastImageAndText02"),
+            Self::ToastImageAndText03 => Some("ToastImageAndText03"),
+            Self::ToastImageAndText04 => Some("ToastImageAndText04"),
+            Self::ToastText01 => Some("ToastText01"),
+            Self::ToastText02 => Some("ToastText02"),
+            Self::ToastText03 => Some("ToastText03"),
+            Self::ToastText04 => Some("ToastText04"),
+            Self::ToastGeneric => Some("ToastGeneric"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastTextPlacement {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Attribution => Some("attribution"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastImageCropType {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::None => Some("none"),
+            Self::Circle => Some("circle"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastImagePlacement {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::AppLogoOverride => Some("appLogoOverride"),
+            Self::Hero => Some("hero"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastInputType {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Text => Some("text"),
+            Self::Selection => Some("selection"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastActionButtonStyle {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Sucess => Some("Success"),
+            Self::Critical => Some("Critical"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastActionAfterActivationBehavior {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Default => Some("default"),
+            Self::PendingUpdate => Some("pendingUpdate"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastActionPlacement {
+    fn as_xml_value(&self) -> Option<&'static str> {
+        match self {
+            Self::ContextMenu => Some("contextMenu"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl ToastHeader {
+    fn to_xml_element(&self) -> XmlElement {
+        XmlElement::new("header")
+            .attr("id", &self.id)
+            .attr("title", &self.title)
+            .attr("arguments", &self.arguments)
+            .opt_attr(
+                "activationType",
+                self.activation_type.as_non_default_xml_value(),
+            )
+    }
+}
+
+impl ToastText {
+    fn to_xml_element(&self) -> XmlElement {
+        XmlElement::new("text")
+            .opt_attr("id", self.id)
+            .opt_attr("lang", self.lang.as_ref())
+            .opt_attr(
+                "placement",
+                self.placement.as_ref().and_then(|p| p.as_xml_value()),
+            )
+            .opt_attr("hint-style", self.hint_style.as_ref())
+            .opt_attr("hint-align", self.hint_align.as_ref())
+            .opt_attr("hint-wrap", self.hint_wrap)
+            .opt_attr("hint-maxLines", self.hint_max_lines)
+            .opt_attr("hint-minLines", self.hint_min_lines)
+            .opt_attr(
+                "hint-callScenarioCenterAlign",
+                self.hint_call_scenario_center_align,
+            )
+            .text(&self.content)
+    }
+}
+
+impl ToastImage {
+    fn to_xml_element(&self) -> XmlElement {
+        XmlElement::new("image")
+            .opt_attr("id", self.id)
+            .attr("src", &self.src)
+            .opt_attr("alt", self.alt.as_ref())
+            .opt_attr("addImageQuery", self.add_image_query.then_some(true))
+            .opt_attr(
+                "placement",
+                self.placement.as_ref().and_then(|p| p.as_xml_value()),
+            )
+            .opt_attr(
+                "hint-crop",
+                self.hint_crop.as_ref().and_then(|c| c.as_xml_value()),
+            )
+            .opt_attr("hint-removeMargin", self.hint_remove_margin)
+            .opt_attr("hint-align", self.hint_align.as_ref())
+    }
+}
+
+impl ToastProgress {
+    fn to_xml_element(&self) -> XmlElement {
+        XmlElement::new("progress")
+            .opt_attr("title", self.title.as_ref())
+            .attr("status", &self.status)
+            .attr("value", &self.value)
+            .opt_attr("valueStringOverride", self.value_string_override.as_ref())
+    }
+}
+
+impl ToastBindingChild {
+    fn to_xml_element(&self) -> XmlElement {
+        match self {
+            Self::Text(text) => text.to_xml_element(),
+            Self::Image(image) => image.to_xml_element(),
+            Self::Progress(progress) => progress.to_xml_element(),
+            Self::Group(group) => {
+                group
+                    .subgroup
+                    .iter()
+                    .fold(XmlElement::new("group"), |element, subgroup| {
+                        let subgroup = subgroup.children.iter().fold(
+                            XmlElement::new("subgroup")
+                                .opt_attr("hint-weight", subgroup.hint_weight)
+                                .opt_attr(
+                                    "hint-textStacking",
+                                    subgroup.hint_text_stacking.as_ref(),
+                                ),
+                            |element, child| {
+                                element.child(match child {
+                                    ToastSubGroupChild::Text(text) => text.to_xml_element(),
+                                    ToastSubGroupChild::Image(image) => image.to_xml_element(),
+                                })
+                            },
+                        );
+                        element.child(subgroup)
+                    })
+            }
+        }
+    }
+}
+
+impl ToastInput {
+    fn to_xml_element(&self) -> XmlElement {
+        let element = XmlElement::new("input")
+            .attr("id", &self.id)
+            .opt_attr("type", self.r#type.as_xml_value())
+            .opt_attr("placeHolderContent", self.placeholder.as_ref())
+            .opt_attr("title", self.title.as_ref())
+            .opt_attr("defaultInput", self.default_input.as_ref());
+
+        self.selection.iter().fold(element, |element, selection| {
+            element.child(
+                XmlElement::new("selection")
+                    .attr("id", &selection.id)
+                    .attr("content", &selection.content),
+            )
+        })
+    }
+}
+
+impl ToastAction {
+    fn to_xml_element(&self) -> XmlElement {
+        XmlElement::new("action")
+            .attr("content", &self.content)
+            .attr("arguments", &self.arguments)
+            .opt_attr(
+                "activationType",
+                self.activation_type.as_non_default_xml_value(),
+            )
+            .opt_attr(
+                "afterActivationBehavior",
+                self.after_activation_behavior
+                    .as_xml_value()
+                    .filter(|v| *v != "default"),
+            )
+            .opt_attr(
+                "placement",
+                self.placement.as_ref().and_then(|p| p.as_xml_value()),
+            )
+            .opt_attr("imageUri", self.image_uri.as_ref())
+            .opt_attr("hint-inputId", self.hint_inputid.as_ref())
+            .opt_attr(
+                "hint-buttonStyle",
+                self.hint_button_style
+                    .as_ref()
+                    .and_then(|s| s.as_xml_value()),
+            )
+            .opt_attr("hint-toolTip", self.hint_tooltip.as_ref())
+    }
+}
+
+impl Toast {
+    /// Serializes the toast as xml following the toast schema, so it can be shown by windows.
+    pub fn to_xml(&self) -> String {
+        let mut toast = XmlElement::new("toast")
+            .opt_attr("launch", self.launch.as_ref())
+            .opt_attr(
+                "activationType",
+                self.activation_type.as_non_default_xml_value(),
+            )
+            .opt_attr(
+                "duration",
+                self.duration.as_xml_value().filter(|v| *v != "short"),
+            )
+            .opt_attr(
+                "scenario",
+                self.scenario.as_ref().and_then(|s| s.as_xml_value()),
+            )
+            .opt_attr("displayTimestamp", self.display_timestamp.as_ref())
+            .opt_attr("useButtonStyle", self.use_button_style.then_some(true));
+
+        if let Some(header) = &self.header {
+            toast = toast.child(header.to_xml_element());
+        }
+
+        let visual = &self.visual;
+        let binding = &visual.binding;
+        let binding = binding.children.iter().fold(
+            XmlElement::new("binding")
+                .opt_attr("template", binding.template.as_xml_value())
+                .opt_attr("fallback", binding.fallback.as_ref())
+                .opt_attr("baseUri", binding.base_uri.as_ref())
+                .opt_attr("lang", binding.lang.as_ref())
+                .opt_attr("addImageQuery", binding.add_image_query.then_some(true)),
+            |binding, child| binding.child(child.to_xml_element()),
+        );
+        toast = toast.child(
+            XmlElement::new("visual")
+                .opt_attr("baseUri", visual.base_uri.as_ref())
+                .opt_attr("lang", visual.lang.as_ref())
+                .opt_attr("version", visual.version)
+                .opt_attr("addImageQuery", visual.add_image_query.then_some(true))
+                .child(binding),
+        );
+
+        if let Some(actions) = &self.actions {
+            toast = toast.child(actions.children.iter().fold(
+                XmlElement::new("actions"),
+                |actions, child| {
+                    actions.child(match child {
+                        ToastActionsChild::Input(input) => input.to_xml_element(),
+                        ToastActionsChild::Action(action) => action.to_xml_element(),
+                    })
+                },
+            ));
+        }
+
+        if let Some(audio) = &self.audio {
+            toast = toast.child(
+                XmlElement::new("audio")
+                    .opt_attr("src", audio.src.as_ref())
+                    .opt_attr("loop", audio.r#loop.then_some(true))
+                    .opt_attr("silent", audio.silent.then_some(true)),
+            );
+        }
+
+        let mut out = String::new();
+        toast.write(&mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use quick_xml::events::Event;
+
+    use super::*;
+
+    fn parse(xml: &str) -> Toast {
+        quick_xml::de::from_str(xml).unwrap_or_else(|e| panic!("invalid toast xml: {e}\n{xml}"))
+    }
+
+    /// Xml tree without the formatting of the document, used to compare documents.
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        name: String,
+        attributes: BTreeMap<String, String>,
+        text: String,
+        children: Vec<Node>,
+    }
+
+    /// Attributes set to the default value of the schema, equivalent to not being set.
+    const SCHEMA_DEFAULTS: [(&str, &str); 4] = [
+        ("activationType", "foreground"),
+        ("afterActivationBehavior", "default"),
+        ("duration", "short"),
+        ("addImageQuery", "false"),
+    ];
+
+    fn node(start: &quick_xml::events::BytesStart) -> Node {
+        let attributes = start
+            .attributes()
+            .map(|attr| {
+                let attr = attr.unwrap();
+                let name = String::from_utf8(attr.key.as_ref().to_vec()).unwrap();
+                (name, attr.unescape_value().unwrap().into_owned())
+            })
+            .filter(|(name, value)| !SCHEMA_DEFAULTS.contains(&(name.as_str(), value.as_str())))
+            .collect();
+        Node {
+            name: String::from_utf8(start.name().as_ref().to_vec()).unwrap(),
+            attributes,
+            text: String::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn dom(xml: &str) -> Node {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut stack = vec![Node {
+            name: String::new(),
+            attributes: BTreeMap::new(),
+            text: String::new(),
+            children: Vec::new(),
+        }];
+
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(start) => stack.push(node(&start)),
+                Event::Empty(start) => stack.last_mut().unwrap().children.push(node(&start)),
+                Event::End(_) => {
+                    let mut node = stack.pop().unwrap();
+                    node.text = node.text.trim().to_owned();
+                    stack.last_mut().unwrap().children.push(node);
+                }
+                Event::Text(text) => {
+                    stack.last_mut().unwrap().text += &text.decode().unwrap();
+                }
+                Event::GeneralRef(reference) => {
+                    let resolved = match reference.resolve_char_ref().unwrap() {
+                        Some(c) => c.to_string(),
+                        None => {
+                            let name = reference.decode().unwrap();
+                            quick_xml::escape::resolve_predefined_entity(&name)
+                                .unwrap_or_else(|| panic!("unknown entity {name}"))
+                                .to_owned()
+                        }
+                    };
+                    stack.last_mut().unwrap().text += &resolved;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        stack.pop().unwrap().children.pop().unwrap()
+    }
+
+    /// Payloads in the shape posted by real apps: chromium based browsers and the samples
+    /// of the toast documentation, which are used as is by many apps.
+    fn samples() -> std::io::Result<Vec<(std::path::PathBuf, String)>> {
+        let mut samples = Vec::new();
+        for entry in std::fs::read_dir("./mocks/toasts")? {
+            let path = entry?.path();
+            let xml = std::fs::read_to_string(&path)?;
+            samples.push((path, xml));
+        }
+        assert!(!samples.is_empty(), "no toast samples found");
+        Ok(samples)
+    }
+
+    #[test]
+    fn test_round_trip_samples() -> std::io::Result<()> {
+        for (path, xml) in samples()? {
+            let serialized = parse(&xml).to_xml();
+            assert_eq!(dom(&xml), dom(&serialized), "lossy round trip for {path:?}");
+            // serializing again should be stable
+            assert_eq!(
+                serialized,
+                parse(&serialized).to_xml(),
+                "unstable output for {path:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dom_detects_dropped_attributes() {
+        let original = dom(r#"<toast scenario="reminder"><visual/></toast>"#);
+        let dropped = dom(r#"<toast><visual/></toast>"#);
+        assert_ne!(original, dropped);
+        // defaults of the schema are the same as not setting them
+        assert_eq!(
+            dom(r#"<toast activationType="foreground" duration="short"/>"#),
+            dom("<toast/>")
+        );
+    }
+
+    #[test]
+    fn test_schema_attribute_values() {
+        let toast = parse(&std::fs::read_to_string("./mocks/toasts/incoming_call.xml").unwrap());
+        assert!(matches!(toast.scenario, Some(ToastScenario::IncomingCall)));
+        let xml = toast.to_xml();
+        assert!(xml.starts_with("<toast "));
+        assert!(xml.contains(r#"scenario="incomingCall""#));
+        assert!(xml.contains(r#"hint-crop="circle""#));
+        assert!(xml.contains(r#"hint-callScenarioCenterAlign="true""#));
+        assert!(xml.contains(r#"hint-buttonStyle="Success""#));
+        assert!(xml.contains(r#"activationType="background""#));
+
+        let toast = parse(&std::fs::read_to_string("./mocks/toasts/chromium_site.xml").unwrap());
+        let xml = toast.to_xml();
+        assert!(xml.contains(r#"placement="attribution""#));
+        assert!(xml.contains(r#"hint-crop="none""#));
+        assert!(xml.contains(r#"placement="contextMenu""#));
+        // not set on the payload, so windows defaults are used
+        assert!(!xml.contains("baseUri"));
+        assert!(!xml.contains("lang="));
+        assert!(!xml.contains("version="));
+    }
+
+    #[test]
+    fn test_escaping() {
+        let mut toast = Toast {
+            launch: Some("app://open?a=1&b=\"2\"".to_string()),
+            ..Default::default()
+        };
+        toast
+            .visual
+            .binding
+            .children
+            .push(ToastBindingChild::Text(ToastText {
+                id: Some(1),
+                content: "<b>Tom & Jerry</b>".to_string(),
+                ..Default::default()
+            }));
+
+        let xml = toast.to_xml();
+        assert!(xml.contains(r#"launch="app://open?a=1&amp;b=&quot;2&quot;""#));
+        assert!(xml.contains(r#"<text id="1">&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</text>"#));
+
+        let reparsed = parse(&xml);
+        assert_eq!(reparsed.launch, toast.launch);
+        assert_eq!(reparsed.texts(), vec!["<b>Tom & Jerry</b>"]);
+    }
+}
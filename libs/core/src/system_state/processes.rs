@@ -0,0 +1,334 @@
+use std::{collections::HashMap, path::PathBuf};
+
+/// Processes that can't be ended or deprioritized without breaking the session.
+const PROTECTED_PROCESSES: &[&str] = &[
+    "system",
+    "registry",
+    "memory compression",
+    "secure system",
+    "smss.exe",
+    "csrss.exe",
+    "wininit.exe",
+    "winlogon.exe",
+    "services.exe",
+    "lsass.exe",
+    "lsaiso.exe",
+    "svchost.exe",
+    "fontdrvhost.exe",
+    "dwm.exe",
+    "sihost.exe",
+    "ctfmon.exe",
+    "audiodg.exe",
+    "seelen-ui.exe",
+    "slu-service.exe",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct ProcessUsage {
+    pub id: u32,
+    pub parent_id: Option<u32>,
+    pub name: String,
+    pub path: Option<PathBuf>,
+    /// app user model id of packaged apps
+    pub umid: Option<String>,
+    /// usage of all the cores, between 0 and 100
+    pub cpu_usage: f32,
+    /// bytes of memory in use
+    pub memory: u64,
+    /// bytes read since the previous refresh
+    pub disk_read: u64,
+    /// bytes written since the previous refresh
+    pub disk_written: u64,
+    /// ending or deprioritizing this process is not allowed
+    pub protected: bool,
+}
+
+impl ProcessUsage {
+    pub fn is_protected(id: u32, name: &str) -> bool {
+        // 0: system idle process, 4: system
+        id == 0
+            || id == 4
+            || PROTECTED_PROCESSES
+                .iter()
+                .any(|p| p.eq_ignore_ascii_case(name))
+    }
+
+    /// Key used to group the processes of the same app.
+    fn app_key(&self) -> String {
+        match (&self.umid, &self.path) {
+            (Some(umid), _) => umid.clone(),
+            (None, Some(path)) => path.to_string_lossy().to_lowercase(),
+            (None, None) => self.name.to_lowercase(),
+        }
+    }
+}
+
+/// Resource usage of all the processes of an app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct AppResourceUsage {
+    pub name: String,
+    /// executable, used with the umid to resolve the icon from the icon pack
+    pub path: Option<PathBuf>,
+    pub umid: Option<String>,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub disk_read: u64,
+    pub disk_written: u64,
+    /// sorted by the same criteria as the apps
+    pub processes: Vec<ProcessUsage>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(repr(enum = name))]
+pub enum ProcessSortBy {
+    #[default]
+    Cpu,
+    Memory,
+    /// bytes read and written
+    Io,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export, optional_fields = nullable))]
+pub struct ProcessUsageQuery {
+    pub sort_by: ProcessSortBy,
+    /// max amount of apps to return
+    pub limit: Option<usize>,
+}
+
+impl ProcessSortBy {
+    fn key(&self, cpu_usage: f32, memory: u64, io: u64) -> f64 {
+        match self {
+            Self::Cpu => cpu_usage as f64,
+            Self::Memory => memory as f64,
+            Self::Io => io as f64,
+        }
+    }
+}
+
+impl ProcessUsageQuery {
+    /// Groups the processes by app and sorts them from the most to the least consuming.
+    pub fn apply(&self, processes: Vec<ProcessUsage>) -> Vec<AppResourceUsage> {
+        let mut apps: Vec<AppResourceUsage> = Vec::new();
+        let mut by_key: HashMap<String, usize> = HashMap::new();
+
+        for process in processes {
+            let idx = *by_key.entry(process.app_key()).or_insert_with(|| {
+                apps.push(AppResourceUsage {
+                    name: process.name.clone(),
+                    path: process.path.clone(),
+                    umid: process.umid.clone(),
+                    cpu_usage: 0.0,
+                    memory: 0,
+                    disk_read: 0,
+                    disk_written: 0,
+                    processes: Vec::new(),
+                });
+                apps.len() - 1
+            });
+
+            let app = &mut apps[idx];
+            app.cpu_usage += process.cpu_usage;
+            app.memory += process.memory;
+            app.disk_read += process.disk_read;
+            app.disk_written += process.disk_written;
+            app.processes.push(process);
+        }
+
+        let sort_by = self.sort_by;
+        for app in &mut apps {
+            app.processes.sort_by(|a, b| {
+                let a = sort_by.key(a.cpu_usage, a.memory, a.disk_read + a.disk_written);
+                let b = sort_by.key(b.cpu_usage, b.memory, b.disk_read + b.disk_written);
+                b.total_cmp(&a)
+            });
+        }
+        apps.sort_by(|a, b| {
+            let a = sort_by.key(a.cpu_usage, a.memory, a.disk_read + a.disk_written);
+            let b = sort_by.key(b.cpu_usage, b.memory, b.disk_read + b.disk_written);
+            b.total_cmp(&a)
+        });
+        apps.truncate(self.limit.unwrap_or(usize::MAX));
+        apps
+    }
+}
+
+/// Process id and parent id of a running process, used to build process trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessNode {
+    pub id: u32,
+    pub parent_id: Option<u32>,
+    /// seconds since the unix epoch
+    pub start_time: u64,
+}
+
+/// Ids of the process and all its descendants, children first so they can be ended in order.\
+/// The parent id of a process is not updated when the parent exits, so children started before
+/// their parent are skipped, as they belong to a previous process with the same id.
+pub fn process_tree(root: u32, processes: &[ProcessNode]) -> Vec<u32> {
+    let root_start_time = processes
+        .iter()
+        .find(|p| p.id == root)
+        .map_or(0, |p| p.start_time);
+
+    let mut tree = Vec::new();
+    let mut pending = vec![(root, root_start_time)];
+    while let Some((id, start_time)) = pending.pop() {
+        if tree.contains(&id) {
+            continue;
+        }
+        tree.push(id);
+        pending.extend(
+            processes
+                .iter()
+                .filter(|p| p.parent_id == Some(id) && p.start_time >= start_time)
+                .map(|p| (p.id, p.start_time)),
+        );
+    }
+    tree.reverse();
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(
+        id: u32,
+        name: &str,
+        umid: Option<&str>,
+        cpu_usage: f32,
+        memory: u64,
+    ) -> ProcessUsage {
+        ProcessUsage {
+            id,
+            parent_id: None,
+            name: name.to_string(),
+            path: Some(PathBuf::from(format!("C:\\Apps\\{name}"))),
+            umid: umid.map(str::to_string),
+            cpu_usage,
+            memory,
+            disk_read: 0,
+            disk_written: id as u64,
+            protected: false,
+        }
+    }
+
+    fn processes() -> Vec<ProcessUsage> {
+        vec![
+            process(1, "chrome.exe", None, 2.0, 500),
+            process(2, "code.exe", None, 10.0, 300),
+            process(3, "chrome.exe", None, 5.0, 400),
+            process(
+                4,
+                "Terminal.exe",
+                Some("Microsoft.WindowsTerminal!App"),
+                1.0,
+                50,
+            ),
+            process(
+                5,
+                "Terminal.exe",
+                Some("Microsoft.WindowsTerminal!App"),
+                0.5,
+                10,
+            ),
+        ]
+    }
+
+    fn summary(apps: &[AppResourceUsage]) -> Vec<(String, Vec<u32>)> {
+        apps.iter()
+            .map(|a| (a.name.clone(), a.processes.iter().map(|p| p.id).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_grouping_and_sorting() {
+        let query = |sort_by, limit| ProcessUsageQuery { sort_by, limit }.apply(processes());
+
+        let by_memory = query(ProcessSortBy::Memory, None);
+        assert_eq!(
+            summary(&by_memory),
+            vec![
+                ("chrome.exe".to_string(), vec![1, 3]),
+                ("code.exe".to_string(), vec![2]),
+                ("Terminal.exe".to_string(), vec![4, 5]),
+            ]
+        );
+        assert_eq!(by_memory[0].memory, 900);
+        assert_eq!(by_memory[2].cpu_usage, 1.5);
+
+        let by_cpu = query(ProcessSortBy::Cpu, Some(2));
+        assert_eq!(
+            summary(&by_cpu),
+            vec![
+                ("code.exe".to_string(), vec![2]),
+                ("chrome.exe".to_string(), vec![3, 1]),
+            ]
+        );
+
+        let by_io = query(ProcessSortBy::Io, Some(1));
+        assert_eq!(
+            summary(&by_io),
+            vec![("Terminal.exe".to_string(), vec![5, 4])]
+        );
+    }
+
+    #[test]
+    fn test_protected_processes() {
+        assert!(ProcessUsage::is_protected(4, "whatever"));
+        assert!(ProcessUsage::is_protected(700, "CSRSS.EXE"));
+        assert!(!ProcessUsage::is_protected(700, "chrome.exe"));
+    }
+
+    fn node(id: u32, parent_id: Option<u32>, start_time: u64) -> ProcessNode {
+        ProcessNode {
+            id,
+            parent_id,
+            start_time,
+        }
+    }
+
+    #[test]
+    fn test_process_tree() {
+        let processes = [
+            node(1, None, 10),
+            node(2, Some(1), 10),
+            node(3, Some(2), 11),
+            node(4, Some(1), 12),
+            node(5, None, 10),
+            node(6, Some(6), 10),
+        ];
+        let tree = process_tree(1, &processes);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.last(), Some(&1));
+        // children are always before their parents
+        let pos = |id| tree.iter().position(|p| *p == id).unwrap();
+        assert!(pos(3) < pos(2));
+        assert!(pos(2) < pos(1) && pos(4) < pos(1));
+
+        assert_eq!(process_tree(6, &processes), vec![6]);
+        assert_eq!(process_tree(9, &processes), vec![9]);
+    }
+
+    #[test]
+    fn test_process_tree_skips_reused_parent_ids() {
+        // 1 was started after its original parent exited and its id was reused,
+        // so 2 and 3 are children of the previous process with id 1
+        let processes = [
+            node(1, None, 20),
+            node(2, Some(1), 5),
+            node(3, Some(2), 6),
+            node(4, Some(1), 21),
+            node(5, Some(4), 19),
+        ];
+        assert_eq!(process_tree(1, &processes), vec![4, 1]);
+        assert_eq!(process_tree(2, &processes), vec![3, 2]);
+    }
+}
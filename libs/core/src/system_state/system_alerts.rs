@@ -0,0 +1,322 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use uuid::Uuid;
+
+use crate::state::{SystemAlertComparison, SystemAlertMetric, SystemAlertRule};
+
+use super::{Battery, Core, Disk, Memory, SystemMetric};
+
+/// Current values used to evaluate the alert rules.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAlertInput {
+    pub cpu_usage: f64,
+    pub memory_usage: f64,
+    /// available bytes by mount point
+    pub disks_available: Vec<(PathBuf, u64)>,
+    pub battery_percentage: Option<f64>,
+    pub battery_time_to_empty: Option<f64>,
+}
+
+impl SystemAlertInput {
+    pub fn new(cores: &[Core], memory: &Memory, disks: &[Disk], batteries: &[Battery]) -> Self {
+        let memory_usage = if memory.total == 0 {
+            0.0
+        } else {
+            memory.total.saturating_sub(memory.free) as f64 * 100.0 / memory.total as f64
+        };
+        let battery = batteries.first();
+        Self {
+            cpu_usage: SystemMetric::cpu_usage(cores),
+            memory_usage,
+            disks_available: disks
+                .iter()
+                .map(|d| (d.mount_point.clone(), d.available_space))
+                .collect(),
+            battery_percentage: battery.map(|b| b.percentage as f64),
+            battery_time_to_empty: battery.and_then(|b| b.time_to_empty).map(|t| t as f64),
+        }
+    }
+
+    /// None if the value is not available, e.g. there is no battery.
+    pub fn value(&self, metric: &SystemAlertMetric) -> Option<f64> {
+        match metric {
+            SystemAlertMetric::CpuUsage => Some(self.cpu_usage),
+            SystemAlertMetric::MemoryUsage => Some(self.memory_usage),
+            SystemAlertMetric::DiskAvailableSpace { mount_point } => self
+                .disks_available
+                .iter()
+                .filter(|(mount, _)| mount_point.as_ref().is_none_or(|m| m == mount))
+                .map(|(_, available)| *available)
+                .min()
+                .map(|available| available as f64),
+            SystemAlertMetric::BatteryPercentage => self.battery_percentage,
+            SystemAlertMetric::BatteryTimeToEmpty => self.battery_time_to_empty,
+        }
+    }
+}
+
+impl SystemAlertMetric {
+    /// Human readable value with its unit, e.g. `93%` or `12.5 GB`.
+    pub fn format_value(&self, value: f64) -> String {
+        match self {
+            Self::CpuUsage | Self::MemoryUsage | Self::BatteryPercentage => {
+                format!("{value:.0}%")
+            }
+            Self::DiskAvailableSpace { .. } => {
+                format!("{:.1} GB", value / (1024.0 * 1024.0 * 1024.0))
+            }
+            Self::BatteryTimeToEmpty => format!("{:.0} min", value / 60.0),
+        }
+    }
+}
+
+impl SystemAlertRule {
+    pub fn is_crossed(&self, value: f64) -> bool {
+        match self.comparison {
+            SystemAlertComparison::Above => value > self.threshold,
+            SystemAlertComparison::Below => value < self.threshold,
+        }
+    }
+}
+
+/// Alert of a rule whose threshold is currently crossed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "gen-binds", ts(export))]
+pub struct SystemAlert {
+    pub rule_id: Uuid,
+    pub name: String,
+    pub metric: SystemAlertMetric,
+    /// last value of the metric
+    pub value: f64,
+    /// unix timestamp in milliseconds of when the threshold was crossed
+    pub since: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AlertRuleState {
+    crossed_since: Option<i64>,
+    active: bool,
+    last_fired: Option<i64>,
+}
+
+/// Keeps the state of the alert rules between evaluations.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAlertsTracker {
+    states: HashMap<Uuid, AlertRuleState>,
+    active: Vec<SystemAlert>,
+}
+
+impl SystemAlertsTracker {
+    /// Evaluates the rules against the input at the unix timestamp in milliseconds.
+    /// Returns the alerts that should be shown to the user now.
+    pub fn evaluate(
+        &mut self,
+        rules: &[SystemAlertRule],
+        input: &SystemAlertInput,
+        now: i64,
+    ) -> Vec<SystemAlert> {
+        // forget removed rules
+        self.states
+            .retain(|id, _| rules.iter().any(|r| r.enabled && &r.id == id));
+
+        let mut fired = Vec::new();
+        let mut active = Vec::new();
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let state = self.states.entry(rule.id).or_default();
+            let Some(value) = input.value(&rule.metric).filter(|v| rule.is_crossed(*v)) else {
+                state.crossed_since = None;
+                state.active = false;
+                continue;
+            };
+
+            let since = *state.crossed_since.get_or_insert(now);
+            if now - since < rule.duration_secs as i64 * 1000 {
+                continue;
+            }
+
+            let alert = SystemAlert {
+                rule_id: rule.id,
+                name: rule.name.clone(),
+                metric: rule.metric.clone(),
+                value,
+                since,
+            };
+
+            let cooldown = rule.cooldown_secs as i64 * 1000;
+            let should_fire = match state.last_fired {
+                None => true,
+                Some(_) if state.active && cooldown == 0 => false,
+                Some(last) => now - last >= cooldown,
+            };
+            if should_fire {
+                state.last_fired = Some(now);
+                fired.push(alert.clone());
+            }
+            state.active = true;
+            active.push(alert);
+        }
+
+        self.active = active;
+        fired
+    }
+
+    /// Alerts of the rules whose threshold is crossed for longer than their duration.
+    pub fn active(&self) -> &[SystemAlert] {
+        &self.active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::SystemAlertAction;
+
+    use super::*;
+
+    const SEC: i64 = 1000;
+
+    fn rule(
+        metric: SystemAlertMetric,
+        comparison: SystemAlertComparison,
+        threshold: f64,
+    ) -> SystemAlertRule {
+        SystemAlertRule {
+            id: Uuid::new_v4(),
+            name: "test".to_string(),
+            enabled: true,
+            metric,
+            comparison,
+            threshold,
+            duration_secs: 0,
+            cooldown_secs: 0,
+            action: SystemAlertAction::Notification,
+        }
+    }
+
+    fn fired_at(
+        tracker: &mut SystemAlertsTracker,
+        rules: &[SystemAlertRule],
+        inputs: &[(i64, SystemAlertInput)],
+    ) -> Vec<i64> {
+        let mut fired = Vec::new();
+        for (secs, input) in inputs {
+            if !tracker.evaluate(rules, input, secs * SEC).is_empty() {
+                fired.push(*secs);
+            }
+        }
+        fired
+    }
+
+    fn cpu(usage: f64) -> SystemAlertInput {
+        SystemAlertInput {
+            cpu_usage: usage,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_input_values() {
+        let input = SystemAlertInput {
+            disks_available: vec![("C:\\".into(), 500), ("D:\\".into(), 100)],
+            battery_percentage: Some(15.0),
+            ..Default::default()
+        };
+        let disk = |mount_point: Option<&str>| {
+            input.value(&SystemAlertMetric::DiskAvailableSpace {
+                mount_point: mount_point.map(PathBuf::from),
+            })
+        };
+        assert_eq!(disk(None), Some(100.0));
+        assert_eq!(disk(Some("C:\\")), Some(500.0));
+        assert_eq!(disk(Some("E:\\")), None);
+        assert_eq!(
+            input.value(&SystemAlertMetric::BatteryPercentage),
+            Some(15.0)
+        );
+        assert_eq!(input.value(&SystemAlertMetric::BatteryTimeToEmpty), None);
+
+        assert_eq!(SystemAlertMetric::MemoryUsage.format_value(92.6), "93%");
+        assert_eq!(
+            SystemAlertMetric::DiskAvailableSpace { mount_point: None }
+                .format_value(5.0 * 1024.0 * 1024.0 * 1024.0),
+            "5.0 GB"
+        );
+        assert_eq!(
+            SystemAlertMetric::BatteryTimeToEmpty.format_value(900.0),
+            "15 min"
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        let mut rule = rule(
+            SystemAlertMetric::CpuUsage,
+            SystemAlertComparison::Above,
+            90.0,
+        );
+        rule.duration_secs = 5;
+        let rules = [rule];
+
+        let mut tracker = SystemAlertsTracker::default();
+        let fired = fired_at(
+            &mut tracker,
+            &rules,
+            &[
+                (0, cpu(95.0)),
+                (3, cpu(50.0)), // resets the duration
+                (4, cpu(95.0)),
+                (8, cpu(95.0)),
+                (9, cpu(99.0)),
+                (10, cpu(99.0)),
+            ],
+        );
+        assert_eq!(fired, vec![9]);
+        assert_eq!(tracker.active().len(), 1);
+        assert_eq!(tracker.active()[0].since, 4 * SEC);
+
+        tracker.evaluate(&rules, &cpu(10.0), 11 * SEC);
+        assert!(tracker.active().is_empty());
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let mut low_battery = rule(
+            SystemAlertMetric::BatteryPercentage,
+            SystemAlertComparison::Below,
+            20.0,
+        );
+        let battery = |percentage: f64| SystemAlertInput {
+            battery_percentage: Some(percentage),
+            ..Default::default()
+        };
+        let inputs = [
+            (0, battery(19.0)),
+            (30, battery(18.0)),
+            (60, battery(17.0)),
+            (70, battery(25.0)),
+            (80, battery(15.0)),
+            (130, battery(14.0)),
+        ];
+
+        // without cooldown it only fires again after going back over the threshold
+        let fired = fired_at(
+            &mut SystemAlertsTracker::default(),
+            &[low_battery.clone()],
+            &inputs,
+        );
+        assert_eq!(fired, vec![0, 80]);
+
+        // with cooldown it reminds while crossed, but never before the cooldown
+        low_battery.cooldown_secs = 60;
+        let fired = fired_at(
+            &mut SystemAlertsTracker::default(),
+            &[low_battery.clone()],
+            &inputs,
+        );
+        assert_eq!(fired, vec![0, 60, 130]);
+
+        low_battery.enabled = false;
+        let fired = fired_at(&mut SystemAlertsTracker::default(), &[low_battery], &inputs);
+        assert!(fired.is_empty());
+    }
+}
@@ -14,6 +14,21 @@ pub struct UIColors {
     pub complement: Option<String>,
 }
 
+impl UIColors {
+    /// Windows uses a dark background color when the dark theme is enabled.
+    pub fn is_dark_mode(&self) -> bool {
+        let hex = self.background.trim_start_matches('#');
+        let channel = |idx: usize| {
+            hex.get(idx..idx + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+                .unwrap_or(0xFF) as f32
+        };
+        // perceived brightness
+        let brightness = 0.299 * channel(0) + 0.587 * channel(2) + 0.114 * channel(4);
+        brightness < 128.0
+    }
+}
+
 /// since v2.2.0 this should be used to handle every used color in the app
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS)]
 #[cfg_attr(feature = "gen-binds", ts(export))]
@@ -88,10 +88,15 @@ impl AppIpc {
 
     /// Sends a message to the current session asynchronously
     pub async fn send(message: AppMessage) -> Result<()> {
+        Self::request(message).await.map(|_| ())
+    }
+
+    /// Same as [`AppIpc::send`] but returning the output of the message, if any
+    pub async fn request(message: AppMessage) -> Result<Option<String>> {
         let stream = AsyncDuplexPipeStream::connect_by_path(Self::path()).await?;
         send_to_ipc_stream(&stream, &message.to_bytes()?)
             .await?
-            .ok()
+            .output()
     }
 
     /// Sends a message to the current session synchronously
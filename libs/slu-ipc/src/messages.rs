@@ -8,13 +8,20 @@ use crate::error::{Error, Result};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IpcResponse {
     Success,
+    /// Success with output to be shown to the client, ex: json of a cli command
+    Output(String),
     Err(String),
 }
 
 impl IpcResponse {
     pub fn ok(self) -> Result<()> {
+        self.output().map(|_| ())
+    }
+
+    pub fn output(self) -> Result<Option<String>> {
         match self {
-            IpcResponse::Success => Ok(()),
+            IpcResponse::Success => Ok(None),
+            IpcResponse::Output(output) => Ok(Some(output)),
             IpcResponse::Err(err) => Err(Error::IpcResponseError(err)),
         }
     }
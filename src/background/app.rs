@@ -18,12 +18,14 @@ use crate::{
     migrations::RestorationAndMigration,
     modules::{
         monitors::{MonitorManager, MonitorManagerEvent},
+        system::SystemInfo,
         system_settings::application::{SystemSettings, SystemSettingsEvent},
     },
     state::application::{FullState, FULL_STATE},
     trace_lock,
     utils::discord::start_discord_rpc,
     widgets::{
+        toolbar::renderer::ToolbarRenderer,
         wallpaper_manager::SeelenWall,
         weg::{weg_items_impl::SEELEN_WEG_STATE, SeelenWeg},
     },
@@ -51,6 +53,8 @@ where
     S: serde::Serialize + Clone,
 {
     // log::trace!("Emitting {event} to webviews");
+    // background evaluated toolbar items use the same data as the webviews
+    ToolbarRenderer::on_event(event);
     if !IS_INTERACTIVE_SESSION.load(Ordering::Acquire) {
         // log::debug!("Skipping event {event} because session is not active");
         return;
@@ -162,6 +166,8 @@ impl Seelen {
         register_win_hook()?;
         MonitorManager::subscribe(Self::on_monitor_event);
         SystemSettings::subscribe(Self::on_system_settings_change);
+        // starts sampling, so the metrics history is ready when widgets request it
+        SystemInfo::instance();
 
         start_discord_rpc()?;
         ServicePipe::request(SvcAction::SetSettings(Box::new(state.settings.clone())))?;
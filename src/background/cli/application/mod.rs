@@ -14,6 +14,7 @@ use windows::Win32::System::Console::{AttachConsole, GetConsoleWindow, ATTACH_PA
 use crate::{
     cli::application::{art::ArtCli, uri::process_uri},
     error::Result,
+    modules::notifications::cli::NotificationsCli,
     resources::cli::ResourceManagerCli,
     virtual_desktops::cli::VirtualDesktopCli,
     widgets::{
@@ -83,6 +84,7 @@ pub enum AppCliCommand {
     Win32(Win32Cli),
     Art(ArtCli),
     TaskSwitcher(TaskSwitcherClient),
+    Notifications(NotificationsCli),
 }
 
 impl SluCliCommand for AppCliCommand {
@@ -185,6 +187,11 @@ fn determine_routing_strategy(cli: &AppCli) -> CliRoutingStrategy {
 }
 
 impl AppCli {
+    /// Whether a subcommand was parsed, uris are not considered commands.
+    pub fn has_command(&self) -> bool {
+        self.command.is_some()
+    }
+
     /// Processes commands that execute directly in the console (async)
     pub async fn process_direct(self) -> Result<()> {
         match self.command {
@@ -193,15 +200,16 @@ impl AppCli {
         }
     }
 
-    /// intended to be called on the main instance
-    pub fn process(self) -> Result<()> {
+    /// intended to be called on the main instance, returns the output to be shown on the client console
+    pub fn process(self) -> Result<Option<String>> {
         if let Some(uri) = self.uri {
-            return process_uri(&uri);
+            process_uri(&uri)?;
+            return Ok(None);
         }
 
         match self.command {
             Some(cmd) => cmd.process(),
-            None => Ok(()),
+            None => Ok(None),
         }
     }
 
@@ -226,9 +234,12 @@ impl AppCli {
             println!("Sending {args:#?}");
         }
 
-        AppIpc::send(AppMessage::Cli(args))
+        let output = AppIpc::request(AppMessage::Cli(args))
             .await
             .map_err(|_| "Can't stablish connection, ensure Seelen UI is running.")?;
+        if let Some(output) = output {
+            println!("{output}");
+        }
         Ok(())
     }
 }
@@ -253,7 +264,7 @@ impl AppCliCommand {
         Ok(())
     }
 
-    pub fn process(self) -> Result<()> {
+    pub fn process(self) -> Result<Option<String>> {
         match self {
             AppCliCommand::Settings => {
                 show_settings()?;
@@ -271,7 +282,7 @@ impl AppCliCommand {
                 command.process()?;
             }
             AppCliCommand::Widget(command) => {
-                command.run()?;
+                return command.run();
             }
             AppCliCommand::Resource(command) => {
                 command.process()?;
@@ -282,11 +293,14 @@ impl AppCliCommand {
             AppCliCommand::TaskSwitcher(command) => {
                 command.process()?;
             }
+            AppCliCommand::Notifications(command) => {
+                command.process()?;
+            }
             _ => {
                 return Err("Command does not support instance execution".into());
             }
         }
-        Ok(())
+        Ok(None)
     }
 }
 
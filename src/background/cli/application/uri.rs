@@ -199,6 +199,8 @@ fn update_popup_to_added_resource(popup_id: &Uuid, resource: &Resource) -> Resul
                             id: Uuid::new_v4(),
                             name: display_name.clone(),
                             wallpapers: vec![used_id.clone().into()],
+                            schedule: None,
+                            rule: None,
                         };
                         state.settings.by_widget.wall.default_collection = Some(collection.id);
                         state.settings.wallpaper_collections.push(collection);
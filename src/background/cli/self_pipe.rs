@@ -8,9 +8,9 @@ use crate::{cli::application::AppCli, error::Result, modules::system_tray::Syste
 
 pub struct SelfPipe;
 impl SelfPipe {
-    fn _handle_cli_message(mut argv: Vec<String>) -> Result<()> {
+    fn _handle_cli_message(mut argv: Vec<String>) -> Result<Option<String>> {
         if argv.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         let first = argv.first().unwrap();
@@ -19,21 +19,24 @@ impl SelfPipe {
         }
 
         if let Ok(cli) = AppCli::try_parse_from(argv) {
-            if let Err(err) = cli.process() {
-                log::error!("Failed to process command: {err}");
-                return Err(err);
+            match cli.process() {
+                Ok(output) => return Ok(output),
+                Err(err) => {
+                    log::error!("Failed to process command: {err}");
+                    return Err(err);
+                }
             }
         }
-        Ok(())
+        Ok(None)
     }
 
     fn handle_message(message: AppMessage) -> IpcResponse {
         match message {
-            AppMessage::Cli(argv) => {
-                if let Err(err) = Self::_handle_cli_message(argv) {
-                    return IpcResponse::Err(err.to_string());
-                }
-            }
+            AppMessage::Cli(argv) => match Self::_handle_cli_message(argv) {
+                Ok(Some(output)) => return IpcResponse::Output(output),
+                Ok(None) => {}
+                Err(err) => return IpcResponse::Err(err.to_string()),
+            },
             AppMessage::TrayChanged(event) => {
                 SystemTrayManager::handle_tray_event(event);
             }
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, os::windows::process::CommandExt, path::PathBuf};
+use std::{
+    collections::HashMap,
+    os::windows::process::CommandExt,
+    path::{Path, PathBuf},
+};
 
 use owo_colors::OwoColorize;
 use seelen_core::{
@@ -17,6 +21,7 @@ use windows::Win32::System::Threading::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_WIND
 use crate::{
     app::{get_app_handle, Seelen},
     error::Result,
+    modules::start::application::StartMenuManager,
     utils::{
         self,
         constants::SEELEN_COMMON,
@@ -59,7 +64,9 @@ async fn run(
     elevated: bool,
 ) -> Result<()> {
     let args = args.map(|args| args.to_string());
-    WindowsApi::execute(program, args, working_dir, elevated)
+    WindowsApi::execute(program.clone(), args, working_dir, elevated)?;
+    StartMenuManager::instance().record_launch(Path::new(&program));
+    Ok(())
 }
 
 #[tauri::command(async)]
@@ -256,11 +263,13 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
     use crate::state::infrastructure::*;
     use crate::virtual_desktops::handlers::*;
     use crate::widgets::popups::handlers::*;
+    use crate::widgets::toolbar::handler::*;
     use crate::widgets::weg::handler::*;
     use crate::widgets::window_manager::handler::*;
     use crate::widgets::*;
 
     use crate::modules::apps::infrastructure::*;
+    use crate::modules::launcher::infrastructure::*;
     use crate::modules::media::devices::infrastructure::*;
     use crate::modules::media::players::infrastructure::*;
     use crate::modules::monitors::brightness::infrastructure::*;
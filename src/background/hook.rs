@@ -24,6 +24,10 @@ use crate::{
     app::{emit_to_webviews, Seelen, SEELEN},
     error::{Result, ResultLogExt},
     event_manager, log_error,
+    modules::{
+        media::devices::profiles::on_foreground_app_changed,
+        notifications::focus::set_foreground_fullscreen,
+    },
     state::application::FULL_STATE,
     trace_lock,
     utils::spawn_named_thread,
@@ -156,10 +160,10 @@ impl HookManager {
             );
 
             if shoup_update_focused && origin.is_focused() {
-                emit_to_webviews(
-                    SeelenEvent::GlobalFocusChanged,
-                    origin.as_focused_app_information(),
-                );
+                let focused = origin.as_focused_app_information();
+                set_foreground_fullscreen(focused.is_fullscreened);
+                on_foreground_app_changed(focused.exe.as_deref());
+                emit_to_webviews(SeelenEvent::GlobalFocusChanged, focused);
             }
         }
 
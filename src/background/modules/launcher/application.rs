@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use clap::Parser;
+use seelen_core::system_state::{LauncherAction, LauncherResult};
+
+use crate::{
+    cli::application::AppCli, error::Result, exposed::open_file,
+    modules::start::application::StartMenuManager, windows_api::WindowsApi,
+};
+
+use super::providers::{
+    AppsProvider, CalculatorProvider, CliCommandsProvider, FilesProvider, KeywordsProvider,
+    LauncherProvider,
+};
+
+/// Providers in priority order, used to resolve ties between results with the same score.
+static PROVIDERS: &[&dyn LauncherProvider] = &[
+    &KeywordsProvider,
+    &CalculatorProvider,
+    &AppsProvider,
+    &CliCommandsProvider,
+    &FilesProvider,
+];
+
+pub fn query(query: &str, limit: usize) -> Vec<LauncherResult> {
+    let results = PROVIDERS
+        .iter()
+        .flat_map(|provider| provider.query(query))
+        .collect();
+    LauncherResult::merge(results, limit)
+}
+
+pub async fn execute(action: LauncherAction) -> Result<()> {
+    match action {
+        LauncherAction::Run { program, args } => {
+            WindowsApi::execute(program.clone(), args, None, false)?;
+            StartMenuManager::instance().record_launch(Path::new(&program));
+        }
+        LauncherAction::Open { path } => {
+            open_file(path)?;
+        }
+        LauncherAction::Url { url } => {
+            open_file(url)?;
+        }
+        LauncherAction::Copy { text } => {
+            WindowsApi::set_clipboard_text(&text)?;
+        }
+        LauncherAction::Cli { args } => {
+            let argv = std::iter::once("seelen-ui".to_string()).chain(args);
+            let cli = AppCli::try_parse_from(argv).map_err(|e| format!("Invalid command: {e}"))?;
+            cli.process()?;
+        }
+    }
+    Ok(())
+}
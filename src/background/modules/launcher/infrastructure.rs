@@ -0,0 +1,15 @@
+use seelen_core::system_state::{LauncherAction, LauncherResult};
+
+use crate::error::Result;
+
+use super::application;
+
+#[tauri::command(async)]
+pub fn launcher_query(query: String, limit: Option<usize>) -> Vec<LauncherResult> {
+    application::query(&query, limit.unwrap_or(50))
+}
+
+#[tauri::command(async)]
+pub async fn launcher_execute(action: LauncherAction) -> Result<()> {
+    application::execute(action).await
+}
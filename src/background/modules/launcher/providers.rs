@@ -0,0 +1,342 @@
+use std::sync::LazyLock;
+
+use clap::{CommandFactory, Parser};
+use evalexpr::Value;
+use seelen_core::{
+    state::value::{KnownPlugin, PluginValue},
+    system_state::{fuzzy_match_score, LauncherAction, LauncherKeywordProvider, LauncherResult},
+};
+
+use crate::{
+    cli::application::AppCli, modules::start::application::StartMenuManager,
+    modules::user::application::UserManager, resources::RESOURCES, state::application::FULL_STATE,
+    trace_lock,
+};
+
+/// Source of launcher results.
+pub trait LauncherProvider: Send + Sync {
+    /// id used as `provider` on the results
+    fn id(&self) -> &'static str;
+    /// Results for the query, unsorted and with scores between 0 and 1.
+    fn query(&self, query: &str) -> Vec<LauncherResult>;
+}
+
+const RESULTS_PER_PROVIDER: usize = 10;
+
+/// Start menu items, using the same index as the start menu search.
+pub struct AppsProvider;
+
+impl LauncherProvider for AppsProvider {
+    fn id(&self) -> &'static str {
+        "apps"
+    }
+
+    fn query(&self, query: &str) -> Vec<LauncherResult> {
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+        StartMenuManager::instance()
+            .search_scored(query, RESULTS_PER_PROVIDER)
+            .into_iter()
+            .map(|(item, score)| {
+                let program = match &item.umid {
+                    Some(umid) => format!("shell:AppsFolder\\{umid}"),
+                    None => item.path.to_string_lossy().to_string(),
+                };
+                LauncherResult {
+                    provider: self.id().to_string(),
+                    title: item.display_name.clone(),
+                    subtitle: None,
+                    icon: None,
+                    score: score.min(1.0),
+                    action: LauncherAction::Run {
+                        program,
+                        args: None,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Files on the user folders (desktop, downloads, documents, etc).
+pub struct FilesProvider;
+
+impl FilesProvider {
+    /// files should not outrank apps with the same match quality
+    const WEIGHT: f64 = 0.8;
+}
+
+impl LauncherProvider for FilesProvider {
+    fn id(&self) -> &'static str {
+        "files"
+    }
+
+    fn query(&self, query: &str) -> Vec<LauncherResult> {
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let manager = trace_lock!(UserManager::instance());
+        let mut results: Vec<LauncherResult> = manager
+            .folders
+            .values()
+            .flat_map(|folder| folder.content.iter())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                let score = fuzzy_match_score(query, &name) * Self::WEIGHT;
+                if score <= 0.0 {
+                    return None;
+                }
+                Some(LauncherResult {
+                    provider: self.id().to_string(),
+                    title: name,
+                    subtitle: path.parent().map(|p| p.to_string_lossy().to_string()),
+                    icon: None,
+                    score,
+                    action: LauncherAction::Open {
+                        path: path.to_string_lossy().to_string(),
+                    },
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(RESULTS_PER_PROVIDER);
+        results
+    }
+}
+
+/// Math expressions, e.g: `2 * (3 + 4)` or `=math::sqrt(16)`.
+pub struct CalculatorProvider;
+
+impl CalculatorProvider {
+    /// Writes the integer literals as floats, evalexpr does integer division on integers
+    /// but users expect `7/2` to be `3.5`.
+    fn with_float_literals(expression: &str) -> String {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+        let mut out = String::with_capacity(expression.len());
+        let mut chars = expression.chars().peekable();
+        let mut in_string = false;
+
+        while let Some(c) = chars.next() {
+            let previous = out.chars().next_back();
+            out.push(c);
+            if c == '"' {
+                in_string = !in_string;
+            }
+            // digits inside strings, identifiers (e.g: `math::log2`) or floats are kept
+            if !c.is_ascii_digit() || in_string || previous.is_some_and(is_word) {
+                continue;
+            }
+
+            while let Some(&next) = chars.peek().filter(|next| next.is_ascii_digit()) {
+                out.push(next);
+                chars.next();
+            }
+            if !chars.peek().is_some_and(|next| is_word(*next)) {
+                out.push_str(".0");
+            }
+        }
+        out
+    }
+
+    fn evaluate(expression: &str) -> Option<String> {
+        match evalexpr::eval(&Self::with_float_literals(expression)) {
+            Ok(Value::Int(value)) => Some(value.to_string()),
+            Ok(Value::Float(value)) if value.is_finite() => Some(Self::format_float(value)),
+            _ => None,
+        }
+    }
+
+    /// Whole results are shown as integers.
+    fn format_float(value: f64) -> String {
+        let text = format!("{value:.10}");
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+impl LauncherProvider for CalculatorProvider {
+    fn id(&self) -> &'static str {
+        "calculator"
+    }
+
+    fn query(&self, query: &str) -> Vec<LauncherResult> {
+        let expression = query.trim().trim_start_matches('=').trim();
+        if !expression.chars().any(|c| c.is_ascii_digit()) {
+            return Vec::new();
+        }
+
+        let Some(result) = Self::evaluate(expression) else {
+            return Vec::new();
+        };
+        // a plain number is not a calculation
+        if result == expression {
+            return Vec::new();
+        }
+
+        vec![LauncherResult {
+            provider: self.id().to_string(),
+            title: result.clone(),
+            subtitle: Some(expression.to_string()),
+            icon: Some("PiCalculatorDuotone".to_string()),
+            score: 1.0,
+            action: LauncherAction::Copy { text: result },
+        }]
+    }
+}
+
+struct CliCommandEntry {
+    args: Vec<String>,
+    about: Option<String>,
+}
+
+/// Seelen UI cli commands that can be executed without arguments, e.g: `settings`.
+static CLI_COMMANDS: LazyLock<Vec<CliCommandEntry>> = LazyLock::new(|| {
+    fn collect(command: &clap::Command, path: Vec<String>, entries: &mut Vec<CliCommandEntry>) {
+        for sub in command.get_subcommands() {
+            let mut sub_path = path.clone();
+            sub_path.push(sub.get_name().to_string());
+
+            if sub.has_subcommands() {
+                collect(sub, sub_path, entries);
+                continue;
+            }
+            if sub.get_arguments().any(|arg| arg.is_required_set()) {
+                continue;
+            }
+            entries.push(CliCommandEntry {
+                args: sub_path,
+                about: sub.get_about().map(|about| about.to_string()),
+            });
+        }
+    }
+
+    let mut entries = Vec::new();
+    collect(&AppCli::command(), Vec::new(), &mut entries);
+    entries
+});
+
+pub struct CliCommandsProvider;
+
+impl CliCommandsProvider {
+    /// commands are rarely the target of a search
+    const WEIGHT: f64 = 0.7;
+
+    fn result(&self, args: Vec<String>, about: Option<String>, score: f64) -> LauncherResult {
+        LauncherResult {
+            provider: self.id().to_string(),
+            title: format!("seelen-ui {}", args.join(" ")),
+            subtitle: about,
+            icon: Some("PiTerminalWindowDuotone".to_string()),
+            score,
+            action: LauncherAction::Cli { args },
+        }
+    }
+}
+
+impl LauncherProvider for CliCommandsProvider {
+    fn id(&self) -> &'static str {
+        "commands"
+    }
+
+    fn query(&self, query: &str) -> Vec<LauncherResult> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results = Vec::new();
+        // full commands with arguments, e.g: `widget trigger @seelen/weg`
+        let args: Vec<String> = query.split_whitespace().map(String::from).collect();
+        let parsed =
+            AppCli::try_parse_from(std::iter::once("seelen-ui".to_string()).chain(args.clone()));
+        if parsed.is_ok_and(|cli| cli.has_command()) {
+            results.push(self.result(args, None, 1.0));
+        }
+
+        for entry in CLI_COMMANDS.iter() {
+            let score = fuzzy_match_score(query, &entry.args.join(" ")) * Self::WEIGHT;
+            if score > 0.0 {
+                results.push(self.result(entry.args.clone(), entry.about.clone(), score));
+            }
+        }
+        results
+    }
+}
+
+/// Keywords from the user settings and from the launcher plugins.
+pub struct KeywordsProvider;
+
+impl LauncherProvider for KeywordsProvider {
+    fn id(&self) -> &'static str {
+        "web-search"
+    }
+
+    fn query(&self, query: &str) -> Vec<LauncherResult> {
+        let mut results: Vec<LauncherResult> = FULL_STATE
+            .load()
+            .settings
+            .launcher
+            .web_search
+            .iter()
+            .filter_map(|keyword| keyword.to_provider().resolve(self.id(), query))
+            .collect();
+
+        let mut plugin_providers: Vec<(String, LauncherKeywordProvider)> = Vec::new();
+        RESOURCES.plugins.scan(|id, plugin| {
+            if let PluginValue::Known(known) = &plugin.plugin {
+                if let KnownPlugin::Launcher(provider) = &**known {
+                    plugin_providers.push((id.to_string(), provider.clone()));
+                }
+            }
+        });
+        results.extend(
+            plugin_providers
+                .iter()
+                .filter_map(|(id, provider)| provider.resolve(id, query)),
+        );
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculator_uses_float_semantics() {
+        assert_eq!(CalculatorProvider::evaluate("7/2").as_deref(), Some("3.5"));
+        assert_eq!(
+            CalculatorProvider::evaluate("1/3").as_deref(),
+            Some("0.3333333333")
+        );
+        assert_eq!(
+            CalculatorProvider::evaluate("2^10").as_deref(),
+            Some("1024")
+        );
+        assert_eq!(CalculatorProvider::evaluate("6/2").as_deref(), Some("3"));
+        assert_eq!(
+            CalculatorProvider::evaluate("math::sqrt(16) + 0.5").as_deref(),
+            Some("4.5")
+        );
+
+        assert_eq!(CalculatorProvider::evaluate("2 +"), None);
+        assert_eq!(CalculatorProvider::evaluate("1/0"), None);
+        // a plain number is not a calculation
+        assert!(CalculatorProvider.query("12").is_empty());
+    }
+
+    #[test]
+    fn test_float_literals() {
+        assert_eq!(
+            CalculatorProvider::with_float_literals("7/2 + 1.5"),
+            "7.0/2.0 + 1.5"
+        );
+        assert_eq!(
+            CalculatorProvider::with_float_literals("math::log2(8)"),
+            "math::log2(8.0)"
+        );
+        assert_eq!(CalculatorProvider::with_float_literals("0x1f"), "0x1f");
+    }
+}
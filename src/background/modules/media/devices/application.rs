@@ -1,4 +1,8 @@
-use std::sync::LazyLock;
+use std::{collections::HashSet, sync::LazyLock};
+
+use parking_lot::Mutex;
+use seelen_core::state::AudioRuleEvent;
+use uuid::Uuid;
 
 use windows::Win32::{
     Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
@@ -27,8 +31,12 @@ use crate::{
 use super::domain::{MediaDevice, MediaDeviceSession, MediaDeviceType};
 
 pub struct DevicesManager {
-    inputs: SyncHashMap<String, MediaDevice>,
-    outputs: SyncHashMap<String, MediaDevice>,
+    pub(super) inputs: SyncHashMap<String, MediaDevice>,
+    pub(super) outputs: SyncHashMap<String, MediaDevice>,
+    /// processes that already got their saved audio routing applied
+    pub(super) routed_processes: Mutex<HashSet<u32>>,
+    /// last applied audio profile, its app overrides are also applied to new sessions
+    pub(super) active_audio_profile: Mutex<Option<Uuid>>,
 
     device_enumerator: IMMDeviceEnumerator,
     mm_notification_client: IMMNotificationClient,
@@ -62,6 +70,8 @@ pub enum DevicesEvent {
         volume: f32,
         muted: bool,
     },
+    /// executable name of the new foreground app
+    AppFocused(String),
 }
 
 unsafe impl Send for DevicesEvent {}
@@ -76,6 +86,8 @@ impl DevicesManager {
         Ok(Self {
             inputs: SyncHashMap::new(),
             outputs: SyncHashMap::new(),
+            routed_processes: Mutex::new(HashSet::new()),
+            active_audio_profile: Mutex::new(None),
             device_enumerator: Com::create_instance(&MMDeviceEnumerator)?,
             mm_notification_client: DevicesManagerEvents.into(),
         })
@@ -96,6 +108,16 @@ impl DevicesManager {
                 .RegisterEndpointNotificationCallback(&self.mm_notification_client)?;
         }
 
+        let mut processes = Vec::new();
+        for devices in [&self.inputs, &self.outputs] {
+            devices.for_each(|(_, device)| {
+                processes.extend(device.sessions.iter().map(|s| s.process_id));
+            });
+        }
+        for process_id in processes {
+            self.apply_saved_routing(process_id).log_error();
+        }
+
         let eid = Self::subscribe(|event| {
             DevicesManager::instance().process_event(event).log_error();
         });
@@ -233,6 +255,15 @@ impl DevicesManager {
             DevicesEvent::DeviceAdded(device_id) => {
                 if let Some(device) = self.get_raw_device(device_id) {
                     self.load_device(&device)?;
+                    let name = self.outputs.get(device_id, |d| d.name.clone());
+                    if let Some(name) =
+                        name.or_else(|| self.inputs.get(device_id, |d| d.name.clone()))
+                    {
+                        Self::on_audio_rule_event(&AudioRuleEvent::DeviceConnected {
+                            id: device_id,
+                            name: &name,
+                        });
+                    }
                 }
             }
             DevicesEvent::DeviceRemoved(device_id) => {
@@ -275,16 +306,25 @@ impl DevicesManager {
                 };
                 self.inputs.get(device_id, cb);
                 self.outputs.get(device_id, cb);
+                self.apply_saved_routing(session.process_id).log_error();
+                self.apply_profile_app_level(session.process_id).log_error();
             }
             DevicesEvent::SessionRemoved {
                 device_id,
                 session_id,
             } => {
-                let cb = |device: &mut MediaDevice| {
+                let mut process_id = None;
+                let mut cb = |device: &mut MediaDevice| {
+                    if let Some(session) = device.session(session_id) {
+                        process_id = Some(session.process_id);
+                    }
                     device.remove_session(session_id);
                 };
-                self.inputs.get(device_id, cb);
-                self.outputs.get(device_id, cb);
+                self.inputs.get(device_id, &mut cb);
+                self.outputs.get(device_id, &mut cb);
+                if let Some(process_id) = process_id {
+                    self.on_session_removed(process_id);
+                }
             }
             DevicesEvent::SessionVolumeChanged {
                 device_id,
@@ -301,6 +341,9 @@ impl DevicesManager {
                 self.inputs.get(device_id, cb);
                 self.outputs.get(device_id, cb);
             }
+            DevicesEvent::AppFocused(exe) => {
+                self.on_app_focused(exe)?;
+            }
         }
         Ok(())
     }
@@ -48,3 +48,19 @@ pub fn set_volume_level(device_id: String, session_id: Option<String>, level: f3
     let manager = get_devices_manager();
     manager.set_volume_level(device_id, session_id, level)
 }
+
+/// Moves the app owning the process to the device, input or output depending on the device.
+#[tauri::command(async)]
+pub fn media_set_app_device(process_id: u32, device_id: String) -> Result<()> {
+    get_devices_manager().set_app_device(process_id, &device_id)
+}
+
+#[tauri::command(async)]
+pub fn media_reset_app_devices(process_id: u32) -> Result<()> {
+    get_devices_manager().reset_app_devices(process_id)
+}
+
+#[tauri::command(async)]
+pub fn media_apply_audio_profile(id: uuid::Uuid) -> Result<()> {
+    get_devices_manager().apply_audio_profile(&id)
+}
@@ -1,5 +1,7 @@
 mod application;
 mod domain;
 pub mod infrastructure;
+pub mod profiles;
+mod routing;
 
 pub use application::DevicesManager;
@@ -0,0 +1,209 @@
+use std::{path::Path, sync::LazyLock};
+
+use parking_lot::Mutex;
+use seelen_core::state::{AudioLevel, AudioRuleEvent};
+use uuid::Uuid;
+use windows::Win32::Media::Audio::ISimpleAudioVolume;
+use windows_core::{Interface, GUID};
+
+use crate::{
+    error::{Result, ResultLogExt},
+    state::application::FULL_STATE,
+    windows_api::{process::Process, WindowsApi},
+};
+
+use super::{application::DevicesEvent, domain::MediaDevice, DevicesManager};
+
+/// executable of the last focused window, used to only trigger rules on focus changes
+static LAST_FOCUSED_EXE: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Should be called each time the foreground window changes.
+pub fn on_foreground_app_changed(exe_path: Option<&Path>) {
+    let exe = exe_path
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+
+    {
+        let mut last = LAST_FOCUSED_EXE.lock();
+        if *last == exe {
+            return;
+        }
+        last.clone_from(&exe);
+    }
+
+    let Some(exe) = exe else {
+        return;
+    };
+    // checked here to avoid posting events when there is no rule for the app
+    let has_rule = FULL_STATE
+        .load()
+        .settings
+        .audio
+        .profile_for_event(&AudioRuleEvent::AppFocused { exe: &exe })
+        .is_some();
+    if has_rule {
+        // the manager subscribes to its events on init, so it should exist before sending
+        DevicesManager::instance();
+        // handled on the devices thread, this is called from the win event hook
+        DevicesManager::send(DevicesEvent::AppFocused(exe));
+    }
+}
+
+impl DevicesManager {
+    /// Applies the profile of the first rule matching the event, if any.
+    pub fn on_audio_rule_event(event: &AudioRuleEvent) {
+        // checked before touching the manager, so no devices are loaded if there is no rule
+        let Some(profile_id) = FULL_STATE
+            .load()
+            .settings
+            .audio
+            .profile_for_event(event)
+            .map(|profile| profile.id)
+        else {
+            return;
+        };
+
+        log::trace!("Audio rule matched {event:?}");
+        Self::instance()
+            .apply_audio_profile(&profile_id)
+            .log_error();
+    }
+
+    /// Applies the profile of the focused app only if it is not already active,
+    /// so volumes adjusted by the user are not overridden on each focus change.
+    pub(super) fn on_app_focused(&self, exe: &str) -> Result<()> {
+        let Some(profile_id) = FULL_STATE
+            .load()
+            .settings
+            .audio
+            .profile_for_event(&AudioRuleEvent::AppFocused { exe })
+            .map(|profile| profile.id)
+        else {
+            return Ok(());
+        };
+
+        if *self.active_audio_profile.lock() == Some(profile_id) {
+            return Ok(());
+        }
+        log::trace!("Audio profile for focused app {exe}: {profile_id}");
+        self.apply_audio_profile(&profile_id)
+    }
+
+    /// Sets the default devices and volumes of the profile, devices not connected are ignored.
+    pub fn apply_audio_profile(&self, profile_id: &Uuid) -> Result<()> {
+        let state = FULL_STATE.load();
+        let profile = state
+            .settings
+            .audio
+            .profile(profile_id)
+            .ok_or_else(|| format!("audio profile not found: {profile_id}"))?;
+
+        // console is the role used by most apps, windows also sets it with multimedia
+        let defaults = [
+            (
+                &profile.default_output,
+                ["console", "multimedia"].as_slice(),
+            ),
+            (&profile.default_input, ["console", "multimedia"].as_slice()),
+            (
+                &profile.communications_output,
+                ["communications"].as_slice(),
+            ),
+            (&profile.communications_input, ["communications"].as_slice()),
+        ];
+        for (device_id, roles) in defaults {
+            let Some(device_id) = device_id.as_deref() else {
+                continue;
+            };
+            if self.device_kind(device_id).is_none() {
+                log::debug!("Audio profile device not connected: {device_id}");
+                continue;
+            }
+            for role in roles {
+                WindowsApi::set_default_audio_device(device_id, role)?;
+            }
+        }
+
+        for (device_id, level) in &profile.devices {
+            let cb = |device: &mut MediaDevice| -> Result<()> {
+                unsafe {
+                    if let Some(volume) = level.volume {
+                        device
+                            .volume_endpoint
+                            .SetMasterVolumeLevelScalar(volume.clamp(0.0, 1.0), &GUID::zeroed())?;
+                    }
+                    if let Some(muted) = level.muted {
+                        device.volume_endpoint.SetMute(muted, &GUID::zeroed())?;
+                    }
+                }
+                Ok(())
+            };
+            self.inputs.get(device_id, cb).transpose()?;
+            self.outputs.get(device_id, cb).transpose()?;
+        }
+
+        *self.active_audio_profile.lock() = Some(profile.id);
+
+        let mut processes = Vec::new();
+        for devices in [&self.inputs, &self.outputs] {
+            devices.for_each(|(_, device)| {
+                processes.extend(device.sessions.iter().map(|s| s.process_id));
+            });
+        }
+        processes.sort_unstable();
+        processes.dedup();
+        for process_id in processes {
+            self.apply_profile_app_level(process_id).log_error();
+        }
+        Ok(())
+    }
+
+    /// Applies the app volume override of the active profile to the sessions of the process.
+    pub(super) fn apply_profile_app_level(&self, process_id: u32) -> Result<()> {
+        // 0 is the system sounds session
+        if process_id == 0 {
+            return Ok(());
+        }
+        let Some(profile_id) = *self.active_audio_profile.lock() else {
+            return Ok(());
+        };
+
+        let state = FULL_STATE.load();
+        let Some(profile) = state.settings.audio.profile(&profile_id) else {
+            return Ok(());
+        };
+        let exe = Process::from_id(process_id).program_exe_name()?;
+        let Some(level) = profile.app_level(&exe) else {
+            return Ok(());
+        };
+
+        let mut controls = Vec::new();
+        for devices in [&self.inputs, &self.outputs] {
+            devices.for_each(|(_, device)| {
+                controls.extend(
+                    device
+                        .sessions
+                        .iter()
+                        .filter(|s| s.process_id == process_id)
+                        .map(|s| s.controls.clone()),
+                );
+            });
+        }
+        for session in controls {
+            set_session_level(&session.cast()?, level)?;
+        }
+        Ok(())
+    }
+}
+
+fn set_session_level(volume: &ISimpleAudioVolume, level: &AudioLevel) -> Result<()> {
+    unsafe {
+        if let Some(value) = level.volume {
+            volume.SetMasterVolume(value.clamp(0.0, 1.0), &GUID::zeroed())?;
+        }
+        if let Some(muted) = level.muted {
+            volume.SetMute(muted, &GUID::zeroed())?;
+        }
+    }
+    Ok(())
+}
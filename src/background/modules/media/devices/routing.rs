@@ -0,0 +1,103 @@
+use seelen_core::{state::AppsAudioRouting, system_state::MediaDeviceType};
+use windows::Win32::Media::Audio::{eCapture, eRender, EDataFlow};
+
+use crate::{
+    error::Result,
+    state::application::FULL_STATE,
+    windows_api::{process::Process, WindowsApi},
+};
+
+use super::{domain::MediaDevice, DevicesManager};
+
+fn data_flow(kind: MediaDeviceType) -> EDataFlow {
+    match kind {
+        MediaDeviceType::Input => eCapture,
+        MediaDeviceType::Output => eRender,
+    }
+}
+
+fn update_saved_routing(f: impl Fn(&mut AppsAudioRouting)) -> Result<()> {
+    FULL_STATE.rcu(|state| {
+        let mut state = state.cloned();
+        f(&mut state.settings.audio_by_app);
+        state
+    });
+    FULL_STATE.load().write_settings()
+}
+
+impl DevicesManager {
+    pub(super) fn device_kind(&self, device_id: &str) -> Option<MediaDeviceType> {
+        if self.outputs.contains_key(device_id) {
+            Some(MediaDeviceType::Output)
+        } else if self.inputs.contains_key(device_id) {
+            Some(MediaDeviceType::Input)
+        } else {
+            None
+        }
+    }
+
+    /// Moves the process to the device and saves it as preferred device of its executable,
+    /// so it is applied again when the app is restarted.
+    pub fn set_app_device(&self, process_id: u32, device_id: &str) -> Result<()> {
+        let kind = self
+            .device_kind(device_id)
+            .ok_or_else(|| format!("device not found: {device_id}"))?;
+        let exe = Process::from_id(process_id).program_exe_name()?;
+
+        WindowsApi::set_process_audio_endpoint(process_id, data_flow(kind), Some(device_id))?;
+        update_saved_routing(|routing| routing.set(&exe, kind, Some(device_id.to_string())))
+    }
+
+    /// Moves the process back to the system default devices and forgets its saved routing.
+    pub fn reset_app_devices(&self, process_id: u32) -> Result<()> {
+        let exe = Process::from_id(process_id).program_exe_name()?;
+        for kind in [MediaDeviceType::Output, MediaDeviceType::Input] {
+            WindowsApi::set_process_audio_endpoint(process_id, data_flow(kind), None)?;
+        }
+        update_saved_routing(|routing| {
+            routing.remove(&exe);
+        })
+    }
+
+    /// Applies the saved routing of the executable to the process, only once per process.
+    /// Failed attempts are retried on the next session of the process.
+    pub(super) fn apply_saved_routing(&self, process_id: u32) -> Result<()> {
+        // 0 is the system sounds session
+        if process_id == 0 || self.routed_processes.lock().contains(&process_id) {
+            return Ok(());
+        }
+
+        let exe = Process::from_id(process_id).program_exe_name()?;
+        let Some(routing) = FULL_STATE.load().settings.audio_by_app.get(&exe).cloned() else {
+            return Ok(());
+        };
+
+        for kind in [MediaDeviceType::Output, MediaDeviceType::Input] {
+            // the saved device could be disconnected
+            if let Some(device_id) = routing
+                .device(kind)
+                .filter(|id| self.device_kind(id) == Some(kind))
+            {
+                log::trace!("Routing {exe} ({process_id}) to {device_id}");
+                WindowsApi::set_process_audio_endpoint(
+                    process_id,
+                    data_flow(kind),
+                    Some(device_id),
+                )?;
+            }
+        }
+        self.routed_processes.lock().insert(process_id);
+        Ok(())
+    }
+
+    /// Forgets the process once all its sessions are closed, so if it opens a new one or its
+    /// id is reused by a new process, the saved routing is applied again.
+    pub(super) fn on_session_removed(&self, process_id: u32) {
+        let has_sessions = |(_, device): (&String, &MediaDevice)| {
+            device.sessions.iter().any(|s| s.process_id == process_id)
+        };
+        if !self.outputs.any(has_sessions) && !self.inputs.any(has_sessions) {
+            self.routed_processes.lock().remove(&process_id);
+        }
+    }
+}
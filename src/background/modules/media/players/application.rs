@@ -1,15 +1,22 @@
 use std::{ffi::OsStr, path::PathBuf, sync::LazyLock, time::Duration};
 
-use seelen_core::system_state::{MediaPlayerOwner, MediaPlayerTimeline};
+use seelen_core::{
+    chrono::Utc,
+    system_state::{MediaPlayerControls, MediaPlayerOwner, MediaPlayerTimeline, MediaRepeatMode},
+};
 use windows::{
     Foundation::TypedEventHandler,
-    Media::Control::{
-        GlobalSystemMediaTransportControlsSession,
-        GlobalSystemMediaTransportControlsSessionManager,
-        GlobalSystemMediaTransportControlsSessionPlaybackStatus,
-        GlobalSystemMediaTransportControlsSessionTimelineProperties,
-        MediaPropertiesChangedEventArgs, PlaybackInfoChangedEventArgs, SessionsChangedEventArgs,
-        TimelinePropertiesChangedEventArgs,
+    Media::{
+        Control::{
+            GlobalSystemMediaTransportControlsSession,
+            GlobalSystemMediaTransportControlsSessionManager,
+            GlobalSystemMediaTransportControlsSessionPlaybackInfo,
+            GlobalSystemMediaTransportControlsSessionPlaybackStatus,
+            GlobalSystemMediaTransportControlsSessionTimelineProperties,
+            MediaPropertiesChangedEventArgs, PlaybackInfoChangedEventArgs,
+            SessionsChangedEventArgs, TimelinePropertiesChangedEventArgs,
+        },
+        MediaPlaybackAutoRepeatMode,
     },
 };
 
@@ -21,7 +28,7 @@ use crate::{
     windows_api::{types::AppUserModelId, WindowsApi},
 };
 
-use super::domain::{MediaPlayer, MediaPlayerSession};
+use super::domain::{MediaPlayer, MediaPlayerSession, PlayerPlayback};
 
 fn timeline_from_raw(
     raw: GlobalSystemMediaTransportControlsSessionTimelineProperties,
@@ -37,6 +44,50 @@ fn timeline_from_raw(
     })
 }
 
+fn playback_from_raw(
+    raw: GlobalSystemMediaTransportControlsSessionPlaybackInfo,
+) -> windows_core::Result<PlayerPlayback> {
+    let controls = raw.Controls()?;
+    Ok(PlayerPlayback {
+        playing: raw.PlaybackStatus()?
+            == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing,
+        controls: MediaPlayerControls {
+            play_pause: controls.IsPlayPauseToggleEnabled()?,
+            next: controls.IsNextEnabled()?,
+            previous: controls.IsPreviousEnabled()?,
+            seek: controls.IsPlaybackPositionEnabled()?,
+            shuffle: controls.IsShuffleEnabled()?,
+            repeat: controls.IsRepeatEnabled()?,
+            playback_rate: controls.IsPlaybackRateEnabled()?,
+        },
+        // these are null if the player doesn't report them
+        shuffle: raw.IsShuffleActive().and_then(|v| v.Value()).ok(),
+        repeat_mode: raw
+            .AutoRepeatMode()
+            .and_then(|v| v.Value())
+            .ok()
+            .map(|mode| match mode {
+                MediaPlaybackAutoRepeatMode::Track => MediaRepeatMode::Track,
+                MediaPlaybackAutoRepeatMode::List => MediaRepeatMode::List,
+                _ => MediaRepeatMode::None,
+            }),
+        playback_rate: raw.PlaybackRate().and_then(|v| v.Value()).ok(),
+    })
+}
+
+pub fn repeat_mode_to_raw(mode: MediaRepeatMode) -> MediaPlaybackAutoRepeatMode {
+    match mode {
+        MediaRepeatMode::None => MediaPlaybackAutoRepeatMode::None,
+        MediaRepeatMode::Track => MediaPlaybackAutoRepeatMode::Track,
+        MediaRepeatMode::List => MediaPlaybackAutoRepeatMode::List,
+    }
+}
+
+/// Current time as windows universal time, same unit as `MediaPlayerTimeline::last_updated_time`.
+fn universal_now() -> i64 {
+    MediaPlayerTimeline::universal_time_from_unix_millis(Utc::now().timestamp_millis())
+}
+
 #[derive(Debug, Clone)]
 pub enum PlayersEvent {
     PlayerAdded(GlobalSystemMediaTransportControlsSession),
@@ -48,9 +99,9 @@ pub enum PlayersEvent {
         author: String,
         thumbnail: Option<PathBuf>,
     },
-    PlaybackStatusChanged {
+    PlaybackChanged {
         id: String,
-        playing: bool,
+        playback: PlayerPlayback,
     },
     TimelineChanged {
         id: String,
@@ -128,6 +179,10 @@ impl PlayersManager {
         self.playing.values()
     }
 
+    pub fn get_player_state(&self, umid: &str) -> Option<seelen_core::system_state::MediaPlayer> {
+        self.playing.get(umid, |player| player.base.clone())
+    }
+
     fn process_event(&self, event: &PlayersEvent) -> Result<()> {
         match event {
             PlayersEvent::PlayerAdded(session) => {
@@ -176,14 +231,14 @@ impl PlayersManager {
                     player.base.thumbnail = thumbnail.clone();
                 });
             }
-            PlayersEvent::PlaybackStatusChanged { id, playing } => {
+            PlayersEvent::PlaybackChanged { id, playback } => {
                 self.playing.get(id, |player| {
-                    player.base.playing = *playing;
+                    player.set_playback(playback.clone(), universal_now());
                 });
             }
             PlayersEvent::TimelineChanged { id, timeline } => {
                 self.playing.get(id, |player| {
-                    player.base.timeline = timeline.clone();
+                    player.set_timeline(timeline.clone());
                 });
             }
         }
@@ -203,9 +258,8 @@ impl PlayersManager {
             session.SourceAppUserModelId()?.to_string_lossy().into();
         let properties = session.TryGetMediaPropertiesAsync()?.get()?;
 
-        let timeline = session.GetTimelineProperties()?;
-        let playback_info = session.GetPlaybackInfo()?;
-        let status = playback_info.PlaybackStatus()?;
+        let timeline = timeline_from_raw(session.GetTimelineProperties()?)?;
+        let playback = playback_from_raw(session.GetPlaybackInfo()?)?;
 
         let display_name = match &source_app_umid {
             AppUserModelId::Appx(umid) => WindowsApi::get_uwp_app_info(umid)?
@@ -229,26 +283,27 @@ impl PlayersManager {
 
         // pre-extraction to avoid flickering on the ui
         request_icon_extraction_from_umid(&source_app_umid);
-        self.playing.upsert(
-            source_app_umid.to_string(),
-            MediaPlayer {
-                base: seelen_core::system_state::MediaPlayer {
-                    umid: source_app_umid.to_string(),
-                    title: properties.Title().unwrap_or_default().to_string_lossy(),
-                    author: properties.Artist().unwrap_or_default().to_string_lossy(),
-                    owner: MediaPlayerOwner { name: display_name },
-                    thumbnail: properties
-                        .Thumbnail()
-                        .ok()
-                        .and_then(|stream| WindowsApi::extract_thumbnail_from_ref(stream).ok()),
-                    playing: status
-                        == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing,
-                    timeline: timeline_from_raw(timeline)?,
-                    default: false,
-                },
-                removed_at: None,
+        let player = MediaPlayer {
+            base: seelen_core::system_state::MediaPlayer {
+                umid: source_app_umid.to_string(),
+                title: properties.Title().unwrap_or_default().to_string_lossy(),
+                author: properties.Artist().unwrap_or_default().to_string_lossy(),
+                owner: MediaPlayerOwner { name: display_name },
+                thumbnail: properties
+                    .Thumbnail()
+                    .ok()
+                    .and_then(|stream| WindowsApi::extract_thumbnail_from_ref(stream).ok()),
+                playing: playback.playing,
+                timeline,
+                default: false,
+                controls: playback.controls,
+                shuffle: playback.shuffle,
+                repeat_mode: playback.repeat_mode,
+                playback_rate: playback.playback_rate,
             },
-        );
+            removed_at: None,
+        };
+        self.playing.upsert(source_app_umid.to_string(), player);
 
         let wrapped_session = MediaPlayerSession::create(
             session,
@@ -337,13 +392,11 @@ impl PlayersManager {
         _args: &Option<PlaybackInfoChangedEventArgs>,
     ) -> windows_core::Result<()> {
         if let Some(session) = session {
-            let playback = session.GetPlaybackInfo()?;
             let player_id = session.SourceAppUserModelId()?;
             let tx = PlayersManager::event_tx();
-            let event = PlayersEvent::PlaybackStatusChanged {
+            let event = PlayersEvent::PlaybackChanged {
                 id: player_id.to_string(),
-                playing: playback.PlaybackStatus()?
-                    == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing,
+                playback: playback_from_raw(session.GetPlaybackInfo()?)?,
             };
             tx.send(event).log_error();
         }
@@ -1,5 +1,6 @@
 use std::time::Instant;
 
+use seelen_core::system_state::{MediaPlayerControls, MediaPlayerTimeline, MediaRepeatMode};
 use windows::{
     Foundation::TypedEventHandler,
     Media::Control::{
@@ -63,12 +64,45 @@ impl Drop for MediaPlayerSession {
     }
 }
 
+/// Playback info of a session as reported by the player.
+#[derive(Debug, Clone)]
+pub struct PlayerPlayback {
+    pub playing: bool,
+    pub controls: MediaPlayerControls,
+    pub shuffle: Option<bool>,
+    pub repeat_mode: Option<MediaRepeatMode>,
+    pub playback_rate: Option<f64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MediaPlayer {
     pub base: seelen_core::system_state::MediaPlayer,
     pub removed_at: Option<Instant>,
 }
 
+impl MediaPlayer {
+    pub fn set_playback(&mut self, playback: PlayerPlayback, now: i64) {
+        // rebase the timeline so the interpolation continues from the current position
+        let timeline = &mut self.base.timeline;
+        timeline.position = timeline.position_at(
+            now,
+            self.base.playing,
+            self.base.playback_rate.unwrap_or(1.0),
+        );
+        timeline.last_updated_time = now;
+
+        self.base.playing = playback.playing;
+        self.base.controls = playback.controls;
+        self.base.shuffle = playback.shuffle;
+        self.base.repeat_mode = playback.repeat_mode;
+        self.base.playback_rate = playback.playback_rate;
+    }
+
+    pub fn set_timeline(&mut self, timeline: MediaPlayerTimeline) {
+        self.base.timeline = timeline;
+    }
+}
+
 impl serde::Serialize for MediaPlayer {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
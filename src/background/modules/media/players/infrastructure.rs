@@ -1,10 +1,11 @@
 use std::sync::Once;
 
-use seelen_core::handlers::SeelenEvent;
+use seelen_core::{handlers::SeelenEvent, system_state::MediaRepeatMode};
+use windows::Media::Control::GlobalSystemMediaTransportControlsSession;
 
 use crate::{app::emit_to_webviews, error::Result};
 
-use super::{domain::MediaPlayer, PlayersManager};
+use super::{application::repeat_mode_to_raw, domain::MediaPlayer, PlayersManager};
 
 fn get_players_manager() -> &'static PlayersManager {
     static TAURI_EVENT_REGISTRATION: Once = Once::new();
@@ -19,6 +20,20 @@ fn get_players_manager() -> &'static PlayersManager {
     PlayersManager::instance()
 }
 
+/// Session and state of the player, commands on unknown players are an error.
+fn get_player(
+    id: &str,
+) -> Result<(
+    GlobalSystemMediaTransportControlsSession,
+    seelen_core::system_state::MediaPlayer,
+)> {
+    let manager = get_players_manager();
+    match (manager.get_media_player(id), manager.get_player_state(id)) {
+        (Some(session), Some(state)) => Ok((session, state)),
+        _ => Err(format!("media player {id} not found").into()),
+    }
+}
+
 #[tauri::command(async)]
 pub fn get_media_sessions() -> Result<Vec<MediaPlayer>> {
     let manager = get_players_manager();
@@ -60,3 +75,69 @@ pub fn media_toggle_play_pause(id: String) -> Result<()> {
     }
     Ok(())
 }
+
+/// `position` is in nanoseconds, clamped to the seekable range of the media.
+#[tauri::command(async)]
+pub fn media_seek(id: String, position: i64) -> Result<()> {
+    let (session, state) = get_player(&id)?;
+    if !state.controls.seek {
+        return Err("the player doesn't support seeking".into());
+    }
+    let position = state.timeline.clamp_seek(position);
+    // the position is expected in ticks of 100ns
+    let success = session
+        .TryChangePlaybackPositionAsync(position / 100)?
+        .get()?;
+    if !success {
+        return Err("failed to seek".into());
+    }
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn media_set_playback_rate(id: String, rate: f64) -> Result<()> {
+    let (session, state) = get_player(&id)?;
+    if !state.controls.playback_rate {
+        return Err("the player doesn't support changing the playback rate".into());
+    }
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(format!("invalid playback rate: {rate}").into());
+    }
+    let success = session.TryChangePlaybackRateAsync(rate)?.get()?;
+    if !success {
+        return Err("failed to change playback rate".into());
+    }
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn media_toggle_shuffle(id: String) -> Result<()> {
+    let (session, state) = get_player(&id)?;
+    if !state.controls.shuffle {
+        return Err("the player doesn't support shuffle".into());
+    }
+    let success = session
+        .TryChangeShuffleActiveAsync(!state.shuffle.unwrap_or(false))?
+        .get()?;
+    if !success {
+        return Err("failed to toggle shuffle".into());
+    }
+    Ok(())
+}
+
+/// Cycles the repeat mode: none → list → track → none.
+#[tauri::command(async)]
+pub fn media_cycle_repeat_mode(id: String) -> Result<()> {
+    let (session, state) = get_player(&id)?;
+    if !state.controls.repeat {
+        return Err("the player doesn't support repeat modes".into());
+    }
+    let next = state.repeat_mode.unwrap_or(MediaRepeatMode::None).next();
+    let success = session
+        .TryChangeAutoRepeatModeAsync(repeat_mode_to_raw(next))?
+        .get()?;
+    if !success {
+        return Err("failed to change repeat mode".into());
+    }
+    Ok(())
+}
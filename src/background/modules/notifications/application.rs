@@ -1,6 +1,8 @@
 use parking_lot::Mutex as ParkingLotMutex;
+use seelen_core::state::NotificationAction;
 use seelen_core::system_state::{
-    AppNotification, Toast, ToastActionActivationType, ToastBindingChild, ToastText,
+    AppNotification, NotificationHistory, Toast, ToastActionActivationType, ToastBindingChild,
+    ToastText,
 };
 use std::{
     collections::HashSet,
@@ -28,8 +30,13 @@ use crate::{
     event_manager, log_error,
     modules::{
         apps::application::msix::get_hightest_quality_posible_for_uwp_image,
+        notifications::{
+            focus::{get_focus_mode, start_focus_mode_tracking},
+            widgets::SEELEN_NOTIFICATIONS_UMID,
+        },
         start::application::StartMenuManager,
     },
+    state::application::FULL_STATE,
     utils::{
         convert_file_to_src, icon_extractor::request_icon_extraction_from_umid,
         lock_free::SyncHashMap, spawn_named_thread,
@@ -44,12 +51,18 @@ static RELEASED: AtomicBool = AtomicBool::new(true);
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationEvent {
     Added(u32),
+    /// notification posted by a widget
+    Posted(u32),
     Removed(u32),
     Cleared,
+    FocusModeChanged,
+    GroupCollapseChanged,
 }
 
 pub struct NotificationManager {
-    notifications: SyncHashMap<u32, AppNotification>,
+    pub(super) notifications: SyncHashMap<u32, AppNotification>,
+    /// notifications received, kept after being removed from the Action Center
+    pub(super) history: ParkingLotMutex<NotificationHistory>,
     manager: ToastNotificationManagerForUser,
     listener: UserNotificationListener,
     event_token: Option<i64>,
@@ -64,6 +77,7 @@ impl NotificationManager {
     fn new() -> Result<Self> {
         Ok(Self {
             notifications: SyncHashMap::new(),
+            history: ParkingLotMutex::new(Self::load_history()),
             manager: ToastNotificationManager::GetDefault()?,
             listener: UserNotificationListener::Current()?,
             event_token: None,
@@ -87,7 +101,9 @@ impl NotificationManager {
     }
 
     pub fn remove_notification(&self, id: u32) -> Result<()> {
-        self.listener.RemoveNotification(id)?;
+        if !self.is_own_notification(id) {
+            self.listener.RemoveNotification(id)?;
+        }
         Self::send(NotificationEvent::Removed(id));
         Ok(())
     }
@@ -95,7 +111,9 @@ impl NotificationManager {
     pub fn clear_notifications(&self) -> Result<()> {
         let mut umids = HashSet::new();
         self.notifications.for_each(|(_, n)| {
-            umids.insert(n.app_umid.clone());
+            if n.widget_id.is_none() && n.app_umid != SEELEN_NOTIFICATIONS_UMID {
+                umids.insert(n.app_umid.clone());
+            }
         });
         for umid in umids {
             let history = self.manager.History()?;
@@ -145,6 +163,7 @@ impl NotificationManager {
 
         let eid = Self::subscribe(|e| log_error!(Self::process_event(e)));
         Self::set_event_handler_priority(&eid, 1);
+        start_focus_mode_tracking();
         Ok(())
     }
 
@@ -199,6 +218,7 @@ impl NotificationManager {
                 let u_notification = UserNotificationListener::Current()?.GetNotification(id)?;
                 manager.load_notification(u_notification)?;
             }
+            NotificationEvent::Posted(_) => {}
             NotificationEvent::Removed(id) => {
                 manager.notifications.remove(&id);
                 LOADED_NOTIFICATIONS.lock().remove(&id);
@@ -207,6 +227,7 @@ impl NotificationManager {
                 manager.notifications.clear();
                 LOADED_NOTIFICATIONS.lock().clear();
             }
+            NotificationEvent::FocusModeChanged | NotificationEvent::GroupCollapseChanged => {}
         }
         Ok(())
     }
@@ -369,8 +390,8 @@ impl NotificationManager {
                         .trim()
                         .to_owned();
                     content.push(ToastBindingChild::Text(ToastText {
-                        id: None,
                         content: text,
+                        ..Default::default()
                     }));
                 }
                 Self::clean_toast(&mut toast, &app_umid.to_string())?;
@@ -381,19 +402,42 @@ impl NotificationManager {
         // pre-extraction to avoid flickering on the ui
         request_icon_extraction_from_umid(&app_umid.to_string().into());
 
-        self.notifications.upsert(
-            notification_id,
-            AppNotification {
-                id: notification_id,
-                app_umid: app_umid.to_string(),
-                app_name: display_info.DisplayName()?.to_string(),
-                app_description: display_info.Description()?.to_string(),
-                date: u_notification.CreationTime()?.UniversalTime,
-                content: notification_content,
-            },
-        );
+        let notification = AppNotification {
+            id: notification_id,
+            app_umid: app_umid.to_string(),
+            app_name: display_info.DisplayName()?.to_string(),
+            app_description: display_info.Description()?.to_string(),
+            date: u_notification.CreationTime()?.UniversalTime,
+            silent: false,
+            widget_id: None,
+            content: notification_content,
+        };
+        self.add_notification(notification);
         Ok(())
     }
+
+    /// Applies the notification rules of the active focus mode and stores the notification.\
+    /// Returns false if the notification was not added to the list by the rules.
+    pub(super) fn add_notification(&self, mut notification: AppNotification) -> bool {
+        let action = FULL_STATE
+            .load()
+            .settings
+            .notifications
+            .evaluate(&get_focus_mode(), &notification);
+        match action {
+            NotificationAction::Suppress => return false,
+            NotificationAction::HistoryOnly => {
+                self.record_in_history(&notification);
+                return false;
+            }
+            NotificationAction::Silence => notification.silent = true,
+            NotificationAction::Show => {}
+        }
+
+        self.record_in_history(&notification);
+        self.notifications.upsert(notification.id, notification);
+        true
+    }
 }
 
 impl Drop for NotificationManager {
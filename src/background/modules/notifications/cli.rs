@@ -0,0 +1,32 @@
+use crate::error::Result;
+
+use super::focus::toggle_focus_mode;
+
+#[derive(Debug, clap::Args)]
+pub struct NotificationsCli {
+    #[command(subcommand)]
+    command: NotificationsCommand,
+}
+
+impl NotificationsCli {
+    pub fn process(self) -> Result<()> {
+        self.command.process()
+    }
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum NotificationsCommand {
+    /// Enables the focus mode at the index, or disables it if it is already enabled.
+    ToggleFocusMode {
+        /// Index of the focus mode on the settings.
+        index: usize,
+    },
+}
+
+impl NotificationsCommand {
+    pub fn process(self) -> Result<()> {
+        match self {
+            NotificationsCommand::ToggleFocusMode { index } => toggle_focus_mode(index),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock, Once,
+    },
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use seelen_core::{
+    chrono::Local,
+    handlers::SeelenEvent,
+    state::{FocusModeContext, FocusModeState},
+};
+use tauri::Listener;
+use uuid::Uuid;
+
+use crate::{app::get_app_handle, error::Result, get_tokio_handle, state::application::FULL_STATE};
+
+use super::application::{NotificationEvent, NotificationManager};
+
+/// focus mode enabled by the user via the ui or a hotkey
+static MANUAL_FOCUS_MODE: LazyLock<Mutex<Option<Uuid>>> = LazyLock::new(|| Mutex::new(None));
+static FOCUS_MODE_STATE: LazyLock<Mutex<FocusModeState>> =
+    LazyLock::new(|| Mutex::new(FocusModeState::default()));
+static FOREGROUND_FULLSCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Starts the re-evaluation of the focus mode on settings changes and schedule ticks.
+pub(super) fn start_focus_mode_tracking() {
+    static STARTED: Once = Once::new();
+    STARTED.call_once(|| {
+        refresh_focus_mode();
+        get_app_handle().listen(SeelenEvent::StateSettingsChanged, |_| {
+            refresh_focus_mode();
+        });
+        get_tokio_handle().spawn(async {
+            loop {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                refresh_focus_mode();
+            }
+        });
+    });
+}
+
+/// Should be called each time the foreground window changes.
+pub fn set_foreground_fullscreen(fullscreen: bool) {
+    if FOREGROUND_FULLSCREEN.swap(fullscreen, Ordering::SeqCst) != fullscreen {
+        refresh_focus_mode();
+    }
+}
+
+pub fn get_focus_mode() -> FocusModeState {
+    FOCUS_MODE_STATE.lock().clone()
+}
+
+pub fn set_manual_focus_mode(id: Option<Uuid>) {
+    *MANUAL_FOCUS_MODE.lock() = id;
+    refresh_focus_mode();
+}
+
+/// Enables the focus mode at the index as manual focus mode, or disables it if it was already enabled.
+pub fn toggle_focus_mode(index: usize) -> Result<()> {
+    let state = FULL_STATE.load();
+    let Some(mode) = state.settings.notifications.focus_modes.get(index) else {
+        return Err(format!("There is no focus mode at index {index}").into());
+    };
+
+    let current = *MANUAL_FOCUS_MODE.lock();
+    set_manual_focus_mode(if current == Some(mode.id) {
+        None
+    } else {
+        Some(mode.id)
+    });
+    Ok(())
+}
+
+fn refresh_focus_mode() {
+    let ctx = FocusModeContext {
+        now: Local::now().naive_local(),
+        fullscreen: FOREGROUND_FULLSCREEN.load(Ordering::SeqCst),
+        manual: *MANUAL_FOCUS_MODE.lock(),
+    };
+    let new_state = FULL_STATE
+        .load()
+        .settings
+        .notifications
+        .resolve_focus_mode(&ctx);
+
+    let mut state = FOCUS_MODE_STATE.lock();
+    if *state != new_state {
+        log::trace!("Focus mode changed: {new_state:?}");
+        *state = new_state;
+        NotificationManager::send(NotificationEvent::FocusModeChanged);
+    }
+}
@@ -0,0 +1,46 @@
+use std::{collections::HashSet, sync::LazyLock};
+
+use parking_lot::Mutex;
+use seelen_core::system_state::{NotificationAppGroup, NotificationGroupId};
+
+use crate::error::Result;
+
+use super::application::{NotificationEvent, NotificationManager};
+
+/// groups collapsed by the user, kept while the groups are empty so new notifications respect it
+static COLLAPSED_GROUPS: LazyLock<Mutex<HashSet<NotificationGroupId>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+impl NotificationManager {
+    pub fn notifications_grouped(&self) -> Vec<NotificationAppGroup> {
+        NotificationAppGroup::group(&self.notifications(), &COLLAPSED_GROUPS.lock())
+    }
+
+    /// Removes all the notifications of the group.
+    pub fn close_group(&self, group: &NotificationGroupId) -> Result<()> {
+        let ids: Vec<u32> = self
+            .notifications()
+            .iter()
+            .filter(|n| group.contains(n))
+            .map(|n| n.id)
+            .collect();
+        for id in ids {
+            self.remove_notification(id)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_group_collapsed(&self, group: NotificationGroupId, collapsed: bool) {
+        let changed = {
+            let mut groups = COLLAPSED_GROUPS.lock();
+            if collapsed {
+                groups.insert(group)
+            } else {
+                groups.remove(&group)
+            }
+        };
+        if changed {
+            Self::send(NotificationEvent::GroupCollapseChanged);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::{fs::File, path::PathBuf, sync::LazyLock, time::Duration};
+
+use seelen_core::{
+    chrono::Utc,
+    system_state::{
+        file_time_from_unix_millis, AppNotification, NotificationHistory, NotificationHistoryQuery,
+    },
+};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    error::Result,
+    state::application::FULL_STATE,
+    utils::{constants::SEELEN_COMMON, Debouncer},
+};
+
+use super::application::NotificationManager;
+
+impl NotificationManager {
+    fn history_path() -> PathBuf {
+        SEELEN_COMMON
+            .app_data_dir()
+            .join("notifications_history.json")
+    }
+
+    pub(super) fn load_history() -> NotificationHistory {
+        let load = || -> Result<NotificationHistory> {
+            let file = File::open(Self::history_path())?;
+            Ok(serde_json::from_reader(file)?)
+        };
+        match load() {
+            Ok(mut history) => {
+                let settings = &FULL_STATE.load().settings.notifications.history;
+                let now = file_time_from_unix_millis(Utc::now().timestamp_millis());
+                history.apply_retention(settings, now);
+                history
+            }
+            Err(_) => NotificationHistory::default(),
+        }
+    }
+
+    fn request_history_save(&self) {
+        static SAVE_DEBOUNCER: LazyLock<Debouncer> =
+            LazyLock::new(|| Debouncer::new(Duration::from_secs(2)));
+
+        SAVE_DEBOUNCER.call(async move || {
+            let history = NotificationManager::instance().history.lock().clone();
+            let mut file = tokio::fs::File::create(Self::history_path()).await?;
+            file.write_all(&serde_json::to_vec(&history)?).await?;
+            file.flush().await?;
+            log::trace!("notifications history successfully saved");
+            Result::Ok(())
+        });
+    }
+
+    /// Stores the notification on the local history, if enabled.
+    pub(super) fn record_in_history(&self, notification: &AppNotification) {
+        let settings = FULL_STATE.load().settings.notifications.history.clone();
+        if !settings.enabled {
+            return;
+        }
+
+        let now = file_time_from_unix_millis(Utc::now().timestamp_millis());
+        let mut history = self.history.lock();
+        if history.record(notification.clone()) {
+            history.apply_retention(&settings, now);
+            self.request_history_save();
+        }
+    }
+
+    pub fn query_history(&self, query: &NotificationHistoryQuery) -> Vec<AppNotification> {
+        self.history.lock().query(query)
+    }
+
+    pub fn clear_history(&self) {
+        self.history.lock().entries.clear();
+        self.request_history_save();
+    }
+}
@@ -1,6 +1,14 @@
 use std::{collections::HashMap, sync::Once};
 
-use seelen_core::{handlers::SeelenEvent, system_state::AppNotification};
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::FocusModeState,
+    system_state::{
+        AppNotification, NotificationAppGroup, NotificationGroupId, NotificationHistoryQuery,
+        WidgetNotification,
+    },
+};
+use uuid::Uuid;
 use windows::{
     core::GUID,
     Win32::UI::Notifications::{INotificationActivationCallback, NOTIFICATION_USER_INPUT_DATA},
@@ -9,18 +17,35 @@ use windows::{
 use crate::{
     app::emit_to_webviews,
     error::Result,
-    modules::notifications::application::{get_toast_activator_clsid, NotificationManager},
+    modules::notifications::{
+        application::{get_toast_activator_clsid, NotificationEvent, NotificationManager},
+        focus::{get_focus_mode, set_manual_focus_mode},
+    },
+    widgets::webview::WidgetWebviewLabel,
     windows_api::{string_utils::WindowsString, types::AppUserModelId, Com},
 };
 
 fn get_notification_manager() -> &'static NotificationManager {
     static TAURI_EVENT_REGISTRATION: Once = Once::new();
     TAURI_EVENT_REGISTRATION.call_once(|| {
-        NotificationManager::subscribe(|_event| {
-            emit_to_webviews(
-                SeelenEvent::Notifications,
-                NotificationManager::instance().notifications(),
-            );
+        NotificationManager::subscribe(|event| match event {
+            NotificationEvent::FocusModeChanged => {
+                emit_to_webviews(SeelenEvent::NotificationsFocusModeChanged, get_focus_mode());
+            }
+            NotificationEvent::GroupCollapseChanged => {
+                emit_to_webviews(
+                    SeelenEvent::NotificationsGrouped,
+                    NotificationManager::instance().notifications_grouped(),
+                );
+            }
+            _ => {
+                let manager = NotificationManager::instance();
+                emit_to_webviews(SeelenEvent::Notifications, manager.notifications());
+                emit_to_webviews(
+                    SeelenEvent::NotificationsGrouped,
+                    manager.notifications_grouped(),
+                );
+            }
         });
     });
     NotificationManager::instance()
@@ -41,6 +66,10 @@ pub fn activate_notification(
 ) -> Result<()> {
     log::trace!("Activating notification \'{umid}\' with args \'{args}\'");
 
+    if get_notification_manager().activate_own_notification(&umid, &args, input_data.clone())? {
+        return Ok(());
+    }
+
     let app_umid = AppUserModelId::from(umid);
 
     if let Ok(activator_clsid) = get_toast_activator_clsid(&app_umid) {
@@ -83,3 +112,54 @@ pub fn notifications_close(id: u32) -> Result<()> {
 pub fn notifications_close_all() -> Result<()> {
     get_notification_manager().clear_notifications()
 }
+
+#[tauri::command(async)]
+pub fn get_notifications_grouped() -> Vec<NotificationAppGroup> {
+    get_notification_manager().notifications_grouped()
+}
+
+#[tauri::command(async)]
+pub fn notifications_close_group(group: NotificationGroupId) -> Result<()> {
+    get_notification_manager().close_group(&group)
+}
+
+#[tauri::command(async)]
+pub fn notifications_set_group_collapsed(group: NotificationGroupId, collapsed: bool) {
+    get_notification_manager().set_group_collapsed(group, collapsed);
+}
+
+#[tauri::command(async)]
+pub fn notifications_post(
+    webview: tauri::WebviewWindow,
+    notification: WidgetNotification,
+) -> Result<u32> {
+    let label = WidgetWebviewLabel::try_from_raw(webview.label())?;
+    get_notification_manager().post_widget_notification(&label.widget_id, notification)
+}
+
+/// Shows a notification of Seelen UI itself, along the system ones.
+pub fn post_seelen_notification(notification: WidgetNotification) -> u32 {
+    get_notification_manager().post_seelen_notification(notification)
+}
+
+#[tauri::command(async)]
+pub fn notifications_history(query: NotificationHistoryQuery) -> Vec<AppNotification> {
+    get_notification_manager().query_history(&query)
+}
+
+#[tauri::command(async)]
+pub fn notifications_history_clear() {
+    get_notification_manager().clear_history();
+}
+
+#[tauri::command(async)]
+pub fn notifications_get_focus_mode() -> FocusModeState {
+    get_notification_manager();
+    get_focus_mode()
+}
+
+#[tauri::command(async)]
+pub fn notifications_set_focus_mode(id: Option<Uuid>) {
+    get_notification_manager();
+    set_manual_focus_mode(id);
+}
@@ -1,3 +1,8 @@
 mod application;
+pub mod cli;
 mod domain;
+pub mod focus;
+mod grouping;
+mod history;
 pub mod infrastructure;
+mod widgets;
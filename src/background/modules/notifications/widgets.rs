@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use seelen_core::{
+    chrono::Utc,
+    handlers::SeelenEvent,
+    resource::WidgetId,
+    system_state::{
+        file_time_from_unix_millis, AppNotification, WidgetNotification,
+        WidgetNotificationActivated,
+    },
+};
+use tauri::Emitter;
+
+use crate::{
+    app::get_app_handle, error::Result, resources::RESOURCES, state::application::FULL_STATE,
+    widgets::manager::WIDGET_MANAGER,
+};
+
+use super::application::{NotificationEvent, NotificationManager};
+
+/// ids of system notifications are assigned by windows, so we use the upper half for widgets
+static NEXT_WIDGET_NOTIFICATION_ID: AtomicU32 = AtomicU32::new(u32::MAX / 2);
+/// used as app of the notifications posted by Seelen UI itself, activations of these
+/// are handled by `activate_own_notification` instead of the system toast activator.
+pub(super) const SEELEN_NOTIFICATIONS_UMID: &str = "Seelen UI";
+
+impl NotificationManager {
+    /// Whether the notification was posted by a widget or by Seelen UI itself,
+    /// these are not known by the system notifications listener.
+    pub fn is_own_notification(&self, id: u32) -> bool {
+        self.notifications
+            .get(&id, |n| {
+                n.widget_id.is_some() || n.app_umid == SEELEN_NOTIFICATIONS_UMID
+            })
+            .unwrap_or(false)
+    }
+
+    /// Adds a notification on behalf of the widget, returns the id of the notification.
+    pub fn post_widget_notification(
+        &self,
+        widget_id: &WidgetId,
+        notification: WidgetNotification,
+    ) -> Result<u32> {
+        let widget = RESOURCES
+            .widgets
+            .get(widget_id)
+            .ok_or("Widget not found")?
+            .clone();
+
+        let app_name = widget
+            .metadata
+            .display_name
+            .get(FULL_STATE.load().locale())
+            .to_owned();
+
+        Ok(self.post_native_notification(
+            widget_id.to_string(),
+            app_name,
+            Some(widget_id.clone()),
+            notification,
+        ))
+    }
+
+    /// Adds a notification on behalf of Seelen UI itself, e.g. system alerts.
+    pub fn post_seelen_notification(&self, notification: WidgetNotification) -> u32 {
+        self.post_native_notification(
+            SEELEN_NOTIFICATIONS_UMID.to_string(),
+            "Seelen UI".to_string(),
+            None,
+            notification,
+        )
+    }
+
+    fn post_native_notification(
+        &self,
+        app_umid: String,
+        app_name: String,
+        widget_id: Option<WidgetId>,
+        notification: WidgetNotification,
+    ) -> u32 {
+        let id = NEXT_WIDGET_NOTIFICATION_ID.fetch_add(1, Ordering::SeqCst);
+        let shown = self.add_notification(AppNotification {
+            id,
+            app_umid,
+            app_name,
+            app_description: String::new(),
+            date: file_time_from_unix_millis(Utc::now().timestamp_millis()),
+            silent: false,
+            widget_id,
+            content: notification.to_toast(id),
+        });
+        // notifications discarded by the rules are not announced
+        if shown {
+            Self::send(NotificationEvent::Posted(id));
+        }
+        id
+    }
+
+    /// Forwards the action click to the widget that posted the notification.
+    /// Returns false if the activation doesn't belong to a widget or Seelen UI notification.
+    pub fn activate_own_notification(
+        &self,
+        umid: &str,
+        args: &str,
+        inputs: HashMap<String, String>,
+    ) -> Result<bool> {
+        // notifications of Seelen UI itself, like system alerts, have no actions
+        if umid == SEELEN_NOTIFICATIONS_UMID {
+            return Ok(true);
+        }
+
+        let Some(activation) = WidgetNotificationActivated::decode(args, inputs) else {
+            return Ok(false);
+        };
+
+        let Some(widget_id) = self
+            .notifications
+            .get(&activation.notification_id, |n| n.widget_id.clone())
+            .flatten()
+            .filter(|id| id.to_string() == umid)
+        else {
+            return Ok(false);
+        };
+
+        for label in WIDGET_MANAGER.labels(&widget_id) {
+            get_app_handle().emit_to(
+                label.raw,
+                SeelenEvent::NotificationsWidgetActionActivated,
+                &activation,
+            )?;
+        }
+        Ok(true)
+    }
+}
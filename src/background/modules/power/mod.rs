@@ -1,3 +1,3 @@
-mod application;
+pub mod application;
 mod domain;
 pub mod infrastructure;
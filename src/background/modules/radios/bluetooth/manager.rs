@@ -1,8 +1,12 @@
 use std::sync::{Arc, LazyLock};
 
 use arc_swap::ArcSwapOption;
-use seelen_core::system_state::{
-    BluetoothDevice as SerializableBluetoothDevice, DevicePairingAnswer, DevicePairingNeededAction,
+use seelen_core::{
+    state::AudioRuleEvent,
+    system_state::{
+        BluetoothDevice as SerializableBluetoothDevice, DevicePairingAnswer,
+        DevicePairingNeededAction,
+    },
 };
 use tokio::sync::mpsc;
 use windows::{
@@ -21,6 +25,7 @@ use windows::{
 use crate::{
     error::{Result, ResultLogExt},
     event_manager, get_tokio_handle, log_error,
+    modules::media::devices::DevicesManager,
     utils::lock_free::SyncHashMap,
     windows_api::{DeviceEnumerator, DeviceEvent, DeviceId},
 };
@@ -156,18 +161,29 @@ impl BluetoothManager {
                     self.le_devices.upsert(id.clone(), wrapper);
                 }
             },
-            BluetoothManagerEvent::DeviceUpdated(id, device_type) => match device_type {
-                BluetoothDeviceType::Classic => {
-                    self.devices.get(id, |device| {
+            BluetoothManagerEvent::DeviceUpdated(id, device_type) => {
+                // name of the device if it was connected by this update
+                let connected = match device_type {
+                    BluetoothDeviceType::Classic => self.devices.get(id, |device| {
+                        let was_connected = device.state.connected;
                         device.refresh_state().log_error();
-                    });
-                }
-                BluetoothDeviceType::LowEnergy => {
-                    self.le_devices.get(id, |device| {
+                        (!was_connected && device.state.connected)
+                            .then(|| device.state.name.clone())
+                    }),
+                    BluetoothDeviceType::LowEnergy => self.le_devices.get(id, |device| {
+                        let was_connected = device.state.connected;
                         device.refresh_state().log_error();
+                        (!was_connected && device.state.connected)
+                            .then(|| device.state.name.clone())
+                    }),
+                };
+                if let Some(name) = connected.flatten() {
+                    DevicesManager::on_audio_rule_event(&AudioRuleEvent::BluetoothConnected {
+                        id,
+                        name: &name,
                     });
                 }
-            },
+            }
             BluetoothManagerEvent::DeviceRemoved(id, device_type) => {
                 self.pending_pair_requests.remove(id);
                 match device_type {
@@ -7,7 +7,8 @@ use notify_debouncer_full::{
     notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
     DebounceEventResult, DebouncedEvent, Debouncer, FileIdMap,
 };
-use seelen_core::system_state::StartMenuItem;
+use parking_lot::Mutex;
+use seelen_core::system_state::{AppSearchData, AppSearchIndex, StartMenuItem};
 use windows::{
     ApplicationModel::PackageCatalog,
     Foundation::TypedEventHandler,
@@ -25,6 +26,10 @@ use crate::{
 
 pub struct StartMenuManager {
     pub list: SyncVec<Arc<StartMenuItem>>,
+    /// launches and aliases used to rank the search results
+    pub(super) search_data: Mutex<AppSearchData>,
+    /// lazily built from `list`
+    pub(super) search_index: Mutex<Option<AppSearchIndex>>,
     cache_path: PathBuf,
     _file_watcher: Option<Arc<Debouncer<ReadDirectoryChangesWatcher, FileIdMap>>>,
     _package_catalog: Option<PackageCatalog>,
@@ -57,6 +62,8 @@ impl StartMenuManager {
     fn new() -> StartMenuManager {
         StartMenuManager {
             list: SyncVec::new(),
+            search_data: Mutex::new(Self::load_search_data()),
+            search_index: Mutex::new(None),
             cache_path: SEELEN_COMMON.app_cache_dir().join("start_menu_v2.json"),
             _file_watcher: None,
             _package_catalog: None,
@@ -81,6 +88,7 @@ impl StartMenuManager {
                         let menu = StartMenuManager::instance();
                         if let Ok(items) = Self::load_start_menu_items() {
                             menu.list.replace(items);
+                            menu.invalidate_search_index();
                             menu.store_cache().log_error();
                         };
                     });
@@ -265,6 +273,7 @@ impl StartMenuManager {
         }
 
         manager.list.replace(new_items);
+        manager.invalidate_search_index();
         manager.store_cache().log_error();
 
         Ok(())
@@ -331,6 +340,7 @@ impl StartMenuManager {
         }
 
         manager.list.replace(new_items);
+        manager.invalidate_search_index();
         manager.store_cache().log_error();
 
         Ok(())
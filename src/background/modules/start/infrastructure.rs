@@ -28,3 +28,13 @@ pub fn get_start_menu_items() -> Result<Vec<StartMenuItem>> {
         .map(|item| (*item).clone())
         .collect())
 }
+
+#[tauri::command(async)]
+pub fn start_menu_search(query: String, limit: Option<usize>) -> Vec<StartMenuItem> {
+    get_start_menu_manager().search(&query, limit.unwrap_or(usize::MAX))
+}
+
+#[tauri::command(async)]
+pub fn start_menu_set_item_aliases(item: StartMenuItem, aliases: Vec<String>) {
+    get_start_menu_manager().set_item_aliases(&item, aliases);
+}
@@ -0,0 +1,94 @@
+use std::{fs::File, path::Path, path::PathBuf, sync::LazyLock, time::Duration};
+
+use seelen_core::{
+    chrono::Utc,
+    system_state::{AppSearchData, AppSearchIndex, StartMenuItem},
+};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    error::Result,
+    utils::{constants::SEELEN_COMMON, Debouncer},
+};
+
+use super::application::StartMenuManager;
+
+impl StartMenuManager {
+    fn search_data_path() -> PathBuf {
+        SEELEN_COMMON.app_data_dir().join("start_menu_search.json")
+    }
+
+    pub(super) fn load_search_data() -> AppSearchData {
+        let load = || -> Result<AppSearchData> {
+            let file = File::open(Self::search_data_path())?;
+            Ok(serde_json::from_reader(file)?)
+        };
+        load().unwrap_or_default()
+    }
+
+    fn request_search_data_save(&self) {
+        static SAVE_DEBOUNCER: LazyLock<Debouncer> =
+            LazyLock::new(|| Debouncer::new(Duration::from_secs(2)));
+
+        SAVE_DEBOUNCER.call(async move || {
+            let data = StartMenuManager::instance().search_data.lock().clone();
+            let mut file = tokio::fs::File::create(Self::search_data_path()).await?;
+            file.write_all(&serde_json::to_vec(&data)?).await?;
+            file.flush().await?;
+            Result::Ok(())
+        });
+    }
+
+    /// Should be called each time the items or the aliases change.
+    pub(super) fn invalidate_search_index(&self) {
+        self.search_index.lock().take();
+    }
+
+    /// Items matching the query, ranked by match quality and frecency.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<StartMenuItem> {
+        self.search_scored(query, limit)
+            .into_iter()
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    pub fn search_scored(&self, query: &str, limit: usize) -> Vec<(StartMenuItem, f64)> {
+        let data = self.search_data.lock();
+        let mut index = self.search_index.lock();
+        let index = index.get_or_insert_with(|| {
+            let items: Vec<StartMenuItem> = self.list.map(|item| (**item).clone());
+            AppSearchIndex::new(&items, &data)
+        });
+        index.search_scored(query, &data, Utc::now().timestamp_millis(), limit)
+    }
+
+    /// Records the launch of the start menu item opened by the program, if any.
+    pub fn record_launch(&self, program: &Path) {
+        let Some(item) = self.list.find(|item| item.is_launched_by(program)) else {
+            return;
+        };
+        self.search_data
+            .lock()
+            .record_launch(item.search_key(), Utc::now().timestamp_millis());
+        self.request_search_data_save();
+    }
+
+    pub fn set_item_aliases(&self, item: &StartMenuItem, aliases: Vec<String>) {
+        let aliases: Vec<String> = aliases
+            .into_iter()
+            .map(|alias| alias.trim().to_string())
+            .filter(|alias| !alias.is_empty())
+            .collect();
+
+        {
+            let mut data = self.search_data.lock();
+            if aliases.is_empty() {
+                data.aliases.remove(&item.search_key());
+            } else {
+                data.aliases.insert(item.search_key(), aliases);
+            }
+        }
+        self.invalidate_search_index();
+        self.request_search_data_save();
+    }
+}
@@ -0,0 +1,103 @@
+use seelen_core::{
+    chrono::Utc,
+    state::{
+        CssStyles, SluPopupConfig, SluPopupContent, SystemAlertAction, SystemAlertMetric,
+        SystemAlertRule,
+    },
+    system_state::{Core, Disk, Memory, SystemAlert, SystemAlertInput, WidgetNotification},
+};
+
+use crate::{
+    error::Result,
+    log_error,
+    modules::{
+        notifications::infrastructure::post_seelen_notification, power::application::PowerManager,
+    },
+    state::application::FULL_STATE,
+    widgets::popups::POPUPS_MANAGER,
+};
+
+use super::{SystemInfo, SystemInfoEvent};
+
+impl SystemInfo {
+    pub(super) fn check_alerts(&self, cores: &[Core], memory: &Memory, disks: &[Disk]) {
+        let state = FULL_STATE.load();
+        let rules = &state.settings.system_metrics.alerts;
+
+        let mut tracker = self.alerts.lock();
+        if rules.is_empty() && tracker.active().is_empty() {
+            return;
+        }
+
+        // avoid loading the power manager if no rule needs it
+        let uses_battery = rules.iter().any(|r| {
+            matches!(
+                r.metric,
+                SystemAlertMetric::BatteryPercentage | SystemAlertMetric::BatteryTimeToEmpty
+            )
+        });
+        let batteries = if uses_battery {
+            PowerManager::instance().lock().batteries.clone()
+        } else {
+            Vec::new()
+        };
+
+        let input = SystemAlertInput::new(cores, memory, disks, &batteries);
+        let active_before: Vec<_> = tracker.active().iter().map(|a| a.rule_id).collect();
+        let fired = tracker.evaluate(rules, &input, Utc::now().timestamp_millis());
+        let active_changed = tracker
+            .active()
+            .iter()
+            .map(|a| a.rule_id)
+            .ne(active_before.into_iter());
+        drop(tracker);
+
+        for alert in fired {
+            if let Some(rule) = rules.iter().find(|r| r.id == alert.rule_id) {
+                log::info!("System alert fired: {} ({})", rule.name, alert.value);
+                log_error!(show_alert(rule, &alert));
+            }
+        }
+        if active_changed {
+            Self::send(SystemInfoEvent::AlertsChanged);
+        }
+    }
+}
+
+fn show_alert(rule: &SystemAlertRule, alert: &SystemAlert) -> Result<()> {
+    let value = alert.metric.format_value(alert.value);
+    let body = t!("system_alert.current_value", value = value).to_string();
+
+    match rule.action {
+        SystemAlertAction::Notification => {
+            post_seelen_notification(WidgetNotification {
+                title: rule.name.clone(),
+                body,
+                image: None,
+                actions: Vec::new(),
+            });
+        }
+        SystemAlertAction::Popup => {
+            let text = |value: String| SluPopupContent::Text {
+                value,
+                styles: None,
+            };
+            POPUPS_MANAGER.lock().create(SluPopupConfig {
+                title: vec![text(rule.name.clone())],
+                content: vec![text(body)],
+                footer: vec![SluPopupContent::Button {
+                    inner: vec![text(t!("system_alert.ok").to_string())],
+                    on_click: "exit".to_string(),
+                    styles: Some(
+                        CssStyles::new()
+                            .add("backgroundColor", "var(--color-blue-700)")
+                            .add("color", "var(--color-white)"),
+                    ),
+                }],
+                ..Default::default()
+            })?;
+        }
+        SystemAlertAction::None => {}
+    }
+    Ok(())
+}
@@ -1,12 +1,20 @@
+mod alerts;
+mod processes;
 pub mod tauri;
 
-use std::sync::LazyLock;
+use std::{collections::HashMap, sync::LazyLock};
 
-use seelen_core::system_state::{Core, Disk, Memory, NetworkStatistics};
+use seelen_core::{
+    chrono::Utc,
+    system_state::{
+        Core, Disk, Memory, MetricsHistory, NetworkStatistics, SystemAlertsTracker, SystemMetric,
+    },
+};
 
 use crate::{
     error::{Result, ResultLogExt},
     event_manager,
+    state::application::FULL_STATE,
     utils::lock_free::TracedMutex,
 };
 
@@ -19,6 +27,11 @@ pub struct SystemInfo {
     pub last_networks: TracedMutex<Vec<NetworkStatistics>>,
     pub last_memory: TracedMutex<Memory>,
     pub last_cores: TracedMutex<Vec<Core>>,
+    /// samples of all the metrics, so widgets can draw graphs from the moment they start
+    pub history: TracedMutex<MetricsHistory>,
+    pub alerts: TracedMutex<SystemAlertsTracker>,
+    /// app user model ids by process id and start time
+    process_umids: TracedMutex<HashMap<(u32, u64), Option<String>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +41,7 @@ pub enum SystemInfoEvent {
     NetworkChanged,
     MemoryChanged,
     CoresChanged,
+    AlertsChanged,
 }
 
 event_manager!(SystemInfo, SystemInfoEvent);
@@ -52,6 +66,11 @@ impl SystemInfo {
             last_networks: TracedMutex::new(Vec::new()),
             last_memory: TracedMutex::new(Memory::default()),
             last_cores: TracedMutex::new(Vec::new()),
+            history: TracedMutex::new(MetricsHistory::new(
+                &FULL_STATE.load().settings.system_metrics,
+            )),
+            alerts: TracedMutex::new(SystemAlertsTracker::default()),
+            process_umids: TracedMutex::new(HashMap::new()),
         }
     }
 
@@ -72,9 +91,30 @@ impl SystemInfo {
     }
 
     fn check_and_emit_changes(&self) {
+        let current_disks = self.disks();
+        let current_network = self.network();
+        let current_memory = self.memory();
+        let current_cores = self.cores();
+
+        {
+            let mut history = self.history.lock();
+            history.configure(&FULL_STATE.load().settings.system_metrics);
+            let now = Utc::now().timestamp_millis();
+            let values = SystemMetric::values(
+                &current_cores,
+                &current_memory,
+                &current_disks,
+                &current_network,
+            );
+            for (metric, value) in values {
+                history.record(metric, now, value);
+            }
+        }
+
+        self.check_alerts(&current_cores, &current_memory, &current_disks);
+
         // Check disks (compare available_space)
         {
-            let current_disks = self.disks();
             let mut last_disks = self.last_disks.lock();
             if Self::disks_changed(&last_disks, &current_disks) {
                 *last_disks = current_disks;
@@ -84,7 +124,6 @@ impl SystemInfo {
 
         // Check network (compare received and transmitted)
         {
-            let current_network = self.network();
             let mut last_network = self.last_networks.lock();
             if Self::network_changed(&last_network, &current_network) {
                 *last_network = current_network;
@@ -93,7 +132,6 @@ impl SystemInfo {
         }
 
         {
-            let current_memory = self.memory();
             let mut last_memory = self.last_memory.lock();
             if Self::memory_changed(&last_memory, &current_memory) {
                 *last_memory = current_memory;
@@ -102,7 +140,6 @@ impl SystemInfo {
         }
 
         {
-            let current_cores = self.cores();
             let mut last_cores = self.last_cores.lock();
             if Self::cores_changed(&last_cores, &current_cores) {
                 *last_cores = current_cores;
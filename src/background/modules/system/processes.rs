@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use seelen_core::system_state::{
+    process_tree, AppResourceUsage, ProcessNode, ProcessUsage, ProcessUsageQuery,
+};
+use sysinfo::{Pid, ProcessesToUpdate};
+use windows::Win32::System::Threading::BELOW_NORMAL_PRIORITY_CLASS;
+
+use crate::{
+    error::Result,
+    utils::icon_extractor::{request_icon_extraction_from_file, request_icon_extraction_from_umid},
+    windows_api::{process::Process, WindowsApi},
+};
+
+use super::SystemInfo;
+
+impl SystemInfo {
+    /// Processes grouped by app, cpu usage is relative to the previous call.
+    pub fn processes_usage(&self, query: &ProcessUsageQuery) -> Vec<AppResourceUsage> {
+        let mut processes: Vec<(ProcessUsage, u64)> = {
+            let mut system = self.system.lock();
+            system.refresh_processes(ProcessesToUpdate::All, true);
+            let cpus = system.cpus().len().max(1) as f32;
+
+            system
+                .processes()
+                .values()
+                .map(|process| {
+                    let id = process.pid().as_u32();
+                    let name = process.name().to_string_lossy().to_string();
+                    let disk = process.disk_usage();
+                    let usage = ProcessUsage {
+                        id,
+                        parent_id: process.parent().map(|p| p.as_u32()),
+                        protected: ProcessUsage::is_protected(id, &name),
+                        name,
+                        path: process.exe().map(|p| p.to_path_buf()),
+                        umid: None,
+                        cpu_usage: process.cpu_usage() / cpus,
+                        memory: process.memory(),
+                        disk_read: disk.read_bytes,
+                        disk_written: disk.written_bytes,
+                    };
+                    (usage, process.start_time())
+                })
+                .collect()
+        };
+
+        // resolved out of the system lock and only once per process, as it opens the process
+        {
+            let mut cache = self.process_umids.lock();
+            let mut alive = HashMap::with_capacity(processes.len());
+            for (process, start_time) in &mut processes {
+                let key = (process.id, *start_time);
+                let umid = cache.remove(&key).unwrap_or_else(|| {
+                    Process::from_id(process.id)
+                        .package_app_user_model_id()
+                        .ok()
+                        .map(|umid| umid.to_string())
+                });
+                process.umid.clone_from(&umid);
+                alive.insert(key, umid);
+            }
+            // ended processes are dropped from the cache
+            *cache = alive;
+        }
+
+        let apps = query.apply(processes.into_iter().map(|(p, _)| p).collect());
+        // pre-extraction so the icons are available on the icon pack
+        for app in &apps {
+            match (&app.umid, &app.path) {
+                (Some(umid), _) => request_icon_extraction_from_umid(&umid.clone().into()),
+                (None, Some(path)) => request_icon_extraction_from_file(path),
+                (None, None) => {}
+            }
+        }
+        apps
+    }
+
+    /// Ends the process and its descendants, protected descendants are skipped.
+    pub fn end_process_tree(&self, process_id: u32) -> Result<()> {
+        let mut system = self.system.lock();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+
+        let is_protected = |id: u32| {
+            system
+                .process(Pid::from_u32(id))
+                .is_some_and(|p| ProcessUsage::is_protected(id, &p.name().to_string_lossy()))
+        };
+        if system.process(Pid::from_u32(process_id)).is_none() {
+            return Err(format!("process not found: {process_id}").into());
+        }
+        if is_protected(process_id) {
+            return Err(format!("process {process_id} is protected").into());
+        }
+
+        let relations: Vec<_> = system
+            .processes()
+            .values()
+            .map(|p| ProcessNode {
+                id: p.pid().as_u32(),
+                parent_id: p.parent().map(|parent| parent.as_u32()),
+                start_time: p.start_time(),
+            })
+            .collect();
+        for id in process_tree(process_id, &relations) {
+            if is_protected(id) {
+                continue;
+            }
+            if let Some(process) = system.process(Pid::from_u32(id)) {
+                process.kill();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn lower_process_priority(&self, process_id: u32) -> Result<()> {
+        let name = Process::from_id(process_id).program_exe_name()?;
+        if ProcessUsage::is_protected(process_id, &name) {
+            return Err(format!("process {process_id} is protected").into());
+        }
+        WindowsApi::set_process_priority(process_id, BELOW_NORMAL_PRIORITY_CLASS)
+    }
+}
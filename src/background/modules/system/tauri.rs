@@ -2,11 +2,15 @@ use std::sync::Once;
 
 use seelen_core::{
     handlers::SeelenEvent,
-    system_state::{Core, Disk, Memory, NetworkStatistics},
+    system_state::{
+        AppResourceUsage, Core, Disk, Memory, MetricBucket, MetricsHistoryQuery, NetworkStatistics,
+        ProcessUsageQuery, SystemAlert,
+    },
 };
 
 use crate::{
     app::emit_to_webviews,
+    error::Result,
     modules::system::{SystemInfo, SystemInfoEvent},
 };
 
@@ -40,6 +44,12 @@ fn get_system_info() -> &'static SystemInfo {
                     &*SystemInfo::instance().last_cores.lock(),
                 );
             }
+            SystemInfoEvent::AlertsChanged => {
+                emit_to_webviews(
+                    SeelenEvent::SystemAlertsChanged,
+                    SystemInfo::instance().alerts.lock().active(),
+                );
+            }
         });
     });
 
@@ -65,3 +75,30 @@ pub fn get_system_memory() -> Memory {
 pub fn get_system_cores() -> Vec<Core> {
     get_system_info().last_cores.lock().clone()
 }
+
+#[tauri::command(async)]
+pub fn get_system_metrics_history(query: MetricsHistoryQuery) -> Vec<MetricBucket> {
+    get_system_info().history.lock().query(&query)
+}
+
+/// Alerts whose threshold is currently crossed, to be shown as badges.
+#[tauri::command(async)]
+pub fn get_system_alerts() -> Vec<SystemAlert> {
+    get_system_info().alerts.lock().active().to_vec()
+}
+
+/// Top processes grouped by app, cpu usage is relative to the previous call.
+#[tauri::command(async)]
+pub fn get_system_processes(query: ProcessUsageQuery) -> Vec<AppResourceUsage> {
+    get_system_info().processes_usage(&query)
+}
+
+#[tauri::command(async)]
+pub fn system_end_process_tree(process_id: u32) -> Result<()> {
+    get_system_info().end_process_tree(process_id)
+}
+
+#[tauri::command(async)]
+pub fn system_lower_process_priority(process_id: u32) -> Result<()> {
+    get_system_info().lower_process_priority(process_id)
+}
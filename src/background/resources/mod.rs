@@ -2,6 +2,7 @@ pub mod cli;
 pub mod commands;
 mod emitters;
 mod system_icon_pack;
+mod wallpaper_folders;
 
 use std::{
     path::{Path, PathBuf},
@@ -36,6 +37,8 @@ pub struct ResourceManager {
     pub system_icon_pack: Arc<TracedMutex<Option<IconPack>>>,
     /// list of manual loaded resources
     pub manual: scc::HashSet<PathBuf>,
+    /// watcher of the folders used by smart wallpaper collections
+    wallpaper_folders: TracedMutex<Option<wallpaper_folders::WallpaperFoldersWatcher>>,
 }
 
 impl ResourceManager {
@@ -0,0 +1,234 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, LazyLock,
+    },
+    time::Duration,
+};
+
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use seelen_core::{
+    resource::WallpaperId,
+    state::{Wallpaper, WallpaperCollection},
+};
+
+use crate::{
+    error::{Result, ResultLogExt},
+    utils::{constants::SEELEN_COMMON, date_based_hex_id, lock_free::TracedMutex},
+};
+
+use super::{ResourceManager, RESOURCES};
+
+/// Files imported from the watched folders, kept after the user deletes the imported
+/// wallpaper so it is not imported again.
+static IMPORTED_FROM_FOLDERS: LazyLock<TracedMutex<ImportedFromFolders>> = LazyLock::new(|| {
+    TracedMutex::new(ImportedFromFolders {
+        files: load_imported_from_folders(),
+        importing: HashSet::new(),
+    })
+});
+
+/// Suffix of the folders of the imported wallpapers, as ids based on milliseconds are
+/// repeated on bulk imports.
+static IMPORT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+struct ImportedFromFolders {
+    files: HashSet<PathBuf>,
+    /// Files claimed by an import in progress, so the initial scan and the watcher
+    /// can't import the same file twice.
+    importing: HashSet<PathBuf>,
+}
+
+fn imported_from_folders_path() -> PathBuf {
+    SEELEN_COMMON
+        .app_data_dir()
+        .join("wallpaper_folders_imported.json")
+}
+
+fn load_imported_from_folders() -> HashSet<PathBuf> {
+    let load = || -> Result<HashSet<PathBuf>> {
+        let file = File::open(imported_from_folders_path())?;
+        Ok(serde_json::from_reader(file)?)
+    };
+    load().unwrap_or_default()
+}
+
+fn save_imported_from_folders(imported: &HashSet<PathBuf>) -> Result<()> {
+    let file = File::create(imported_from_folders_path())?;
+    serde_json::to_writer(file, imported)?;
+    Ok(())
+}
+
+/// Watcher of the folders used by smart wallpaper collections.
+pub struct WallpaperFoldersWatcher {
+    folders: HashSet<PathBuf>,
+    _debouncer: Debouncer<ReadDirectoryChangesWatcher, FileIdMap>,
+}
+
+impl ResourceManager {
+    /// Returns the wallpapers of the collection, including the ones matched by its rule.
+    pub fn resolve_collection(&self, collection: &WallpaperCollection) -> Vec<WallpaperId> {
+        let mut available = Vec::new();
+        self.wallpapers.scan(|_, v| {
+            available.push(v.clone());
+        });
+        collection.resolve(available.iter().map(|w| w.as_ref()))
+    }
+
+    /// Starts watching the folders of the folder based collections, stopping the old ones.
+    /// Files not imported yet are imported in background.
+    pub fn watch_wallpaper_folders(&self, collections: &[WallpaperCollection]) -> Result<()> {
+        let folders: HashSet<PathBuf> = collections
+            .iter()
+            .filter_map(|c| c.rule.as_ref()?.folder().cloned())
+            .collect();
+
+        let mut guard = self.wallpaper_folders.lock();
+        if guard.as_ref().is_some_and(|w| w.folders == folders) {
+            return Ok(());
+        }
+
+        if folders.is_empty() {
+            *guard = None;
+            return Ok(());
+        }
+
+        log::trace!("Watching wallpaper folders: {folders:?}");
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            |result: DebounceEventResult| match result {
+                Ok(events) => {
+                    let paths: HashSet<PathBuf> = events
+                        .into_iter()
+                        .flat_map(|e| e.event.paths)
+                        .filter(|p| p.is_file())
+                        .collect();
+                    RESOURCES.import_wallpapers_from_folder(paths);
+                }
+                Err(errors) => errors
+                    .iter()
+                    .for_each(|e| log::error!("Wallpaper Folders Watcher Error: {e:?}")),
+            },
+        )?;
+
+        for folder in &folders {
+            debouncer
+                .watcher()
+                .watch(folder, RecursiveMode::Recursive)
+                .log_error();
+        }
+
+        let to_scan = folders.clone();
+        std::thread::spawn(move || {
+            let files = to_scan.iter().flat_map(|folder| {
+                walkdir::WalkDir::new(folder)
+                    .into_iter()
+                    .flatten()
+                    .filter(|e| e.file_type().is_file())
+                    .map(|e| e.into_path())
+            });
+            RESOURCES.import_wallpapers_from_folder(files);
+        });
+
+        *guard = Some(WallpaperFoldersWatcher {
+            folders,
+            _debouncer: debouncer,
+        });
+        Ok(())
+    }
+
+    /// Imports the files, saving the list of imported files once at the end, and notifies
+    /// the webviews if any of them was imported.
+    fn import_wallpapers_from_folder(&self, paths: impl IntoIterator<Item = PathBuf>) {
+        let mut changed = false;
+        let mut imported = false;
+        for path in paths {
+            let result = self.import_wallpaper_from_folder(&path);
+            if matches!(result, Ok(ImportResult::Skipped)) {
+                continue;
+            }
+
+            // release the claim taken by the import
+            let mut guard = IMPORTED_FROM_FOLDERS.lock();
+            guard.importing.remove(&path);
+            match result {
+                Ok(ImportResult::Skipped) => {}
+                Ok(ImportResult::AlreadyImported) => {
+                    changed |= guard.files.insert(path);
+                }
+                Ok(ImportResult::Imported) => {
+                    guard.files.insert(path);
+                    changed = true;
+                    imported = true;
+                }
+                Err(err) => log::error!("Failed to import wallpaper {path:?}: {err}"),
+            }
+        }
+
+        if changed {
+            // saved under the lock so concurrent batches can't overwrite it with an older list
+            save_imported_from_folders(&IMPORTED_FROM_FOLDERS.lock().files).log_error();
+        }
+        if imported {
+            self.emit_wallpapers().log_error();
+        }
+    }
+
+    /// Imports the file as a wallpaper if it is supported and was not imported before,
+    /// the file is copied so the watched folder is never modified.\
+    /// The file is claimed while importing, the caller must release it.
+    fn import_wallpaper_from_folder(&self, path: &Path) -> Result<ImportResult> {
+        let Some(extension) = path.extension() else {
+            return Ok(ImportResult::Skipped);
+        };
+        let extension = extension.to_string_lossy().to_lowercase();
+        if !Wallpaper::SUPPORTED_IMAGES.contains(&extension.as_ref())
+            && !Wallpaper::SUPPORTED_VIDEOS.contains(&extension.as_ref())
+        {
+            return Ok(ImportResult::Skipped);
+        }
+
+        {
+            let mut guard = IMPORTED_FROM_FOLDERS.lock();
+            if guard.files.contains(path) || !guard.importing.insert(path.to_path_buf()) {
+                return Ok(ImportResult::Skipped);
+            }
+        }
+
+        // imported before the list of imported files existed
+        if self
+            .wallpapers
+            .any(|_, v| v.origin.as_deref() == Some(path))
+        {
+            return Ok(ImportResult::AlreadyImported);
+        }
+
+        let folder_to_store = SEELEN_COMMON.user_wallpapers_path().join(format!(
+            "{}_{}",
+            date_based_hex_id(),
+            IMPORT_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        log::info!("Importing wallpaper from watched folder: {path:?}");
+        let wallpaper = Wallpaper::create_from_file(path, &folder_to_store, true)?;
+        self.wallpapers
+            .upsert(wallpaper.id.clone(), Arc::new(wallpaper));
+        Ok(ImportResult::Imported)
+    }
+}
+
+enum ImportResult {
+    /// Not supported, already imported or being imported by another scan, nothing was claimed.
+    Skipped,
+    /// Imported before the list of imported files existed.
+    AlreadyImported,
+    Imported,
+}
@@ -26,8 +26,11 @@ use std::{
 };
 
 use crate::{
-    error::Result, log_error, resources::RESOURCES, utils::constants::SEELEN_COMMON,
-    widgets::popups::POPUPS_MANAGER,
+    error::Result,
+    log_error,
+    resources::RESOURCES,
+    utils::constants::SEELEN_COMMON,
+    widgets::{hot_reload::hot_reload_widgets, popups::POPUPS_MANAGER},
 };
 
 use super::domain::{Settings, ToolbarState};
@@ -167,12 +170,30 @@ impl FullState {
             RESOURCES.emit_plugins()?;
         }
 
+        let widgets_reloaded = widgets_changed;
+        // on development the user widgets are reloaded one by one, keeping the instances alive
+        if widgets_changed && self.settings.dev_tools {
+            let user_widgets: HashSet<PathBuf> = changed
+                .iter()
+                .filter(|path| path.starts_with(SEELEN_COMMON.user_widgets_path()))
+                .cloned()
+                .collect();
+            hot_reload_widgets(&user_widgets)?;
+            widgets_changed = changed
+                .iter()
+                .any(|path| path.starts_with(SEELEN_COMMON.bundled_widgets_path()));
+        }
+
         if widgets_changed {
             log::info!("Widgets changed");
             RESOURCES.load_all_of_type(ResourceKind::Widget)?;
             RESOURCES.emit_widgets()?;
         }
 
+        if widgets_reloaded && self.sanitize_widget_settings() {
+            self.emit_settings()?;
+        }
+
         if wallpapers_changed {
             log::info!("Wallpapers changed");
             RESOURCES.load_all_of_type(ResourceKind::Wallpaper)?;
@@ -2,7 +2,7 @@ use seelen_core::{handlers::SeelenEvent, state::Settings};
 
 use crate::{
     app::{emit_to_webviews, SEELEN},
-    error::Result,
+    error::{Result, ResultLogExt},
     resources::RESOURCES,
     trace_lock,
     utils::constants::SEELEN_COMMON,
@@ -26,6 +26,13 @@ impl FullState {
             self.settings = Settings::load(path)?;
             self.migration_v2_5_0()?;
             self.sanitize_wallpaper_collections();
+            self.sanitize_widget_settings();
+            for error in self.settings.notifications.compile_rules() {
+                log::warn!("Invalid notification rule text: {error}");
+            }
+            RESOURCES
+                .watch_wallpaper_folders(&self.settings.wallpaper_collections)
+                .log_error();
         } else {
             self.write_settings()?; // create initial settings file
         }
@@ -105,6 +112,29 @@ impl FullState {
         changed
     }
 
+    /// Fix the stored settings of the third party widgets that don't match their declarations
+    pub(super) fn sanitize_widget_settings(&mut self) -> bool {
+        let mut widgets = Vec::new();
+        RESOURCES
+            .widgets
+            .scan(|_, widget| widgets.push(widget.clone()));
+
+        let fixes = self
+            .settings
+            .sanitize_widget_settings(widgets.iter().map(|widget| widget.as_ref()));
+        for fix in &fixes {
+            log::warn!(
+                "Invalid setting \"{}\" of widget {}: {:?} {:?} -> {:?}",
+                fix.key,
+                fix.widget_id,
+                fix.action,
+                fix.previous.0,
+                fix.value.as_ref().map(|value| &value.0),
+            );
+        }
+        !fixes.is_empty()
+    }
+
     pub(super) fn read_settings(&mut self) {
         if let Err(err) = self._read_settings() {
             log::error!("Failed to read settings: {err}");
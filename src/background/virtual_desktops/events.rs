@@ -1,4 +1,7 @@
-use seelen_core::{state::WorkspaceId, system_state::MonitorId};
+use seelen_core::{
+    state::{WorkspaceId, WorkspaceSwitchDirection},
+    system_state::MonitorId,
+};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -8,6 +11,8 @@ pub enum VirtualDesktopEvent {
     DesktopChanged {
         monitor: MonitorId,
         workspace: WorkspaceId,
+        previous: WorkspaceId,
+        direction: Option<WorkspaceSwitchDirection>,
     },
     /// Emitted when the virtual desktops state changes (e.g., wallpapers updated)
     StateChanged,
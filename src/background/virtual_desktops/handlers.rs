@@ -1,21 +1,45 @@
+use std::collections::HashMap;
 use std::sync::Once;
 
 use seelen_core::{
     handlers::SeelenEvent,
     resource::{SluResource, WallpaperId},
-    state::{VirtualDesktops, Wallpaper},
+    state::{
+        VirtualDesktops, Wallpaper, WallpaperSpan, WorkspaceId, WorkspaceSnapshot, WorkspaceSwitch,
+    },
     system_state::MonitorId,
 };
 
 use crate::{
-    app::emit_to_webviews, error::Result, resources::RESOURCES, utils::date_based_hex_id,
-    virtual_desktops::SluWorkspacesManager2,
+    app::emit_to_webviews,
+    error::Result,
+    modules::monitors::infrastructure::get_connected_monitors,
+    resources::RESOURCES,
+    state::application::FULL_STATE,
+    utils::date_based_hex_id,
+    virtual_desktops::{events::VirtualDesktopEvent, SluWorkspacesManager2},
 };
 
 fn get_vd_manager() -> &'static SluWorkspacesManager2 {
     static TAURI_EVENT_REGISTRATION: Once = Once::new();
     TAURI_EVENT_REGISTRATION.call_once(|| {
-        SluWorkspacesManager2::subscribe(|_event| {
+        SluWorkspacesManager2::subscribe(|event| {
+            if let VirtualDesktopEvent::DesktopChanged {
+                monitor,
+                workspace,
+                previous,
+                direction,
+            } = event
+            {
+                let payload = WorkspaceSwitch {
+                    monitor,
+                    from: previous,
+                    to: workspace,
+                    direction,
+                };
+                emit_to_webviews(SeelenEvent::VirtualDesktopsSwitched, payload);
+            }
+
             let payload: VirtualDesktops = SluWorkspacesManager2::instance().into();
             emit_to_webviews(SeelenEvent::VirtualDesktopsChanged, payload);
         });
@@ -29,6 +53,11 @@ pub fn get_virtual_desktops() -> VirtualDesktops {
     get_vd_manager().into()
 }
 
+#[tauri::command(async)]
+pub fn get_workspaces_snapshots() -> HashMap<WorkspaceId, WorkspaceSnapshot> {
+    get_vd_manager().get_snapshots()
+}
+
 #[tauri::command(async)]
 pub fn switch_workspace(workspace_id: seelen_core::state::WorkspaceId) -> Result<()> {
     let manager = get_vd_manager();
@@ -86,3 +115,19 @@ pub fn wallpaper_save_thumbnail(wallpaper_id: WallpaperId, thumbnail_bytes: Vec<
     wallpaper_mut.save()?;
     Ok(())
 }
+
+#[tauri::command(async)]
+pub fn wallpaper_get_span(wallpaper_id: Option<WallpaperId>) -> Result<WallpaperSpan> {
+    let monitors = get_connected_monitors()?;
+    let offsets = wallpaper_id
+        .and_then(|id| {
+            FULL_STATE
+                .load()
+                .settings
+                .by_wallpaper
+                .get(&id)
+                .map(|config| config.monitor_offsets.clone())
+        })
+        .unwrap_or_default();
+    Ok(WallpaperSpan::compute(&monitors, &offsets))
+}
@@ -1,27 +1,38 @@
 pub mod cli;
 pub mod events;
 pub mod handlers;
+mod snapshots;
 pub mod wallpapers;
 
 use std::collections::HashMap;
 use std::fs::File;
 use std::sync::LazyLock;
 
-use seelen_core::state::{DesktopWorkspace, VirtualDesktopMonitor, VirtualDesktops, WorkspaceId};
+use seelen_core::state::{
+    AppExtraFlag, DesktopWorkspace, VirtualDesktopMonitor, VirtualDesktops, WorkspaceId,
+    WorkspaceSnapshot,
+};
 use seelen_core::system_state::MonitorId;
 use tokio::io::AsyncWriteExt;
-use windows::Win32::UI::WindowsAndMessaging::{SW_FORCEMINIMIZE, SW_MINIMIZE, SW_RESTORE};
+use windows::Win32::Foundation::RECT;
+use windows::Win32::UI::WindowsAndMessaging::{
+    SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, SW_FORCEMINIMIZE, SW_MINIMIZE,
+    SW_RESTORE,
+};
 
 use crate::error::{Result, ResultLogExt};
 use crate::hook::HookManager;
 use crate::modules::apps::application::{UserAppWinEvent, UserAppsManager};
 use crate::modules::monitors::{MonitorManager, MonitorManagerEvent};
+use crate::state::application::FULL_STATE;
 use crate::utils::constants::SEELEN_COMMON;
 use crate::utils::lock_free::{SyncHashMap, SyncVec};
 use crate::utils::Debouncer;
 use crate::virtual_desktops::wallpapers::WorkspaceWallpapersManager;
+use crate::windows_api::monitor::Monitor;
 use crate::windows_api::window::event::WinEvent;
 use crate::windows_api::window::Window;
+use crate::windows_api::MonitorEnumerator;
 use crate::{event_manager, log_error};
 
 use events::VirtualDesktopEvent;
@@ -37,6 +48,8 @@ pub struct SluWorkspacesManager2 {
     pub monitors: SyncHashMap<MonitorId, VirtualDesktopMonitor>,
     pub workspace_index: SyncHashMap<WorkspaceId, MonitorId>,
     pub pinned: SyncVec<isize>,
+    /// last known state of each workspace, used by the workspaces overview
+    pub snapshots: SyncHashMap<WorkspaceId, WorkspaceSnapshot>,
 }
 
 event_manager!(SluWorkspacesManager2, VirtualDesktopEvent);
@@ -143,7 +156,7 @@ impl SluWorkspacesManager2 {
 
         UserAppsManager::subscribe(|event| match event {
             UserAppWinEvent::Added(addr) => {
-                Self::instance().add_to_current_workspace(&Window::from(addr));
+                Self::instance().add_new_window(&Window::from(addr));
             }
             UserAppWinEvent::Removed(addr) => {
                 Self::instance().remove(&Window::from(addr));
@@ -209,7 +222,12 @@ impl SluWorkspacesManager2 {
             }
             WinEvent::SyntheticMonitorChanged => {
                 let manager = Self::instance();
-                if manager.contains(&window) && !manager.is_pinned(&window_id) {
+                // windows already placed on a workspace of their new monitor (e.g. moved by
+                // an app binding) are left untouched
+                if manager.contains(&window)
+                    && !manager.is_pinned(&window_id)
+                    && window.workspace_id().is_err()
+                {
                     manager.remove(&window);
                     manager.add_to_current_workspace(&window);
                 }
@@ -292,6 +310,110 @@ impl SluWorkspacesManager2 {
         }
     }
 
+    /// Adds a newly created window, honoring the monitor/workspace bindings of its app config.
+    fn add_new_window(&self, window: &Window) {
+        match self.add_to_bound_workspace(window) {
+            Ok(true) => {}
+            Ok(false) => self.add_to_current_workspace(window),
+            Err(err) => {
+                log::error!("Failed to apply workspace binding for {window}: {err}");
+                self.add_to_current_workspace(window);
+            }
+        }
+    }
+
+    /// Returns false if the window has no bindings so the caller can fallback to the default behavior.
+    fn add_to_bound_workspace(&self, window: &Window) -> Result<bool> {
+        let window_id = window.address();
+        let (bound_monitor, bound_workspace, bound_workspace_name, follow) = {
+            let guard = FULL_STATE.load();
+            let Some(config) = guard.get_app_config_by_window(window.hwnd())? else {
+                return Ok(false);
+            };
+            if !config.has_bindings() {
+                return Ok(false);
+            }
+            (
+                config.bound_monitor,
+                config.bound_workspace,
+                config.bound_workspace_name.clone(),
+                config.options.contains(&AppExtraFlag::VdFollow),
+            )
+        };
+
+        let current_monitor = window.monitor();
+        let target_monitor = match bound_monitor {
+            Some(idx) => *MonitorEnumerator::enumerate_win32()?
+                .get(idx)
+                .ok_or_else(|| format!("Bound monitor index {idx} not found"))?,
+            None => current_monitor,
+        };
+        let monitor_id = target_monitor.stable_id2()?;
+
+        let (workspace_id, is_active) = self.monitors.get_or_insert(
+            monitor_id.clone(),
+            VirtualDesktopMonitor::create,
+            |monitor| {
+                let workspace_id = monitor
+                    .find_workspace(bound_workspace, bound_workspace_name.as_deref())
+                    .unwrap_or(monitor.active_workspace_id())
+                    .clone();
+                let workspace = monitor
+                    .workspaces
+                    .iter_mut()
+                    .find(|w| w.id == workspace_id)
+                    .expect("workspace should exist");
+                if !workspace.windows.contains(&window_id) {
+                    workspace.windows.push(window_id);
+                }
+                let is_active = monitor.active_workspace_id() == &workspace_id;
+                (workspace_id, is_active)
+            },
+        );
+        log::trace!("adding {window} to bound workspace {workspace_id}");
+        self.workspace_index
+            .upsert(workspace_id.clone(), monitor_id.clone());
+
+        // the window is added before moving it so the monitor change event doesn't reassign it
+        if target_monitor != current_monitor {
+            Self::move_to_monitor(window, &current_monitor, &target_monitor)?;
+        }
+
+        if !is_active {
+            if follow {
+                self.switch_to_id(&monitor_id, &workspace_id)?;
+            } else {
+                window.show_window(SW_MINIMIZE)?;
+                let _ = MINIMIZED_BY_WORKSPACES.insert(window_id);
+            }
+        }
+
+        Self::send(VirtualDesktopEvent::WindowAdded {
+            window: window_id,
+            desktop: workspace_id,
+        });
+        self.request_save();
+        Ok(true)
+    }
+
+    /// Moves the window keeping its relative position on the monitor
+    fn move_to_monitor(window: &Window, from: &Monitor, to: &Monitor) -> Result<()> {
+        let from_rect = from.rect()?;
+        let to_rect = to.rect()?;
+        let rect = window.outer_rect()?;
+        let left = to_rect.left + (rect.left - from_rect.left);
+        let top = to_rect.top + (rect.top - from_rect.top);
+        window.set_position(
+            &RECT {
+                left,
+                top,
+                right: left + rect.width(),
+                bottom: top + rect.height(),
+            },
+            SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_ASYNCWINDOWPOS,
+        )
+    }
+
     fn remove(&self, window: &Window) {
         let window_id = window.address();
         log::trace!("Removing {window} from workspaces");
@@ -305,6 +427,7 @@ impl SluWorkspacesManager2 {
                 workspace.windows.retain(|w| w != &window_id);
             }
         });
+        self.remove_from_snapshots(window_id);
 
         Self::send(VirtualDesktopEvent::WindowRemoved { window: window_id });
         self.request_save();
@@ -312,25 +435,32 @@ impl SluWorkspacesManager2 {
 
     /// Switch to a workspace by ID on a specific monitor
     pub fn switch_to_id(&self, monitor_id: &MonitorId, workspace_id: &WorkspaceId) -> Result<()> {
-        let changed = self
+        let switched = self
             .monitors
             .get(monitor_id, |monitor| {
                 if monitor.active_workspace_id() == workspace_id {
-                    return Ok(false);
+                    return Ok(None);
                 }
 
+                let previous = monitor.active_workspace_id().clone();
+                let direction = monitor.switch_direction(&previous, workspace_id);
+
+                // keep the last known state of the workspace before its windows are minimized
+                self.update_snapshot(monitor.active_workspace());
                 monitor.active_workspace().hide(false);
                 monitor.set_active_workspace(workspace_id)?;
                 monitor.active_workspace().restore();
-                Result::Ok(true)
+                Result::Ok(Some((previous, direction)))
             })
             .ok_or("Monitor not found")??;
 
-        if changed {
+        if let Some((previous, direction)) = switched {
             log::trace!("Switched to workspace {workspace_id} on monitor {monitor_id}");
             Self::send(VirtualDesktopEvent::DesktopChanged {
                 monitor: monitor_id.clone(),
                 workspace: workspace_id.clone(),
+                previous,
+                direction,
             });
             self.request_save();
         }
@@ -430,6 +560,7 @@ impl SluWorkspacesManager2 {
 
         // Remove from workspace index
         self.workspace_index.remove(workspace_id);
+        self.snapshots.remove(workspace_id);
         Self::send(VirtualDesktopEvent::DesktopDestroyed(workspace_id.clone()));
         self.request_save();
         Ok(())
@@ -465,6 +596,7 @@ impl From<VirtualDesktops> for SluWorkspacesManager2 {
             monitors: SyncHashMap::from(value.monitors),
             workspace_index: SyncHashMap::from(workspace_index),
             pinned: SyncVec::from(value.pinned),
+            snapshots: SyncHashMap::new(),
         }
     }
 }
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use seelen_core::state::{
+    DesktopWorkspace, WorkspaceId, WorkspaceSnapshot, WorkspaceWindowSnapshot,
+};
+
+use crate::{
+    modules::apps::application::previews::WinPreviewManager, utils::now_timestamp_as_millis,
+    windows_api::window::Window,
+};
+
+use super::SluWorkspacesManager2;
+
+impl SluWorkspacesManager2 {
+    /// Updates the cached snapshot of the workspace with the current state of its windows,
+    /// minimized windows keep the rect of the previous snapshot.
+    pub(super) fn update_snapshot(&self, workspace: &DesktopWorkspace) {
+        let previews = &WinPreviewManager::instance().previews;
+        let last = self
+            .snapshots
+            .get(&workspace.id, |snapshot| snapshot.windows.clone())
+            .unwrap_or_default();
+
+        let windows = workspace
+            .windows
+            .iter()
+            .filter_map(|addr| {
+                let window = Window::from(*addr);
+                let rect = if window.is_minimized() {
+                    last.iter()
+                        .find(|w| &w.hwnd == addr)
+                        .map(|w| w.rect.clone())
+                } else {
+                    window.outer_rect().ok()
+                }?;
+
+                Some(WorkspaceWindowSnapshot {
+                    hwnd: *addr,
+                    rect,
+                    preview_hash: previews.get(addr, |preview| preview.hash.clone()),
+                })
+            })
+            .collect();
+
+        self.snapshots.upsert(
+            workspace.id.clone(),
+            WorkspaceSnapshot {
+                windows,
+                updated_at: now_timestamp_as_millis() as i64,
+            },
+        );
+    }
+
+    /// Returns the snapshots of all the workspaces, active ones are refreshed before.
+    pub fn get_snapshots(&self) -> HashMap<WorkspaceId, WorkspaceSnapshot> {
+        let mut active = Vec::new();
+        self.monitors.for_each(|(_, monitor)| {
+            active.push(monitor.active_workspace().clone());
+        });
+
+        for workspace in &active {
+            self.update_snapshot(workspace);
+        }
+        self.snapshots.to_hash_map()
+    }
+
+    pub(super) fn remove_from_snapshots(&self, window_id: isize) {
+        self.snapshots.for_each(|(_, snapshot)| {
+            snapshot.windows.retain(|w| w.hwnd != window_id);
+        });
+    }
+}
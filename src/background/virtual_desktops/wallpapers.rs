@@ -4,9 +4,10 @@ use std::time::Duration;
 
 use parking_lot::RwLock;
 use rand::Rng;
+use seelen_core::chrono::Local;
 use seelen_core::handlers::SeelenEvent;
 use seelen_core::resource::WallpaperId;
-use seelen_core::state::{WallpaperCollection, WorkspaceId};
+use seelen_core::state::{WallpaperCollection, WallpaperScheduleContext, WorkspaceId};
 use seelen_core::system_state::MonitorId;
 use tauri::Listener;
 use tokio::sync::mpsc;
@@ -15,6 +16,8 @@ use uuid::Uuid;
 use crate::app::get_app_handle;
 use crate::error::{Result, ResultLogExt};
 use crate::get_tokio_handle;
+use crate::modules::system_settings::application::{SystemSettings, SystemSettingsEvent};
+use crate::resources::RESOURCES;
 use crate::state::application::FULL_STATE;
 
 use super::events::VirtualDesktopEvent;
@@ -48,12 +51,19 @@ impl WorkspaceWallpapersManager {
 
         // Listen for settings changes
         get_app_handle().listen(SeelenEvent::StateSettingsChanged, |_| {
-            Self::on_settings_changed();
+            Self::refresh();
+        });
+
+        // Theme based schedules depend on the system colors
+        SystemSettings::subscribe(|event| {
+            if event == SystemSettingsEvent::ColorChanged {
+                Self::refresh();
+            }
         });
     }
 
-    /// Handle settings changes
-    fn on_settings_changed() {
+    /// Re-evaluate the wallpapers of all workspaces and notify the change
+    fn refresh() {
         let vd_manager = super::SluWorkspacesManager2::instance();
         Self::update_workspace_wallpapers_internal(vd_manager);
         super::SluWorkspacesManager2::send(VirtualDesktopEvent::StateChanged);
@@ -82,15 +92,17 @@ impl WorkspaceWallpapersManager {
         state.settings.by_widget.wall.default_collection
     }
 
-    /// Get the collection by UUID
+    /// Get the collection by UUID, with the wallpapers matched by its rule already resolved
     fn get_collection(collection_id: &Uuid) -> Option<WallpaperCollection> {
         let state = FULL_STATE.load();
-        state
+        let mut collection = state
             .settings
             .wallpaper_collections
             .iter()
             .find(|c| &c.id == collection_id)
-            .cloned()
+            .cloned()?;
+        collection.wallpapers = RESOURCES.resolve_collection(&collection);
+        Some(collection)
     }
 
     /// Get the current wallpaper for a workspace
@@ -101,6 +113,12 @@ impl WorkspaceWallpapersManager {
         let collection_id = Self::get_collection_id(monitor_id, workspace_id)?;
         let collection = Self::get_collection(&collection_id)?;
 
+        if let Some(schedule) = &collection.schedule {
+            if let Some(wallpaper_id) = schedule.resolve(&Self::schedule_context()) {
+                return Some(wallpaper_id.clone());
+            }
+        }
+
         if collection.wallpapers.is_empty() {
             return None;
         }
@@ -112,6 +130,30 @@ impl WorkspaceWallpapersManager {
         collection.wallpapers.get(wallpaper_index).cloned()
     }
 
+    fn schedule_context() -> WallpaperScheduleContext {
+        let dark_mode = SystemSettings::instance()
+            .get_colors()
+            .map(|colors| colors.is_dark_mode())
+            .unwrap_or(false);
+        WallpaperScheduleContext {
+            now: Local::now().naive_local(),
+            dark_mode,
+        }
+    }
+
+    /// Time until the next change of any collection schedule
+    fn get_next_schedule_change() -> Option<Duration> {
+        let state = FULL_STATE.load();
+        let now = Local::now().naive_local();
+        state
+            .settings
+            .wallpaper_collections
+            .iter()
+            .filter_map(|c| c.schedule.as_ref()?.next_change(now))
+            .min()
+            .and_then(|next| (next - now).to_std().ok())
+    }
+
     /// Increment the index for a collection
     fn increment_collection_index(collection_id: &Uuid, direction: ChangeDirection) {
         let collection = match Self::get_collection(collection_id) {
@@ -185,11 +227,7 @@ impl WorkspaceWallpapersManager {
             Self::increment_collection_index(&collection_id, direction);
         }
 
-        {
-            let vd_manager = super::SluWorkspacesManager2::instance();
-            Self::update_workspace_wallpapers_internal(vd_manager);
-            super::SluWorkspacesManager2::send(VirtualDesktopEvent::StateChanged);
-        }
+        Self::refresh();
     }
 
     /// Update wallpaper IDs in all workspaces (internal method with manager reference)
@@ -213,13 +251,18 @@ impl WorkspaceWallpapersManager {
     async fn rotation_loop(mut rx: mpsc::UnboundedReceiver<ChangeDirection>) -> Result<()> {
         loop {
             let interval = Self::get_interval_duration();
+            let schedule_change = Self::get_next_schedule_change();
 
-            // Wait for either the interval to elapse or a manual change to be triggered
+            // Wait for either the interval to elapse, a schedule to change or a manual change to be triggered
             tokio::select! {
                 _ = tokio::time::sleep(interval) => {
                     log::trace!("Automatic wallpaper rotation triggered");
                     Self::update_all_wallpapers(ChangeDirection::Next);
                 }
+                _ = tokio::time::sleep(schedule_change.unwrap_or(interval)), if schedule_change.is_some() => {
+                    log::trace!("Wallpaper schedule changed");
+                    Self::refresh();
+                }
                 direction = rx.recv() => {
                     if let Some(direction) = direction {
                         log::trace!("Manual wallpaper change triggered: {:?}", direction);
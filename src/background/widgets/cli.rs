@@ -1,7 +1,19 @@
 use clap::{Args, Subcommand};
-use seelen_core::state::WidgetTriggerPayload;
+use seelen_core::{
+    resource::WidgetId,
+    state::{WidgetInfo, WidgetTriggerPayload},
+};
+use uuid::Uuid;
 
-use crate::{error::Result, widgets::trigger_widget};
+use crate::{
+    error::Result,
+    resources::RESOURCES,
+    state::application::FULL_STATE,
+    widgets::{
+        manager::{WidgetManager, WIDGET_MANAGER},
+        trigger_widget, WidgetWebviewLabel,
+    },
+};
 
 #[derive(Debug, Args)]
 pub struct WidgetCli {
@@ -13,15 +25,333 @@ pub struct WidgetCli {
 enum WidgetCommand {
     /// Triggers a widget
     Trigger { widget_id: String },
+    /// Prints the widgets with their instances and status as json
+    List,
+    /// Enables a widget, creating its instances
+    Enable { widget_id: String },
+    /// Disables a widget, destroying its instances
+    Disable { widget_id: String },
+    /// Destroys and creates again all the instances of a widget
+    Reload { widget_id: String },
+    /// Manages the runtime instances of multiple instance widgets
+    Instance {
+        #[command(subcommand)]
+        command: InstanceCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum InstanceCommand {
+    /// Creates a runtime instance, a random id is used if none is given
+    Add {
+        widget_id: String,
+        instance_id: Option<Uuid>,
+    },
+    /// Removes a runtime instance
+    Remove {
+        widget_id: String,
+        instance_id: Uuid,
+    },
+}
+
+/// What the widget commands are run on, the widget manager of the app.
+trait WidgetHost {
+    fn list(&self) -> Vec<WidgetInfo>;
+    fn info(&self, widget_id: &WidgetId) -> Result<WidgetInfo>;
+    fn set_enabled(&self, widget_id: &WidgetId, enabled: bool) -> Result<()>;
+    fn reload(&self, widget_id: &WidgetId) -> Result<()>;
+    fn trigger(&self, payload: WidgetTriggerPayload) -> Result<()>;
+    fn remove_runtime_instance(&self, label: &WidgetWebviewLabel) -> Result<()>;
+}
+
+impl WidgetHost for WidgetManager {
+    fn list(&self) -> Vec<WidgetInfo> {
+        self.list()
+    }
+
+    fn info(&self, widget_id: &WidgetId) -> Result<WidgetInfo> {
+        self.info(widget_id)
+    }
+
+    fn set_enabled(&self, widget_id: &WidgetId, enabled: bool) -> Result<()> {
+        if !RESOURCES.widgets.contains(widget_id) {
+            return Err(format!("Widget {widget_id} not found").into());
+        }
+
+        FULL_STATE.rcu(|state| {
+            let mut state = state.cloned();
+            state.settings.by_widget.set_enabled(widget_id, enabled);
+            state
+        });
+        FULL_STATE.load().write_settings()?;
+        // settings file changes are applied async, refreshing here to output the new instances
+        self.refresh()
+    }
+
+    fn reload(&self, widget_id: &WidgetId) -> Result<()> {
+        self.reload(widget_id)
+    }
+
+    fn trigger(&self, payload: WidgetTriggerPayload) -> Result<()> {
+        trigger_widget(payload)
+    }
+
+    fn remove_runtime_instance(&self, label: &WidgetWebviewLabel) -> Result<()> {
+        self.remove_runtime_instance(label)
+    }
 }
 
 impl WidgetCli {
-    pub fn run(&self) -> Result<()> {
-        match &self.command {
+    /// Returns the json output of the command
+    pub fn run(&self) -> Result<Option<String>> {
+        self.run_on(&*WIDGET_MANAGER)
+    }
+
+    fn run_on(&self, host: &impl WidgetHost) -> Result<Option<String>> {
+        let output = match &self.command {
             WidgetCommand::Trigger { widget_id } => {
-                trigger_widget(WidgetTriggerPayload::new(widget_id.clone().into()))?;
+                host.trigger(WidgetTriggerPayload::new(widget_id.clone().into()))?;
+                return Ok(None);
+            }
+            WidgetCommand::List => serde_json::to_value(host.list())?,
+            WidgetCommand::Enable { widget_id } => set_enabled(host, widget_id, true)?,
+            WidgetCommand::Disable { widget_id } => set_enabled(host, widget_id, false)?,
+            WidgetCommand::Reload { widget_id } => {
+                let widget_id = WidgetId::from(widget_id.as_str());
+                host.reload(&widget_id)?;
+                serde_json::to_value(host.info(&widget_id)?)?
+            }
+            WidgetCommand::Instance { command } => command.run(host)?,
+        };
+        Ok(Some(serde_json::to_string_pretty(&output)?))
+    }
+}
+
+impl InstanceCommand {
+    fn run(&self, host: &impl WidgetHost) -> Result<serde_json::Value> {
+        let widget_id = match self {
+            InstanceCommand::Add {
+                widget_id,
+                instance_id,
+            } => {
+                let widget_id = WidgetId::from(widget_id.as_str());
+                let instance_id = instance_id.unwrap_or_else(Uuid::new_v4);
+                let mut payload = WidgetTriggerPayload::new(widget_id.clone());
+                payload.instance_id = Some(instance_id);
+                // triggering creates the runtime instance and starts its webview
+                host.trigger(payload)?;
+                widget_id
+            }
+            InstanceCommand::Remove {
+                widget_id,
+                instance_id,
+            } => {
+                let widget_id = WidgetId::from(widget_id.as_str());
+                let label = WidgetWebviewLabel::new(&widget_id, None, Some(instance_id));
+                host.remove_runtime_instance(&label)?;
+                widget_id
             }
+        };
+        Ok(serde_json::to_value(host.info(&widget_id)?)?)
+    }
+}
+
+fn set_enabled(
+    host: &impl WidgetHost,
+    widget_id: &str,
+    enabled: bool,
+) -> Result<serde_json::Value> {
+    let widget_id = WidgetId::from(widget_id);
+    host.set_enabled(&widget_id, enabled)?;
+    Ok(serde_json::to_value(host.info(&widget_id)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, sync::Arc};
+
+    use clap::Parser;
+    use seelen_core::state::{
+        Widget, WidgetInstanceMode, WidgetInstanceState, WidgetRegistry, WidgetWebviewFactory,
+    };
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        widget: WidgetCli,
+    }
+
+    struct FakeFactory;
+
+    impl WidgetWebviewFactory for FakeFactory {
+        type Webview = ();
+
+        fn create(
+            &self,
+            _widget: &Widget,
+            _instance: &WidgetInstanceState<()>,
+        ) -> std::result::Result<(), String> {
+            Ok(())
         }
-        Ok(())
+    }
+
+    /// Same as the widget manager, but on fake webviews.
+    struct FakeHost {
+        widgets: RefCell<Vec<(Arc<Widget>, bool)>>,
+        registry: RefCell<WidgetRegistry<()>>,
+    }
+
+    fn static_labels(widget: &Widget) -> Vec<String> {
+        match widget.instances {
+            WidgetInstanceMode::Single => {
+                vec![WidgetWebviewLabel::new(&widget.id, None, None).to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    impl FakeHost {
+        fn new(widgets: Vec<(Arc<Widget>, bool)>) -> Self {
+            let host = Self {
+                widgets: RefCell::new(widgets),
+                registry: RefCell::new(WidgetRegistry::default()),
+            };
+            host.refresh();
+            host
+        }
+
+        fn refresh(&self) {
+            let enabled: Vec<Arc<Widget>> = self
+                .widgets
+                .borrow()
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(widget, _)| widget.clone())
+                .collect();
+            let mut registry = self.registry.borrow_mut();
+            registry.sync(&enabled, static_labels);
+            registry.start_eager(&FakeFactory, 0);
+        }
+    }
+
+    impl WidgetHost for FakeHost {
+        fn list(&self) -> Vec<WidgetInfo> {
+            self.registry.borrow().list(&self.widgets.borrow())
+        }
+
+        fn info(&self, widget_id: &WidgetId) -> Result<WidgetInfo> {
+            let info = self
+                .list()
+                .into_iter()
+                .find(|w| &w.id == widget_id)
+                .ok_or(format!("Widget {widget_id} not found"))?;
+            Ok(info)
+        }
+
+        fn set_enabled(&self, widget_id: &WidgetId, enabled: bool) -> Result<()> {
+            for (widget, state) in self.widgets.borrow_mut().iter_mut() {
+                if &widget.id == widget_id {
+                    *state = enabled;
+                }
+            }
+            self.refresh();
+            Ok(())
+        }
+
+        fn reload(&self, widget_id: &WidgetId) -> Result<()> {
+            self.registry
+                .borrow_mut()
+                .reload(widget_id, static_labels)?;
+            self.refresh();
+            Ok(())
+        }
+
+        fn trigger(&self, payload: WidgetTriggerPayload) -> Result<()> {
+            let label = WidgetWebviewLabel::new(&payload.id, None, payload.instance_id.as_ref());
+            let mut registry = self.registry.borrow_mut();
+            let group = registry
+                .group_mut(&payload.id)
+                .ok_or("Can't trigger a disabled widget")?;
+            if group.definition().instances == WidgetInstanceMode::Multiple {
+                group.add_runtime_instance(&label.to_string());
+            }
+            group.start(&label.to_string(), &FakeFactory, 0)?;
+            Ok(())
+        }
+
+        fn remove_runtime_instance(&self, label: &WidgetWebviewLabel) -> Result<()> {
+            let mut registry = self.registry.borrow_mut();
+            let group = registry
+                .group_mut(&label.widget_id)
+                .ok_or(format!("Widget {} is not loaded", label.widget_id))?;
+            group.remove_runtime_instance(&label.to_string())?;
+            Ok(())
+        }
+    }
+
+    fn run(host: &FakeHost, args: &[&str]) -> Result<Option<Value>> {
+        let cli = TestCli::try_parse_from(std::iter::once("widget").chain(args.iter().copied()))
+            .map_err(|e| e.to_string())?;
+        let output = cli.widget.run_on(host)?;
+        Ok(output.map(|output| serde_json::from_str(&output).unwrap()))
+    }
+
+    fn widget(id: &str, instances: WidgetInstanceMode) -> Arc<Widget> {
+        Arc::new(Widget {
+            id: id.into(),
+            instances,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_commands_output() {
+        let host = FakeHost::new(vec![
+            (widget("@user/weg", WidgetInstanceMode::Single), true),
+            (widget("@user/notes", WidgetInstanceMode::Multiple), false),
+        ]);
+
+        let list = run(&host, &["list"]).unwrap().unwrap();
+        assert_eq!(list[0]["id"], json!("@user/notes"));
+        assert_eq!(list[0]["enabled"], json!(false));
+        assert_eq!(list[0]["instances"], json!([]));
+        assert_eq!(list[1]["id"], json!("@user/weg"));
+        assert_eq!(list[1]["instances"][0]["label"], json!("@user/weg"));
+        assert_eq!(list[1]["instances"][0]["instanceType"], json!("Static"));
+        assert_eq!(list[1]["instances"][0]["status"], json!("Mounting"));
+
+        let info = run(&host, &["enable", "@user/notes"]).unwrap().unwrap();
+        assert_eq!(info["enabled"], json!(true));
+
+        let instance_id = Uuid::new_v4().to_string();
+        let info = run(&host, &["instance", "add", "@user/notes", &instance_id])
+            .unwrap()
+            .unwrap();
+        let instances = info["instances"].as_array().unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(
+            instances[0]["label"],
+            json!(format!("@user/notes?instanceId={instance_id}"))
+        );
+        assert_eq!(instances[0]["instanceType"], json!("Runtime"));
+        assert_eq!(instances[0]["status"], json!("Mounting"));
+
+        let info = run(&host, &["instance", "remove", "@user/notes", &instance_id])
+            .unwrap()
+            .unwrap();
+        assert_eq!(info["instances"], json!([]));
+
+        // unknown instances can't be removed and ids are validated on parsing
+        assert!(run(&host, &["instance", "remove", "@user/weg", &instance_id]).is_err());
+        assert!(run(&host, &["instance", "remove", "@user/notes", "not-an-id"]).is_err());
+
+        assert_eq!(run(&host, &["trigger", "@user/weg"]).unwrap(), None);
+
+        let info = run(&host, &["disable", "@user/weg"]).unwrap().unwrap();
+        assert_eq!(info["enabled"], json!(false));
+        assert_eq!(info["instances"], json!([]));
     }
 }
@@ -0,0 +1,102 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use seelen_core::{
+    handlers::SeelenEvent,
+    resource::ResourceKind,
+    state::{Widget, WidgetReloadError},
+};
+
+use crate::{
+    app::emit_to_webviews, error::Result, resources::RESOURCES, utils::constants::SEELEN_COMMON,
+    widgets::manager::WIDGET_MANAGER,
+};
+
+/// Hot reload of the user widgets, used instead of reloading all the widgets while developer
+/// tools are enabled. Only the changed widgets are loaded again, if the load fails the old
+/// version keeps running.
+pub fn hot_reload_widgets(changed: &HashSet<PathBuf>) -> Result<()> {
+    let user_widgets = SEELEN_COMMON.user_widgets_path();
+
+    let mut by_widget: HashMap<PathBuf, Vec<&PathBuf>> = HashMap::new();
+    for path in changed {
+        let Some(entry) = path
+            .strip_prefix(user_widgets)
+            .ok()
+            .and_then(|relative| relative.components().next())
+        else {
+            continue;
+        };
+        by_widget
+            .entry(user_widgets.join(entry))
+            .or_default()
+            .push(path);
+    }
+
+    for (path, files) in by_widget {
+        hot_reload_widget(&path, &files)?;
+    }
+    Ok(())
+}
+
+fn loaded_from(path: &Path) -> Option<Arc<Widget>> {
+    let mut found = None;
+    RESOURCES.widgets.scan(|_, widget| {
+        if widget.metadata.internal.path == path {
+            found = Some(widget.clone());
+        }
+    });
+    found
+}
+
+fn hot_reload_widget(path: &Path, files: &[&PathBuf]) -> Result<()> {
+    let old = loaded_from(path);
+
+    if !path.exists() {
+        log::info!("Widget removed: {path:?}");
+        RESOURCES.unload(&ResourceKind::Widget, path);
+        if let Some(old) = &old {
+            WIDGET_MANAGER.unload(&old.id);
+        }
+        return RESOURCES.emit_widgets();
+    }
+
+    if let Err(err) = RESOURCES.load(&ResourceKind::Widget, path) {
+        for file in files {
+            log::error!("Failed to hot reload widget file {file:?}: {err}");
+            emit_to_webviews(
+                SeelenEvent::WidgetHotReloadFailed,
+                WidgetReloadError {
+                    widget_id: old.as_ref().map(|w| w.id.clone()),
+                    path: file.to_path_buf(),
+                    error: err.to_string(),
+                },
+            );
+        }
+        return Ok(());
+    }
+
+    let Some(new) = loaded_from(path) else {
+        return Ok(());
+    };
+
+    match old {
+        Some(old) if old.id == new.id => {
+            let kind = old.reload_kind(&new);
+            log::info!("Hot reloading widget {} ({kind:?})", new.id);
+            RESOURCES.emit_widgets()?;
+            RESOURCES.emit_plugins()?;
+            WIDGET_MANAGER.hot_reload(new, kind)
+        }
+        Some(old) => {
+            // the id was changed on the metadata file, so it is a new widget
+            RESOURCES.widgets.remove(&old.id);
+            WIDGET_MANAGER.unload(&old.id);
+            RESOURCES.emit_widgets()
+        }
+        None => RESOURCES.emit_widgets(),
+    }
+}
@@ -1,79 +1,188 @@
-use std::sync::LazyLock;
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
 
 use seelen_core::{
+    chrono::Utc,
     resource::WidgetId,
-    state::{WidgetLoader, WidgetStatus},
+    state::{Widget, WidgetInfo, WidgetLoader, WidgetRegistry, WidgetReloadKind, WidgetStatus},
 };
 
 use crate::{
-    error::Result,
+    error::{Result, ResultLogExt},
     resources::RESOURCES,
     state::application::FULL_STATE,
-    utils::lock_free::SyncHashMap,
-    widgets::{loader::WidgetContainer, WidgetWebviewLabel},
+    utils::{lock_free::TracedMutex, spawn_named_thread},
+    widgets::{
+        loader::{hot_reload_instance, static_labels, TauriWebviewFactory},
+        webview::WidgetWebview,
+        WidgetWebviewLabel,
+    },
 };
 
 pub static WIDGET_MANAGER: LazyLock<WidgetManager> = LazyLock::new(WidgetManager::create);
 
 pub struct WidgetManager {
-    /// group of widgets instances by widget resource id
-    pub groups: SyncHashMap<WidgetId, WidgetContainer>,
+    /// instances of the loaded widgets by widget resource id
+    registry: TracedMutex<WidgetRegistry<WidgetWebview>>,
+}
+
+fn log_failures(failed: Vec<(String, String)>) {
+    for (label, err) in failed {
+        log::error!("Failed to create webview for {label}: {err}");
+    }
 }
 
 impl WidgetManager {
     fn create() -> Self {
+        spawn_named_thread("Widget Supervisor", || loop {
+            WIDGET_MANAGER.restart_due();
+            std::thread::sleep(Duration::from_secs(1));
+        });
         Self {
-            groups: SyncHashMap::new(),
+            registry: TracedMutex::new(WidgetRegistry::default()),
+        }
+    }
+
+    /// Starts again the crashed or destroyed instances, as the restart policy of each widget allows.
+    fn restart_due(&self) {
+        let now = Utc::now().timestamp_millis();
+        let mut registry = self.registry.lock();
+        for group in registry.groups_mut() {
+            log_failures(group.restart_due(&TauriWebviewFactory, now));
         }
     }
 
     pub fn is_ready(&self, label: &WidgetWebviewLabel) -> bool {
-        self.groups
-            .get(&label.widget_id, |c| {
-                c.instances.any(|(k, i)| k == label && i.is_ready())
-            })
-            .unwrap_or(false)
+        self.registry
+            .lock()
+            .group(&label.widget_id)
+            .is_some_and(|g| g.is_ready(&label.to_string()))
     }
 
     pub fn set_status(&self, label: &WidgetWebviewLabel, status: WidgetStatus) {
-        self.groups.get(&label.widget_id, |c| {
-            c.instances.get(label, |instance| {
-                instance.set_status(status);
-            });
-        });
+        log::trace!("{label} status changed to: {status:?}");
+        if let Some(group) = self.registry.lock().group_mut(&label.widget_id) {
+            group.set_status(&label.to_string(), status);
+        }
     }
 
-    pub fn refresh(&self) -> Result<()> {
-        // remove deleted resources
-        self.groups.retain(|key, _| RESOURCES.widgets.contains(key));
+    /// Labels of the loaded instances of the widget.
+    pub fn labels(&self, widget_id: &WidgetId) -> Vec<WidgetWebviewLabel> {
+        self.registry
+            .lock()
+            .group(widget_id)
+            .map(|g| g.labels())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|label| WidgetWebviewLabel::from_decoded(&label))
+            .collect()
+    }
 
-        let mut filtered = Vec::new();
-        RESOURCES.widgets.scan(|k, w| {
-            if w.loader != WidgetLoader::Legacy {
-                filtered.push((k.clone(), w.clone()));
+    /// Creates the webview of the instance if it is not created yet.
+    pub fn start(&self, label: &WidgetWebviewLabel) {
+        let now = Utc::now().timestamp_millis();
+        if let Some(group) = self.registry.lock().group_mut(&label.widget_id) {
+            if let Err(err) = group.start(&label.to_string(), &TauriWebviewFactory, now) {
+                log::error!("Failed to create webview for {label}: {err}");
             }
-        });
+        }
+    }
+
+    /// Creates the instance without starting it, returns false if it already exists.
+    pub fn add_runtime_instance(&self, label: &WidgetWebviewLabel) -> bool {
+        self.registry
+            .lock()
+            .group_mut(&label.widget_id)
+            .is_some_and(|g| g.add_runtime_instance(&label.to_string()))
+    }
 
+    pub fn remove_runtime_instance(&self, label: &WidgetWebviewLabel) -> Result<()> {
+        let mut registry = self.registry.lock();
+        let group = registry
+            .group_mut(&label.widget_id)
+            .ok_or(format!("Widget {} is not loaded", label.widget_id))?;
+        group.remove_runtime_instance(&label.to_string())?;
+        Ok(())
+    }
+
+    pub fn on_destroyed(&self, label: &WidgetWebviewLabel) {
+        let now = Utc::now().timestamp_millis();
+        if let Some(group) = self.registry.lock().group_mut(&label.widget_id) {
+            group.on_destroyed(&label.to_string(), now);
+        }
+    }
+
+    /// All the widgets handled by the manager, sorted by id.
+    pub fn list(&self) -> Vec<WidgetInfo> {
         let state = FULL_STATE.load();
-        for (id, widget) in filtered {
-            if !state.is_widget_enabled(&id) {
-                self.groups.remove(&id);
-                continue;
+        let mut widgets = Vec::new();
+        RESOURCES.widgets.scan(|id, widget| {
+            if widget.loader != WidgetLoader::Legacy {
+                widgets.push((widget.clone(), state.is_widget_enabled(id)));
+            }
+        });
+        self.registry.lock().list(&widgets)
+    }
+
+    pub fn info(&self, widget_id: &WidgetId) -> Result<WidgetInfo> {
+        let info = self
+            .list()
+            .into_iter()
+            .find(|w| &w.id == widget_id)
+            .ok_or(format!("Widget {widget_id} not found"))?;
+        Ok(info)
+    }
+
+    /// Releases the instances of the widget, returns false if it was not loaded.
+    pub fn unload(&self, widget_id: &WidgetId) -> bool {
+        self.registry.lock().unload(widget_id)
+    }
+
+    /// Destroys all the instances of the widget and creates them again.
+    pub fn reload(&self, widget_id: &WidgetId) -> Result<()> {
+        self.registry.lock().reload(widget_id, static_labels)?;
+        self.refresh()
+    }
+
+    /// Updates the running instances to the new version of the widget.
+    pub fn hot_reload(&self, widget: Arc<Widget>, kind: WidgetReloadKind) -> Result<()> {
+        if kind == WidgetReloadKind::Instances {
+            if self.registry.lock().contains(&widget.id) {
+                self.reload(&widget.id)?;
             }
+            return Ok(());
+        }
 
-            if !self.groups.contains_key(&id) {
-                self.groups
-                    .upsert(id.clone(), WidgetContainer::create(widget));
+        if let Some(group) = self.registry.lock().group_mut(&widget.id) {
+            group.set_definition(widget.clone());
+            for instance in group.instances_mut() {
+                hot_reload_instance(instance, &widget, kind).log_error();
             }
         }
+        Ok(())
+    }
+
+    pub fn refresh(&self) -> Result<()> {
+        let state = FULL_STATE.load();
+        let mut enabled = Vec::new();
+        RESOURCES.widgets.scan(|id, widget| {
+            if widget.loader != WidgetLoader::Legacy && state.is_widget_enabled(id) {
+                enabled.push(widget.clone());
+            }
+        });
+        // removed and disabled widgets are released
+        self.registry.lock().sync(&enabled, static_labels);
 
         // lazy creation of webviews to reduce startup time
         std::thread::spawn(|| {
-            WIDGET_MANAGER.groups.for_each(|(_, c)| {
-                if !c.definition.lazy {
-                    c.start_all_webviews();
-                }
-            });
+            let now = Utc::now().timestamp_millis();
+            let failed = WIDGET_MANAGER
+                .registry
+                .lock()
+                .start_eager(&TauriWebviewFactory, now);
+            log_failures(failed);
         });
 
         Ok(())
@@ -1,4 +1,5 @@
 pub mod cli;
+pub mod hot_reload;
 pub mod loader;
 pub mod manager;
 pub mod popups;
@@ -80,15 +81,10 @@ pub fn trigger_widget(mut payload: WidgetTriggerPayload) -> Result<()> {
             }
         }
         WidgetInstanceMode::Multiple => {
-            let Some(instance_id) = &payload.instance_id else {
+            if payload.instance_id.is_none() {
                 return Err("Instance id is required for multiple instance widgets".into());
-            };
-
-            WIDGET_MANAGER.groups.get(&payload.id, |container| {
-                if !container.instances.contains_key(&label) {
-                    container.create_runtime_instance(instance_id);
-                }
-            });
+            }
+            WIDGET_MANAGER.add_runtime_instance(&label);
         }
     }
 
@@ -100,9 +96,7 @@ pub fn trigger_widget(mut payload: WidgetTriggerPayload) -> Result<()> {
         log::warn!("Trigger postponed, because widget instance is not ready: {label}");
         PENDING_TRIGGERS.upsert(label.clone(), payload);
 
-        WIDGET_MANAGER.groups.get(&label.widget_id, |c| {
-            c.start_webview(&label);
-        });
+        WIDGET_MANAGER.start(&label);
         return Ok(());
     }
 
@@ -0,0 +1,16 @@
+use seelen_core::state::RenderedToolbarItem;
+
+use super::{
+    remote_data::{RemoteDataFetcher, ToolbarRemoteData},
+    renderer::ToolbarRenderer,
+};
+
+#[tauri::command(async)]
+pub fn get_rendered_toolbar_items() -> Vec<RenderedToolbarItem> {
+    ToolbarRenderer::instance().rendered()
+}
+
+#[tauri::command(async)]
+pub fn get_toolbar_remote_data() -> ToolbarRemoteData {
+    RemoteDataFetcher::instance().all()
+}
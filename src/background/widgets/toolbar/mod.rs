@@ -1,15 +1,23 @@
+pub mod handler;
 pub mod hook;
+pub mod remote_data;
+pub mod renderer;
+mod scope;
 
 use crate::{
     app::get_app_handle,
     error::Result,
     log_error,
+    resources::RESOURCES,
     state::application::FULL_STATE,
     widgets::webview::WebviewArgs,
     windows_api::{monitor::Monitor, AppBarData, WindowsApi},
 };
 use base64::Engine;
-use seelen_core::state::{FancyToolbarSide, HideMode};
+use seelen_core::state::{
+    value::{KnownPlugin, PluginValue},
+    FancyToolbarSide, HideMode, ToolbarItem, ToolbarItem2,
+};
 use tauri::WebviewWindow;
 use windows::Win32::{
     Foundation::{HWND, RECT},
@@ -57,6 +65,45 @@ impl FancyToolbar {
         base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Self::decoded_label(monitor_id))
     }
 
+    /// Inline and plugin items placed on the toolbar, plugin items are identified by the plugin id.
+    pub fn items() -> Vec<ToolbarItem> {
+        Self::items_with_origin()
+            .into_iter()
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    /// Same as [`Self::items`], paired with a flag telling if the item comes from a bundled plugin.
+    pub fn items_with_origin() -> Vec<(ToolbarItem, bool)> {
+        let state = FULL_STATE.load();
+        let toolbar = &state.toolbar_items;
+
+        let mut items = Vec::new();
+        for entry in toolbar
+            .left
+            .iter()
+            .chain(&toolbar.center)
+            .chain(&toolbar.right)
+        {
+            match entry {
+                ToolbarItem2::Inline(item) => items.push((item.as_ref().clone(), false)),
+                ToolbarItem2::Plugin(id) => {
+                    let Some(plugin) = RESOURCES.plugins.get(id) else {
+                        continue;
+                    };
+                    if let PluginValue::Known(known) = &plugin.plugin {
+                        if let KnownPlugin::FacyToolbar(item) = &**known {
+                            let mut item = item.clone();
+                            item.id = id.to_string();
+                            items.push((item, plugin.metadata.internal.bundled));
+                        }
+                    }
+                }
+            }
+        }
+        items
+    }
+
     fn create_window(monitor_id: &str) -> Result<WebviewWindow> {
         let manager = get_app_handle();
         let args = WebviewArgs::new().disable_gpu();
@@ -0,0 +1,325 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect,
+};
+use seelen_core::{
+    chrono::Utc,
+    handlers::SeelenEvent,
+    state::{
+        is_local_address, is_local_url, RemoteDataRequest, RemoteDataResponse, RemoteDataScheduler,
+        ToolbarItem,
+    },
+    utils::TsUnknown,
+};
+
+use crate::{app::emit_to_webviews, error::Result, utils::lock_free::TracedMutex};
+
+use super::FancyToolbar;
+
+/// Remote data of each toolbar item by item id and declaration key.
+pub type ToolbarRemoteData = HashMap<String, HashMap<String, TsUnknown>>;
+
+/// Fetches the remote data declared by the toolbar items, responses are shared by
+/// all the items and toolbars declaring the same source.
+pub struct RemoteDataFetcher {
+    scheduler: TracedMutex<RemoteDataScheduler>,
+}
+
+impl RemoteDataFetcher {
+    pub fn instance() -> &'static Self {
+        static INSTANCE: LazyLock<RemoteDataFetcher> = LazyLock::new(|| {
+            tauri::async_runtime::spawn(async {
+                loop {
+                    RemoteDataFetcher::instance().schedule();
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            });
+            RemoteDataFetcher {
+                scheduler: TracedMutex::new(RemoteDataScheduler::default()),
+            }
+        });
+        &INSTANCE
+    }
+
+    /// Data of the item by key, null while it is not fetched yet.
+    pub fn item_data(&self, item: &ToolbarItem) -> HashMap<String, TsUnknown> {
+        let scheduler = self.scheduler.lock();
+        item.remote_data
+            .iter()
+            .map(|(key, declaration)| {
+                let data = scheduler.data(declaration).cloned().unwrap_or_default();
+                (key.clone(), TsUnknown(data))
+            })
+            .collect()
+    }
+
+    pub fn all(&self) -> ToolbarRemoteData {
+        FancyToolbar::items()
+            .iter()
+            .filter(|item| !item.remote_data.is_empty())
+            .map(|item| (item.id.clone(), self.item_data(item)))
+            .collect()
+    }
+
+    fn schedule(&'static self) {
+        let items = FancyToolbar::items_with_origin();
+        let due = {
+            let mut scheduler = self.scheduler.lock();
+            scheduler.sync(items.iter().flat_map(|(item, bundled)| {
+                item.remote_data
+                    .values()
+                    .map(|declaration| (declaration, *bundled))
+            }));
+            let due = scheduler.due(Utc::now().timestamp_millis());
+            for (key, _) in &due {
+                scheduler.start(key);
+            }
+            due
+        };
+
+        for (key, request) in due {
+            tauri::async_runtime::spawn(async move {
+                let result = fetch(&request).await.map_err(|err| err.to_string());
+                if let Err(err) = &result {
+                    log::warn!("Failed to fetch remote data from {}: {err}", request.url);
+                }
+
+                let changed =
+                    self.scheduler
+                        .lock()
+                        .finish(&key, result, Utc::now().timestamp_millis());
+                if changed {
+                    emit_to_webviews(SeelenEvent::ToolbarRemoteDataChanged, self.all());
+                }
+            });
+        }
+    }
+}
+
+/// Resolves domains only to public addresses, so the items can't reach the local
+/// network through a domain pointing to it.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| !is_local_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} resolves to a local address", name.as_str()).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+async fn fetch(request: &RemoteDataRequest) -> Result<RemoteDataResponse> {
+    /// Client of the bundled items, allowed to request local network hosts.
+    static LOCAL_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default()
+    });
+    static PUBLIC_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .dns_resolver(Arc::new(PublicResolver))
+            // ip hosts are not resolved, so redirects to them are checked here
+            .redirect(redirect::Policy::custom(|attempt| {
+                if is_local_url(attempt.url()) {
+                    attempt.error(format!("redirect to local host {}", attempt.url()))
+                } else if attempt.previous().len() >= 10 {
+                    attempt.error("too many redirects")
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .unwrap_or_default()
+    });
+
+    // requests are validated by the scheduler, before adding the conditional headers
+    let client = if request.local_network {
+        &LOCAL_CLIENT
+    } else {
+        &PUBLIC_CLIENT
+    };
+
+    let method = reqwest::Method::from_bytes(request.method.as_bytes())
+        .map_err(|_| format!("invalid method {}", request.method))?;
+    let mut builder = client.request(method, request.url.clone());
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = &request.body {
+        builder = builder.body(body.clone());
+    }
+
+    let response = builder.send().await?;
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE);
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let status = response.status().as_u16();
+
+    Ok(RemoteDataResponse {
+        status,
+        content_type,
+        etag,
+        last_modified,
+        body: response.text().await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    use seelen_core::state::RemoteDataDeclaration;
+    use serde_json::json;
+
+    use super::*;
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!(
+            "HTTP/1.1 {status}\r\nConnection: close\r\nContent-Length: {}\r\n",
+            body.len()
+        );
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str("\r\n");
+        response.push_str(body);
+        response
+    }
+
+    /// Listens on a local port and answers each connection with the next response,
+    /// returns the address and the raw requests received.
+    fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        request.push_str(&line);
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                requests.push(request.to_lowercase());
+            }
+            requests
+        });
+        (address, handle)
+    }
+
+    /// Fetches everything due at `now`, returns true if some data changed.
+    fn tick(scheduler: &mut RemoteDataScheduler, now: i64) -> bool {
+        let mut changed = false;
+        for (key, request) in scheduler.due(now) {
+            scheduler.start(&key);
+            let result =
+                tauri::async_runtime::block_on(fetch(&request)).map_err(|err| err.to_string());
+            changed |= scheduler.finish(&key, result, now);
+        }
+        changed
+    }
+
+    #[test]
+    fn test_fetch_from_local_server() {
+        let (address, server) = serve(vec![
+            response(
+                "200 OK",
+                &[
+                    "Content-Type: application/json; charset=utf-8",
+                    "ETag: \"v1\"",
+                    "Last-Modified: Wed, 21 Oct 2015 07:28:00 GMT",
+                ],
+                r#"{ "temp": 20 }"#,
+            ),
+            response("304 Not Modified", &["ETag: \"v1\""], ""),
+            response(
+                "200 OK",
+                &["Content-Type: text/plain", "ETag: \"v2\""],
+                r#"{ "temp": 25 }"#,
+            ),
+        ]);
+
+        let declaration = RemoteDataDeclaration {
+            url: format!("{address}/weather").parse().unwrap(),
+            request_init: Some(TsUnknown(json!({
+                "method": "POST",
+                "headers": { "Content-Type": "text/plain" },
+                "body": "{}",
+            }))),
+            update_interval_seconds: Some(60),
+        };
+        let mut scheduler = RemoteDataScheduler::default();
+        // the local server is only reachable by bundled items
+        scheduler.sync([(&declaration, false)]);
+        assert!(scheduler.due(0).is_empty());
+        scheduler.sync([(&declaration, true)]);
+
+        assert!(tick(&mut scheduler, 0));
+        assert_eq!(scheduler.data(&declaration), Some(&json!({ "temp": 20 })));
+
+        // not modified keeps the previous data
+        assert!(!tick(&mut scheduler, 60_000));
+        assert_eq!(scheduler.data(&declaration), Some(&json!({ "temp": 20 })));
+        assert_eq!(scheduler.error(&declaration), None);
+
+        // non json content is handled as text
+        assert!(tick(&mut scheduler, 120_000));
+        assert_eq!(
+            scheduler.data(&declaration),
+            Some(&json!(r#"{ "temp": 25 }"#))
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /weather http/1.1\r\n"));
+        assert!(requests[0].contains("\r\ncontent-type: text/plain\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\n{}"));
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("\r\nif-none-match: \"v1\"\r\n"));
+        assert!(requests[1].contains("\r\nif-modified-since: wed, 21 oct 2015 07:28:00 gmt\r\n"));
+        assert!(requests[2].contains("\r\nif-none-match: \"v1\"\r\n"));
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        mpsc::{Receiver, RecvTimeoutError, Sender},
+        OnceLock,
+    },
+    time::Duration,
+};
+
+use seelen_core::{
+    chrono::{Local, Timelike},
+    handlers::SeelenEvent,
+    state::{RenderedToolbarItem, ToolbarEngine, ToolbarItem, ToolbarJsScope},
+};
+use serde_json::{Map, Value};
+
+use crate::{
+    app::emit_to_webviews,
+    utils::{lock_free::TracedMutex, spawn_named_thread},
+};
+
+use super::{remote_data::RemoteDataFetcher, scope::scope_data, FancyToolbar};
+
+/// Evaluates the toolbar items marked for background evaluation, so the webview
+/// only has to display the results.\
+/// Items are rendered again only when the data of their scopes changes.
+pub struct ToolbarRenderer {
+    rendered: TracedMutex<Vec<RenderedToolbarItem>>,
+    invalidations: Sender<Invalidation>,
+}
+
+static INSTANCE: OnceLock<ToolbarRenderer> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+enum Invalidation {
+    Scope(ToolbarJsScope),
+    RemoteData,
+    Items,
+}
+
+impl ToolbarRenderer {
+    pub fn instance() -> &'static Self {
+        INSTANCE.get_or_init(|| {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            spawn_named_thread("Toolbar Renderer", move || {
+                RenderLoop::default().run(receiver)
+            });
+            ToolbarRenderer {
+                rendered: TracedMutex::new(Vec::new()),
+                invalidations: sender,
+            }
+        })
+    }
+
+    pub fn rendered(&self) -> Vec<RenderedToolbarItem> {
+        self.rendered.lock().clone()
+    }
+
+    /// Renders again the items affected by the event, nothing is done until the
+    /// toolbar requests the rendered items for first time.
+    pub fn on_event(event: &str) {
+        let Some(renderer) = INSTANCE.get() else {
+            return;
+        };
+
+        let invalidations: Vec<Invalidation> = match event {
+            SeelenEvent::ToolbarRemoteDataChanged => vec![Invalidation::RemoteData],
+            SeelenEvent::StatePluginsChanged => vec![Invalidation::Items],
+            _ => ToolbarJsScope::changed_by(event)
+                .iter()
+                .map(|scope| Invalidation::Scope(*scope))
+                .collect(),
+        };
+
+        for invalidation in invalidations {
+            // the render thread never stops, so this can't fail
+            let _ = renderer.invalidations.send(invalidation);
+        }
+    }
+
+    fn publish(&self, rendered: Vec<RenderedToolbarItem>) {
+        let mut last = self.rendered.lock();
+        if *last == rendered {
+            return;
+        }
+
+        for item in &rendered {
+            let errors_changed = last
+                .iter()
+                .find(|l| l.id == item.id)
+                .is_none_or(|l| l.errors != item.errors);
+            if errors_changed && !item.errors.is_empty() {
+                log::warn!("Toolbar item {} failed: {:?}", item.id, item.errors);
+            }
+        }
+
+        *last = rendered;
+        emit_to_webviews(SeelenEvent::ToolbarItemsRendered, last.clone());
+    }
+}
+
+/// Item with its own engine, reused on each render.
+struct ItemRenderer {
+    item: ToolbarItem,
+    engine: Option<ToolbarEngine>,
+    rendered: Option<RenderedToolbarItem>,
+}
+
+/// State of the render thread, the js engines can't be shared with other threads.
+#[derive(Default)]
+struct RenderLoop {
+    items: Vec<ItemRenderer>,
+}
+
+impl RenderLoop {
+    fn run(mut self, receiver: Receiver<Invalidation>) {
+        let mut items_changed = true;
+        let mut remote_data_changed = false;
+        let mut scopes = HashSet::new();
+
+        loop {
+            if items_changed {
+                self.sync_items();
+            }
+            self.render(&scopes, remote_data_changed);
+            ToolbarRenderer::instance().publish(self.rendered());
+
+            items_changed = false;
+            remote_data_changed = false;
+            scopes.clear();
+
+            let first = if self.uses_date() {
+                receiver.recv_timeout(until_next_second())
+            } else {
+                receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+            let first = match first {
+                Ok(invalidation) => invalidation,
+                Err(RecvTimeoutError::Timeout) => Invalidation::Scope(ToolbarJsScope::Date),
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+
+            // events usually come in bursts, so all the pending ones are rendered at once
+            for invalidation in std::iter::once(first).chain(receiver.try_iter()) {
+                match invalidation {
+                    Invalidation::Scope(scope) => {
+                        scopes.insert(scope);
+                    }
+                    Invalidation::RemoteData => remote_data_changed = true,
+                    Invalidation::Items => items_changed = true,
+                }
+            }
+        }
+    }
+
+    /// Keeps the engines of the unchanged items, new and changed items are rendered on the next render.
+    fn sync_items(&mut self) {
+        let mut previous: HashMap<String, ItemRenderer> = self
+            .items
+            .drain(..)
+            .map(|renderer| (renderer.item.id.clone(), renderer))
+            .collect();
+
+        self.items = FancyToolbar::items()
+            .into_iter()
+            .filter(|item| item.background_evaluation)
+            .map(|item| match previous.remove(&item.id) {
+                Some(renderer) if renderer.item == item => renderer,
+                Some(mut renderer) => {
+                    renderer.item = item;
+                    renderer.rendered = None;
+                    renderer
+                }
+                None => ItemRenderer {
+                    item,
+                    engine: None,
+                    rendered: None,
+                },
+            })
+            .collect();
+    }
+
+    fn render(&mut self, changed_scopes: &HashSet<ToolbarJsScope>, remote_data_changed: bool) {
+        // scopes are shared by the items, so each one is only built once per render
+        let mut scopes: HashMap<ToolbarJsScope, Result<Map<String, Value>, String>> =
+            HashMap::new();
+
+        for renderer in &mut self.items {
+            let item = &renderer.item;
+            let outdated = renderer.rendered.is_none()
+                || item
+                    .scopes
+                    .iter()
+                    .any(|scope| changed_scopes.contains(scope))
+                || (remote_data_changed && !item.remote_data.is_empty());
+            if !outdated {
+                continue;
+            }
+
+            let mut scope = Map::new();
+            let mut scope_errors = Vec::new();
+            for kind in &item.scopes {
+                let data = scopes
+                    .entry(*kind)
+                    .or_insert_with(|| scope_data(*kind).map_err(|e| e.to_string()));
+                match data {
+                    Ok(data) => scope.extend(data.clone()),
+                    Err(err) => scope_errors.push(format!("{kind:?} scope: {err}")),
+                }
+            }
+            for (key, data) in RemoteDataFetcher::instance().item_data(item) {
+                scope.insert(key, data.0);
+            }
+
+            if renderer.engine.is_none() {
+                renderer.engine = ToolbarEngine::new().ok();
+            }
+            let mut rendered = match &renderer.engine {
+                Some(engine) => item.render_on(engine, &scope),
+                // reports the error of creating the engine
+                None => item.render(&scope),
+            };
+            rendered.errors.splice(0..0, scope_errors);
+            renderer.rendered = Some(rendered);
+        }
+    }
+
+    fn rendered(&self) -> Vec<RenderedToolbarItem> {
+        self.items
+            .iter()
+            .filter_map(|renderer| renderer.rendered.clone())
+            .collect()
+    }
+
+    fn uses_date(&self) -> bool {
+        self.items
+            .iter()
+            .any(|renderer| renderer.item.scopes.contains(&ToolbarJsScope::Date))
+    }
+}
+
+/// Time left to the start of the next second, so the clock changes with the system clock.
+fn until_next_second() -> Duration {
+    let nanos = Local::now().nanosecond() % 1_000_000_000;
+    Duration::from_nanos(1_000_000_000 - nanos as u64)
+}
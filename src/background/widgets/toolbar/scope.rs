@@ -0,0 +1,173 @@
+use std::fmt::Write;
+
+use seelen_core::{
+    chrono::Local,
+    resource::WidgetId,
+    state::{moment_to_chrono_format, ToolbarJsScope},
+    system_state::SystemLanguage,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    error::Result,
+    modules::{
+        apps::infrastructure::get_focused_app,
+        media::{
+            devices::infrastructure::get_media_devices, players::infrastructure::get_media_sessions,
+        },
+        network::infrastructure::{
+            get_network_adapters, get_network_default_local_ip, get_network_internet_connection,
+        },
+        notifications::infrastructure::get_notifications,
+        power::infrastructure::{get_batteries, get_power_mode, get_power_status},
+        radios::bluetooth::handlers::get_bluetooth_devices,
+        system::tauri::{
+            get_system_cores, get_system_disks, get_system_memory, get_system_network,
+        },
+        system_settings::language::infrastructure::get_system_languages,
+        user::infrastructure::get_user,
+    },
+    state::application::FULL_STATE,
+};
+
+/// Same data that the toolbar webview adds to the item scope, functions like `onWheel` are excluded.
+pub fn scope_data(kind: ToolbarJsScope) -> Result<Map<String, Value>> {
+    let data = match kind {
+        ToolbarJsScope::Date => {
+            let format = moment_to_chrono_format(&FULL_STATE.load().settings.date_format);
+            let mut date = String::new();
+            // invalid formats are rendered as empty instead of panicking
+            let _ = write!(date, "{}", Local::now().format(&format));
+            json!({ "date": date })
+        }
+        ToolbarJsScope::Notifications => json!({ "count": get_notifications().len() }),
+        ToolbarJsScope::Media => {
+            let (inputs, outputs) = get_media_devices()?;
+            let default_of = |devices: &Value| {
+                devices
+                    .as_array()
+                    .and_then(|d| d.iter().find(|d| d["isDefaultMultimedia"] == true))
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let output = default_of(&outputs);
+            let input = default_of(&inputs);
+            let media_session = get_media_sessions()?.into_iter().find(|s| s.default);
+            json!({
+                "volume": output["volume"].as_f64().unwrap_or(0.0),
+                "isMuted": output["muted"].as_bool().unwrap_or(true),
+                "inputVolume": input["volume"].as_f64().unwrap_or(0.0),
+                "inputIsMuted": input["muted"].as_bool().unwrap_or(true),
+                "mediaSession": media_session,
+            })
+        }
+        ToolbarJsScope::Network => {
+            let interfaces = get_network_adapters()?;
+            let default_ip = get_network_default_local_ip()?;
+            let using_interface = interfaces
+                .iter()
+                .find(|i| i.ipv4.as_deref() == Some(default_ip.as_str()));
+            json!({
+                "online": get_network_internet_connection()?,
+                "interfaces": interfaces,
+                "usingInterface": using_interface,
+            })
+        }
+        ToolbarJsScope::Keyboard => keyboard_scope(get_system_languages()),
+        ToolbarJsScope::User => {
+            let user = get_user();
+            let by_gamertag = FULL_STATE
+                .load()
+                .settings
+                .by_widget
+                .others
+                .get(&WidgetId::from("@seelen/user-menu"))
+                .and_then(|config| config.rest.get("displayNameSource"))
+                .is_some_and(|source| source.0 == "xboxGamertag");
+            let display_name = match &user.xbox_gamertag {
+                Some(gamertag) if by_gamertag => gamertag.clone(),
+                _ => user.name.clone(),
+            };
+            let mut user = serde_json::to_value(user)?;
+            user["displayName"] = display_name.into();
+            json!({ "user": user })
+        }
+        ToolbarJsScope::Bluetooth => {
+            let devices = get_bluetooth_devices();
+            let connected: Vec<_> = devices.iter().filter(|d| d.connected).collect();
+            json!({ "devices": devices, "connectedDevices": connected })
+        }
+        ToolbarJsScope::Power => json!({
+            "power": get_power_status(),
+            "powerMode": get_power_mode(),
+            "batteries": get_batteries(),
+        }),
+        ToolbarJsScope::FocusedApp => json!({ "focusedApp": get_focused_app() }),
+        ToolbarJsScope::Workspaces => {
+            return Err("depends on the monitor of the toolbar, use webview evaluation".into());
+        }
+        ToolbarJsScope::Disk => json!({ "disks": get_system_disks() }),
+        ToolbarJsScope::NetworkStatistics => {
+            json!({ "networkStatistics": get_system_network() })
+        }
+        ToolbarJsScope::Memory => json!({ "memory": get_system_memory() }),
+        ToolbarJsScope::Cpu => json!({ "cores": get_system_cores() }),
+    };
+
+    match data {
+        Value::Object(data) => Ok(data),
+        _ => Ok(Map::new()),
+    }
+}
+
+fn keyboard_scope(languages: Vec<SystemLanguage>) -> Value {
+    let active_lang = languages
+        .iter()
+        .find(|l| l.keyboard_layouts.iter().any(|k| k.active))
+        .or(languages.first());
+    let active_keyboard = active_lang.and_then(|l| {
+        l.keyboard_layouts
+            .iter()
+            .find(|k| k.active)
+            .or(l.keyboard_layouts.first())
+    });
+
+    let active_lang_prefix: String = active_lang
+        .map(|l| {
+            l.native_name
+                .chars()
+                .take(3)
+                .filter(|c| !['(', ')', ' '].contains(c))
+                .collect::<String>()
+                .to_uppercase()
+        })
+        .unwrap_or_default();
+
+    let words: Vec<&str> = active_keyboard
+        .map(|k| {
+            k.display_name
+                .split(|c: char| c.is_whitespace() || ['-', '(', ')'].contains(&c))
+                .collect()
+        })
+        .unwrap_or_default();
+    let active_keyboard_prefix = if words.len() > 1 {
+        words
+            .iter()
+            .filter_map(|w| w.chars().next())
+            .collect::<String>()
+            .to_uppercase()
+    } else {
+        words
+            .first()
+            .map(|w| w.chars().take(3).collect::<String>().to_uppercase())
+            .unwrap_or_default()
+    };
+
+    json!({
+        "activeLang": active_lang,
+        "activeKeyboard": active_keyboard,
+        "activeLangPrefix": active_lang_prefix,
+        "activeKeyboardPrefix": active_keyboard_prefix,
+        "languages": languages,
+    })
+}
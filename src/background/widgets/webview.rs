@@ -108,6 +108,16 @@ impl WidgetWebviewLabel {
         }
     }
 
+    /// Inverse of `to_string`, used with the labels of the widget registry.
+    pub fn from_decoded(decoded: &str) -> Self {
+        let widget_id = WidgetId::from(decoded.split('?').next().unwrap_or_default());
+        Self {
+            raw: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(decoded),
+            decoded: decoded.to_string(),
+            widget_id,
+        }
+    }
+
     pub fn try_from_raw(raw: &str) -> Result<Self> {
         let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(raw)?;
         let decoded = String::from_utf8(decoded)?;
@@ -9,6 +9,7 @@ use tauri_plugin_shell::ShellExt;
 use crate::{
     app::get_app_handle,
     error::Result,
+    modules::start::application::StartMenuManager,
     state::application::FULL_STATE,
     trace_lock,
     widgets::weg::weg_items_impl::SEELEN_WEG_STATE,
@@ -114,5 +115,8 @@ pub fn weg_pin_item(path: PathBuf) -> Result<()> {
     items.center.insert(0, WegItem::Pinned(data));
     items.sanitize();
     guard.write_weg_items(&items)?;
+
+    // pinning an app is a strong signal of usage, so it is ranked as a launch
+    StartMenuManager::instance().record_launch(&path);
     Ok(())
 }
@@ -79,16 +79,20 @@ use windows::{
         },
         System::{
             Com::{IPersistFile, STGM_READ},
+            DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData},
             Environment::ExpandEnvironmentStringsW,
             LibraryLoader::GetModuleHandleW,
+            Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+            Ole::CF_UNICODETEXT,
             Power::{GetSystemPowerStatus, SetSuspendState, SYSTEM_POWER_STATUS},
             RemoteDesktop::ProcessIdToSessionId,
             Shutdown::{ExitWindowsEx, LockWorkStation, EXIT_WINDOWS_FLAGS, SHUTDOWN_REASON},
             SystemInformation::{GetComputerNameExW, COMPUTER_NAME_FORMAT},
             Threading::{
                 AttachThreadInput, GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId,
-                OpenProcess, OpenProcessToken, QueryFullProcessImageNameW, PROCESS_ACCESS_RIGHTS,
-                PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+                OpenProcess, OpenProcessToken, QueryFullProcessImageNameW, SetPriorityClass,
+                PROCESS_ACCESS_RIGHTS, PROCESS_CREATION_FLAGS, PROCESS_NAME_WIN32,
+                PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
             },
         },
         UI::{
@@ -511,6 +515,15 @@ impl WindowsApi {
         Ok(path.to_os_string())
     }
 
+    /// `priority` is a priority class, e.g. `BELOW_NORMAL_PRIORITY_CLASS`.
+    pub fn set_process_priority(process_id: u32, priority: PROCESS_CREATION_FLAGS) -> Result<()> {
+        let handle = Self::open_process(PROCESS_SET_INFORMATION, false, process_id)?;
+        let result = unsafe { SetPriorityClass(handle, priority) };
+        unsafe { CloseHandle(handle)? };
+        result?;
+        Ok(())
+    }
+
     pub fn get_class(hwnd: HWND) -> Result<String> {
         let mut text: [u16; 512] = [0; 512];
         let len = unsafe { GetClassNameW(hwnd, &mut text) };
@@ -993,6 +1006,38 @@ impl WindowsApi {
         Ok(name.to_string())
     }
 
+    /// Replaces the clipboard content with the text.
+    pub fn set_clipboard_text(text: &str) -> Result<()> {
+        let text = WindowsString::from_str(text);
+        let data = text.as_slice();
+        let size = std::mem::size_of_val(data);
+
+        unsafe {
+            OpenClipboard(None)?;
+            let result = (|| -> Result<()> {
+                EmptyClipboard()?;
+                let memory = GlobalAlloc(GMEM_MOVEABLE, size)?;
+                let ptr = GlobalLock(memory) as *mut u16;
+                if ptr.is_null() {
+                    let _ = GlobalFree(Some(memory));
+                    return Err("Failed to lock clipboard memory".into());
+                }
+                std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+                let _ = GlobalUnlock(memory);
+
+                // on success the memory is owned by the system
+                if let Err(err) = SetClipboardData(CF_UNICODETEXT.0 as u32, Some(HANDLE(memory.0)))
+                {
+                    let _ = GlobalFree(Some(memory));
+                    return Err(err.into());
+                }
+                Ok(())
+            })();
+            CloseClipboard()?;
+            result
+        }
+    }
+
     /// https://learn.microsoft.com/en-us/windows/win32/shell/knownfolderid
     pub fn known_folder(folder_id: windows::core::GUID) -> Result<PathBuf> {
         let path = unsafe { SHGetKnownFolderPath(&folder_id, KF_FLAG_DEFAULT, None)? };
@@ -0,0 +1,91 @@
+use std::{ffi::c_void, mem::zeroed};
+
+use windows::{
+    core::{Interface, Result, HRESULT, HSTRING},
+    Win32::Media::Audio::{EDataFlow, ERole},
+};
+
+/// Activatable class id of the factory
+#[allow(non_upper_case_globals)]
+pub const AudioPolicyConfig: &str = "Windows.Media.Internal.AudioPolicyConfig";
+
+// IID used since Windows 10 21H2, older versions used 2a59116d-6c4f-45e0-a74f-707e3fef9258
+windows_core::imp::define_interface!(
+    IAudioPolicyConfigFactory,
+    IAudioPolicyConfigFactory_Vtbl,
+    0xab3d4648_e242_459f_b02f_541c70306324
+);
+
+windows_core::imp::interface_hierarchy!(
+    IAudioPolicyConfigFactory,
+    windows_core::IUnknown,
+    windows_core::IInspectable
+);
+
+#[allow(non_snake_case)]
+impl IAudioPolicyConfigFactory {
+    /// `device_id` should be the device interface path, an empty string resets the
+    /// process to the system default device.
+    pub unsafe fn SetPersistedDefaultAudioEndpoint(
+        &self,
+        process_id: u32,
+        flow: EDataFlow,
+        role: ERole,
+        device_id: &HSTRING,
+    ) -> Result<()> {
+        (Interface::vtable(self).SetPersistedDefaultAudioEndpoint)(
+            Interface::as_raw(self),
+            process_id,
+            flow,
+            role,
+            std::mem::transmute_copy(device_id),
+        )
+        .ok()
+    }
+
+    pub unsafe fn GetPersistedDefaultAudioEndpoint(
+        &self,
+        process_id: u32,
+        flow: EDataFlow,
+        role: ERole,
+    ) -> Result<HSTRING> {
+        let mut result__ = zeroed::<HSTRING>();
+        (Interface::vtable(self).GetPersistedDefaultAudioEndpoint)(
+            Interface::as_raw(self),
+            process_id,
+            flow,
+            role,
+            &mut result__ as *mut HSTRING as *mut *mut c_void,
+        )
+        .map(|| result__)
+    }
+
+    pub unsafe fn ClearAllPersistedApplicationDefaultEndpoints(&self) -> Result<()> {
+        (Interface::vtable(self).ClearAllPersistedApplicationDefaultEndpoints)(Interface::as_raw(
+            self,
+        ))
+        .ok()
+    }
+}
+
+type Unknown = unsafe extern "system" fn(this: *mut c_void) -> HRESULT;
+
+#[repr(C)]
+#[doc(hidden)]
+#[allow(non_snake_case, non_camel_case_types)]
+pub struct IAudioPolicyConfigFactory_Vtbl {
+    pub base__: windows_core::IInspectable_Vtbl,
+    // volume groups, ringer and chat apps, not used
+    pub __reserved: [Unknown; 19],
+    pub SetPersistedDefaultAudioEndpoint:
+        unsafe extern "system" fn(this: *mut c_void, u32, EDataFlow, ERole, *mut c_void) -> HRESULT,
+    pub GetPersistedDefaultAudioEndpoint: unsafe extern "system" fn(
+        this: *mut c_void,
+        u32,
+        EDataFlow,
+        ERole,
+        *mut *mut c_void,
+    ) -> HRESULT,
+    pub ClearAllPersistedApplicationDefaultEndpoints:
+        unsafe extern "system" fn(this: *mut c_void) -> HRESULT,
+}
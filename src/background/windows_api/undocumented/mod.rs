@@ -1,9 +1,15 @@
 mod audio_policy_config;
+mod audio_policy_config_factory;
 
 pub use audio_policy_config::*;
-use windows::Win32::{
-    Media::Audio::{eCommunications, eConsole, eMultimedia},
-    Security::SE_DEBUG_NAME,
+pub use audio_policy_config_factory::*;
+use windows::{
+    core::HSTRING,
+    Win32::{
+        Media::Audio::{eCapture, eCommunications, eConsole, eMultimedia, EDataFlow},
+        Security::SE_DEBUG_NAME,
+        System::WinRT::RoGetActivationFactory,
+    },
 };
 
 use crate::{error::Result, windows_api::string_utils::WindowsString};
@@ -30,4 +36,34 @@ impl WindowsApi {
             Ok(())
         })
     }
+
+    /// Sets the preferred device of the process for the given flow, None resets it to the
+    /// system default. `device_id` is the endpoint id as returned by `IMMDevice::GetId`.
+    pub fn set_process_audio_endpoint(
+        process_id: u32,
+        flow: EDataFlow,
+        device_id: Option<&str>,
+    ) -> Result<()> {
+        // the factory expects the device interface path instead of the endpoint id
+        let device_path = match device_id {
+            Some(id) => {
+                let interface = if flow == eCapture {
+                    "{2eef81be-33fa-4800-9670-1cd474972c3f}"
+                } else {
+                    "{e6327cad-dcec-4949-ae8a-991e976a79d2}"
+                };
+                HSTRING::from(format!("\\\\?\\SWD#MMDEVAPI#{id}#{interface}"))
+            }
+            None => HSTRING::new(),
+        };
+
+        Com::run_with_context(|| unsafe {
+            let factory: IAudioPolicyConfigFactory =
+                RoGetActivationFactory(&HSTRING::from(AudioPolicyConfig))?;
+            for role in [eConsole, eMultimedia, eCommunications] {
+                factory.SetPersistedDefaultAudioEndpoint(process_id, flow, role, &device_path)?;
+            }
+            Ok(())
+        })
+    }
 }
@@ -185,7 +185,6 @@ impl Window {
         self.process().program_display_name()
     }
 
-    #[allow(dead_code)]
     pub fn outer_rect(&self) -> Result<Rect> {
         let rect = WindowsApi::get_outer_window_rect(self.hwnd())?;
         Ok(Rect {
@@ -366,7 +365,6 @@ impl Window {
         }
     }
 
-    #[allow(dead_code)]
     pub fn set_position(&self, rect: &RECT, flags: SET_WINDOW_POS_FLAGS) -> Result<()> {
         if self.process().open_handle().is_ok() {
             WindowsApi::set_position(self.hwnd(), None, rect, flags)
@@ -187,6 +187,8 @@ fn hotkey_action_to_cli_command(action: SluHotkeyAction) -> Option<Vec<String>>
         // wallpaper manager
         CycleWallpaperNext => cmd!["wallpaper", "next"],
         CycleWallpaperPrev => cmd!["wallpaper", "prev"],
+        // notifications
+        ToggleFocusMode { index } => cmd!["notifications", "toggle-focus-mode", index],
         // Weg
         StartWegApp { index } => cmd!["weg", "foreground-or-run-app", index],
         // Window Manager
@@ -137,6 +137,10 @@ export function WallSettings() {
                   label: t("wall.extend"),
                   value: MultimonitorBehaviour.Extend,
                 },
+                {
+                  label: t("wall.span"),
+                  value: MultimonitorBehaviour.Span,
+                },
               ]}
             />
           }
@@ -55,6 +55,8 @@ export const EditAppModal = ({ idx, onCancel, onSave, isNew, open, readonlyApp }
 
   const onSelectMonitor = (value: number | null) => setApp({ ...app, boundMonitor: value });
   const onSelectWorkspace = (value: number | null) => setApp({ ...app, boundWorkspace: value });
+  const updateWorkspaceName = (e: React.ChangeEvent<HTMLInputElement>) =>
+    setApp({ ...app, boundWorkspaceName: e.currentTarget.value || null });
 
   const onChangeOption = (option: AppExtraFlag, checked: boolean) => {
     setApp({
@@ -145,6 +147,14 @@ export const EditAppModal = ({ idx, onCancel, onSave, isNew, open, readonlyApp }
                 onChange={onSelectWorkspace}
               />
             </SettingsOption>
+            <SettingsOption>
+              <span>{t("apps_configurations.app.workspace_name")}</span>
+              <Input
+                value={app.boundWorkspaceName || ""}
+                placeholder={t("apps_configurations.app.workspace_name_placeholder")}
+                onChange={updateWorkspaceName}
+              />
+            </SettingsOption>
           </SettingsSubGroup>
         </SettingsGroup>
 
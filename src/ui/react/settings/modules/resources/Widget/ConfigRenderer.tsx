@@ -1,5 +1,7 @@
+import { getHiddenWidgetSettings, getWidgetSettingsDefaultValues, isWidgetConfigGroupVisible } from "@seelen-ui/lib";
 import {
   type WidgetConfigDefinition,
+  type WidgetConfigGroup,
   WidgetSelectSubtype,
   type WidgetSettingItem,
   type WidgetSettingsDeclarationList,
@@ -7,7 +9,7 @@ import {
 import { ResourceText } from "libs/ui/react/components/ResourceText/index.tsx";
 import { Button, ColorPicker, Input, InputNumber, Select, Slider, Switch, Tooltip } from "antd";
 import type { ReactNode } from "react";
-import { useMemo } from "react";
+import { useMemo, useState } from "react";
 
 import { SettingsGroup, SettingsOption, SettingsSubGroup } from "../../../components/SettingsBox/index.tsx";
 import Compact from "antd/es/space/Compact";
@@ -25,6 +27,16 @@ interface Props {
 }
 
 export function RenderBySettingsDeclaration({ definitions, values, onConfigChange }: Props) {
+  // visibility conditions are evaluated over the values including the defaults
+  const valuesWithDefaults = useMemo(
+    () => ({ ...getWidgetSettingsDefaultValues(definitions), ...values }),
+    [definitions, values],
+  );
+  const hidden = useMemo(
+    () => getHiddenWidgetSettings(definitions, valuesWithDefaults),
+    [definitions, valuesWithDefaults],
+  );
+
   return (
     <>
       {definitions.map((definition, idx) => (
@@ -32,6 +44,8 @@ export function RenderBySettingsDeclaration({ definitions, values, onConfigChang
           key={idx}
           definition={definition}
           values={values}
+          valuesWithDefaults={valuesWithDefaults}
+          hidden={hidden}
           onConfigChange={onConfigChange}
           nestLevel={0}
         />
@@ -45,46 +59,58 @@ export function RenderBySettingsDeclaration({ definitions, values, onConfigChang
 interface WidgetConfigDefinitionProps {
   definition: WidgetConfigDefinition;
   values: Record<string, any>;
+  valuesWithDefaults: Record<string, unknown>;
+  // keys of the hidden settings items
+  hidden: Set<string>;
   onConfigChange: (key: string, value: any) => void;
   nestLevel: number;
 }
 
-function WidgetConfigDefinition({
-  definition,
-  values,
-  onConfigChange,
-  nestLevel,
-}: WidgetConfigDefinitionProps) {
-  const content = renderContent(definition, values, onConfigChange, nestLevel);
-
-  return nestLevel === 0 ? <SettingsGroup>{content}</SettingsGroup> : content;
-}
+function WidgetConfigDefinition(props: WidgetConfigDefinitionProps) {
+  const { definition, valuesWithDefaults, hidden, nestLevel } = props;
 
-function renderContent(
-  definition: WidgetConfigDefinition,
-  values: Record<string, any>,
-  onConfigChange: (key: string, value: any) => void,
-  nestLevel: number,
-): ReactNode {
   // Check if it's a group (has "group" property)
   if ("group" in definition) {
-    return (
-      <SettingsSubGroup label={<ResourceText text={definition.group.label} />}>
-        {definition.group.items.map((item, idx) => (
-          <WidgetConfigDefinition
-            key={idx}
-            definition={item}
-            values={values}
-            onConfigChange={onConfigChange}
-            nestLevel={nestLevel + 1}
-          />
-        ))}
-      </SettingsSubGroup>
-    );
+    if (!isWidgetConfigGroupVisible(definition.group, valuesWithDefaults)) {
+      return null;
+    }
+    const content = <WidgetConfigGroupRenderer {...props} group={definition.group} />;
+    return nestLevel === 0 ? <SettingsGroup>{content}</SettingsGroup> : content;
   }
 
   // It's a setting item
-  return <WidgetSettingItemRenderer def={definition} values={values} onConfigChange={onConfigChange} />;
+  if (hidden.has(definition.key)) {
+    return null;
+  }
+  const content = (
+    <WidgetSettingItemRenderer def={definition} values={props.values} onConfigChange={props.onConfigChange} />
+  );
+  return nestLevel === 0 ? <SettingsGroup>{content}</SettingsGroup> : content;
+}
+
+function WidgetConfigGroupRenderer({
+  group,
+  nestLevel,
+  ...props
+}: WidgetConfigDefinitionProps & { group: WidgetConfigGroup }) {
+  const [collapsed, setCollapsed] = useState(group.collapsible && group.collapsed);
+
+  const label = group.collapsible
+    ? (
+      <span style={{ cursor: "pointer" }} onClick={() => setCollapsed(!collapsed)}>
+        <Icon iconName={collapsed ? "FaChevronRight" : "FaChevronDown"} /> <ResourceText text={group.label} />
+      </span>
+    )
+    : <ResourceText text={group.label} />;
+
+  return (
+    <SettingsSubGroup label={label}>
+      {!collapsed &&
+        group.items.map((item, idx) => (
+          <WidgetConfigDefinition key={idx} {...props} definition={item} nestLevel={nestLevel + 1} />
+        ))}
+    </SettingsSubGroup>
+  );
 }
 
 // ================================================
@@ -100,18 +126,6 @@ function WidgetSettingItemRenderer({
   values,
   onConfigChange,
 }: WidgetSettingItemRendererProps) {
-  // Check if all dependencies are met
-  const isDependencyMet = useMemo(() => {
-    if (!def.dependencies || def.dependencies.length === 0) {
-      return true;
-    }
-    return def.dependencies.every((depKey) => !!values[depKey]);
-  }, [def.dependencies, values]);
-
-  if (!isDependencyMet) {
-    return null;
-  }
-
   const action = renderInput(def, values, onConfigChange);
 
   return (
@@ -115,6 +115,7 @@ export function getHotkeysGroups(hotkeys: SluHotkey[]): Groups {
         groups.virtualDesktop.send.push(hotkey);
         break;
       // misc
+      case "toggle_focus_mode":
       case "misc_open_settings":
       case "misc_toggle_lock_tracing":
       case "misc_toggle_win_event_tracing":
@@ -9,7 +9,7 @@ import type { HTMLAttributes } from "preact/compat";
 import { useCallback, useMemo } from "preact/compat";
 
 import { EvaluateAction } from "../app/actionEvaluator.ts";
-import { $toolbar_state } from "../../shared/state/items.ts";
+import { $rendered_items, $toolbar_state } from "../../shared/state/items.ts";
 import { useItemContextMenu } from "./ContextMenu.tsx";
 import { ElementsFromEvaluated, StringFromEvaluated, useSandboxedCode } from "./EvaluatedComponents.tsx";
 import { useRemoteData } from "../app/hooks/useRemoteData.ts";
@@ -23,10 +23,10 @@ export interface InnerItemProps {
 const isReorderDisabled = computed(() => $toolbar_state.value.isReorderDisabled);
 
 function InnerItemComponent({ module }: InnerItemProps) {
-  const { id, onClick, style, remoteData = {} } = module;
+  const { id, onClick, style } = module;
 
   const { fetching, data: extraVars } = useItemScope(module.scopes);
-  const fetchedData = useRemoteData(remoteData);
+  const fetchedData = useRemoteData(id);
   const { onContextMenu } = useItemContextMenu(id);
 
   const { attributes, listeners, setNodeRef, transform, transition, isDragging } = useSortable({
@@ -46,9 +46,20 @@ function InnerItemComponent({ module }: InnerItemProps) {
     fetchedData,
   });
 
-  const content = useSandboxedCode({ code: module.template, scope });
-  const tooltip = module.tooltip ? useSandboxedCode({ code: module.tooltip, scope }) : null;
-  const badge = module.badge ? useSandboxedCode({ code: module.badge, scope }) : null;
+  const rendered = module.backgroundEvaluation ? $rendered_items.value[id] : undefined;
+  const content = module.backgroundEvaluation
+    ? rendered?.template
+    : useSandboxedCode({ code: module.template, scope });
+  const tooltip = module.backgroundEvaluation
+    ? rendered?.tooltip
+    : module.tooltip
+    ? useSandboxedCode({ code: module.tooltip, scope })
+    : null;
+  const badge = module.backgroundEvaluation
+    ? rendered?.badge
+    : module.badge
+    ? useSandboxedCode({ code: module.badge, scope })
+    : null;
 
   // Memoize callbacks to prevent unnecessary re-renders
   const handleClick = useCallback(() => {
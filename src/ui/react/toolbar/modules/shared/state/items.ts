@@ -1,6 +1,6 @@
 import { effect, signal } from "@preact/signals";
-import { invoke, PluginList, SeelenCommand } from "@seelen-ui/lib";
-import type { PluginId, ToolbarItem2, ToolbarState } from "@seelen-ui/lib/types";
+import { invoke, PluginList, SeelenCommand, SeelenEvent, subscribe } from "@seelen-ui/lib";
+import type { PluginId, RenderedToolbarItem, ToolbarItem2, ToolbarState } from "@seelen-ui/lib/types";
 
 import { matchIds } from "../utils.ts";
 import { debounce } from "lodash";
@@ -20,6 +20,22 @@ await PluginList.onChange((list) => {
   $plugins.value = list.forCurrentWidget();
 });
 
+function byId(items: RenderedToolbarItem[]) {
+  return Object.fromEntries(items.map((item) => [item.id, item]));
+}
+
+// items with background evaluation enabled are evaluated by the background
+export const $rendered_items = signal(byId(await invoke(SeelenCommand.GetRenderedToolbarItems)));
+await subscribe(SeelenEvent.ToolbarItemsRendered, ({ payload }) => {
+  $rendered_items.value = byId(payload);
+});
+
+// remote data is fetched by the background and shared between all the toolbars
+export const $remote_data = signal(await invoke(SeelenCommand.GetToolbarRemoteData));
+await subscribe(SeelenEvent.ToolbarRemoteDataChanged, ({ payload }) => {
+  $remote_data.value = payload;
+});
+
 export const saveTbState = debounce(async (items: ToolbarState) => {
   console.trace("Saving toolbar state");
   await invoke(SeelenCommand.StateWriteToolbarItems, { items });
@@ -1,5 +1,12 @@
 import { batch, useComputed, useSignal, useSignalEffect } from "@preact/signals";
-import { MultimonitorBehaviour, type PhysicalMonitor, type WallpaperId } from "@seelen-ui/lib/types";
+import { invoke, SeelenCommand } from "@seelen-ui/lib";
+import {
+  type MonitorId,
+  MultimonitorBehaviour,
+  type PhysicalMonitor,
+  type WallpaperId,
+  type WallpaperSpan,
+} from "@seelen-ui/lib/types";
 import { Wallpaper as WallpaperComponent } from "libs/ui/react/components/Wallpaper/index.tsx";
 import { useTranslation } from "react-i18next";
 
@@ -16,9 +23,10 @@ import { $relativeMonitors } from "./derived.ts";
 
 export function MonitorContainers() {
   const isExtendMode = $settings.value.multimonitorBehaviour === MultimonitorBehaviour.Extend;
+  const isSpanMode = $settings.value.multimonitorBehaviour === MultimonitorBehaviour.Span;
+  const primaryMonitor = $monitors.value.find((m) => m.isPrimary) || $monitors.value[0];
 
   if (isExtendMode) {
-    const primaryMonitor = $monitors.value.find((m) => m.isPrimary) || $monitors.value[0];
     if (!primaryMonitor) {
       console.error("Primary monitor not found");
       return null;
@@ -26,8 +34,10 @@ export function MonitorContainers() {
     return <Monitor extended monitor={primaryMonitor} />;
   }
 
+  // on span mode all the monitors show pieces of the same wallpaper, the one of the primary monitor
+  const spanSourceId = isSpanMode ? primaryMonitor?.id : undefined;
   return $relativeMonitors.value.map((monitor) => {
-    return <Monitor key={monitor.id} monitor={monitor} />;
+    return <Monitor key={monitor.id} monitor={monitor} spanned={isSpanMode} wallpaperSourceId={spanSourceId} />;
   });
 }
 
@@ -39,7 +49,18 @@ export function MonitorContainers() {
  *    - old wallpaper will persist for 1 second during transition
  * 3. performance mode will disable video wallpapers
  */
-function Monitor({ monitor, extended }: { monitor: PhysicalMonitor; extended?: boolean }) {
+function Monitor({
+  monitor,
+  extended,
+  spanned,
+  wallpaperSourceId,
+}: {
+  monitor: PhysicalMonitor;
+  extended?: boolean;
+  spanned?: boolean;
+  // monitor whose active wallpaper is shown, defaults to this monitor
+  wallpaperSourceId?: MonitorId;
+}) {
   const { t } = useTranslation();
   const $render_old = useSignal(false);
   const $current_was_loaded = useSignal(false);
@@ -56,7 +77,7 @@ function Monitor({ monitor, extended }: { monitor: PhysicalMonitor; extended?: b
 
   // Get wallpaper from active workspace for this monitor
   const $active_wallpaper_id = useComputed(() => {
-    const monitorData = $virtualDesktops.value.monitors[monitor.id];
+    const monitorData = $virtualDesktops.value.monitors[wallpaperSourceId || monitor.id];
     if (!monitorData) return null;
 
     const activeWorkspace = monitorData.workspaces.find(
@@ -81,6 +102,21 @@ function Monitor({ monitor, extended }: { monitor: PhysicalMonitor; extended?: b
     }
   });
 
+  // crop of the wallpaper for this monitor when spanning it across all monitors
+  const $span = useSignal<WallpaperSpan | null>(null);
+  useSignalEffect(() => {
+    // refresh on monitors or wallpaper settings (offsets) changes
+    $monitors.value;
+    $settings.value.byWallpaper;
+    if (!spanned) {
+      $span.value = null;
+      return;
+    }
+    invoke(SeelenCommand.WallpaperGetSpan, { wallpaperId: $current_id.value })
+      .then((span) => ($span.value = span))
+      .catch(console.error);
+  });
+
   const oldWallpaper = $wallpapers.value.find((wallpaper) => wallpaper.id === $old_id.value);
   const wallpaper = $wallpapers.value.find((wallpaper) => wallpaper.id === $current_id.value);
 
@@ -96,6 +132,30 @@ function Monitor({ monitor, extended }: { monitor: PhysicalMonitor; extended?: b
   let width = extended ? "100%" : (monitor.rect.right - monitor.rect.left) / globalThis.devicePixelRatio;
   let height = extended ? "100%" : (monitor.rect.bottom - monitor.rect.top) / globalThis.devicePixelRatio;
 
+  const crop = $span.value?.monitors[monitor.id];
+  const cropScale = crop ? (monitor.rect.right - monitor.rect.left) / globalThis.devicePixelRatio / crop.width : 1;
+
+  const wallpapers = [
+    $render_old.value && (
+      <WallpaperComponent
+        key={oldWallpaper?.id || "themed"}
+        definition={oldWallpaper}
+        config={oldWallpaper && $settings.value.byWallpaper[oldWallpaper.id]}
+        paused // inmediately pause exiting wallpaper, to avoid gpu usage.
+        out={$current_was_loaded.value}
+      />
+    ),
+    <WallpaperComponent
+      key={wallpaper?.id || "themed"}
+      definition={wallpaper}
+      config={wallpaper && $settings.value.byWallpaper[wallpaper.id]}
+      onLoad={() => ($current_was_loaded.value = true)}
+      paused={$paused.value}
+      muted={$muted.value || !monitor.isPrimary}
+      pausedMessage={$performance_mode.value !== "Disabled" ? t("paused_by_performance_mode") : undefined}
+    />,
+  ];
+
   return (
     <div
       className="monitor"
@@ -105,28 +165,25 @@ function Monitor({ monitor, extended }: { monitor: PhysicalMonitor; extended?: b
         top,
         width,
         height,
+        overflow: "hidden",
       }}
     >
-      {[
-        $render_old.value && (
-          <WallpaperComponent
-            key={oldWallpaper?.id || "themed"}
-            definition={oldWallpaper}
-            config={oldWallpaper && $settings.value.byWallpaper[oldWallpaper.id]}
-            paused // inmediately pause exiting wallpaper, to avoid gpu usage.
-            out={$current_was_loaded.value}
-          />
-        ),
-        <WallpaperComponent
-          key={wallpaper?.id || "themed"}
-          definition={wallpaper}
-          config={wallpaper && $settings.value.byWallpaper[wallpaper.id]}
-          onLoad={() => ($current_was_loaded.value = true)}
-          paused={$paused.value}
-          muted={$muted.value || !monitor.isPrimary}
-          pausedMessage={$performance_mode.value !== "Disabled" ? t("paused_by_performance_mode") : undefined}
-        />,
-      ]}
+      {crop && $span.value
+        ? (
+          <div
+            className="monitor-span"
+            style={{
+              position: "absolute",
+              left: -crop.x * cropScale,
+              top: -crop.y * cropScale,
+              width: $span.value.width * cropScale,
+              height: $span.value.height * cropScale,
+            }}
+          >
+            {wallpapers}
+          </div>
+        )
+        : wallpapers}
     </div>
   );
 }
@@ -1,3 +1,6 @@
+import { SeelenEvent } from "@seelen-ui/lib";
+import type { WidgetReloadError } from "@seelen-ui/lib/types";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { disableAnimationsOnPerformanceMode } from "libs/ui/react/utils/performance";
 
 import "@shared/styles/colors.css";
@@ -11,11 +14,20 @@ if (html) {
   document.body.innerHTML = html;
 }
 
-if (css) {
-  const style = document.createElement("style");
-  style.textContent = css;
-  document.head.appendChild(style);
-}
+const style = document.createElement("style");
+style.textContent = css || "";
+document.head.appendChild(style);
+
+// hot reload, only emitted while developer tools are enabled
+const webview = getCurrentWebviewWindow();
+webview.listen<string>(SeelenEvent.WidgetCssChanged, ({ payload }) => {
+  style.textContent = payload;
+});
+webview.listen<WidgetReloadError>(SeelenEvent.WidgetHotReloadFailed, ({ payload }) => {
+  if (payload.widgetId === window.__SLU_WIDGET.id) {
+    console.error(`Hot reload failed on ${payload.path}: ${payload.error}`);
+  }
+});
 
 if (js) {
   const script = document.createElement("script");